@@ -5,14 +5,16 @@
 //! tests later down the line.
 
 use self::{lints::LintCrate, toolchain::Toolchain};
-use crate::config::LintDependencyEntry;
+use crate::config::{LintDependencyEntry, LintLevel};
 use crate::error::prelude::*;
 use crate::observability::display::{self, print_stage};
 use crate::observability::prelude::*;
 use camino::Utf8PathBuf;
+use cargo_metadata::Metadata;
 use itertools::Itertools;
 use std::collections::BTreeMap;
 
+pub mod cache;
 pub mod cargo;
 pub mod driver;
 pub mod lints;
@@ -22,6 +24,7 @@ pub mod toolchain;
 ///
 /// It's assumed that all paths in this struct are absolute paths.
 #[derive(Debug)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Config {
     /// The base directory used by Marker to fetch and compile lints.
     /// This will default to something like `./target/marker`.
@@ -32,34 +35,136 @@ pub struct Config {
     pub marker_dir: Utf8PathBuf,
     /// The list of lints.
     pub lints: BTreeMap<String, LintDependencyEntry>,
+    /// The lint crates and lint levels for individual packages. All lint
+    /// crates are used for every package, if this is empty.
+    pub packages: Vec<PackageLints>,
     /// Additional flags, which should be passed to rustc during the compilation
     /// of crates.
     pub build_rustc_flags: String,
     /// Indicates if this is a release or debug build.
     pub debug_build: bool,
+    /// Indicates if the driver should report every construct, which can't be
+    /// represented by Marker's API and was replaced by a placeholder.
+    pub report_unsupported: bool,
+    /// Indicates if rustc's flattening of `format_args!` calls should be
+    /// disabled in the driver.
+    pub unflatten_format_args: bool,
+    /// The path of the `Marker.lock` file, which pins the versions of the lint
+    /// crates and their dependencies. This is `None`, if the lint crates were
+    /// specified on the command line.
+    pub lock_file: Option<Utf8PathBuf>,
+    /// Requires the `Marker.lock` file to be up to date, like Cargo's `--locked` flag.
+    pub locked: bool,
+    /// Prevents Cargo from accessing the network, like Cargo's `--offline` flag.
+    pub offline: bool,
+    /// The directory of the global cache for compiled lint crates, which is
+    /// shared between workspaces. This is `None`, if the cache is disabled.
+    pub global_cache: Option<Utf8PathBuf>,
+    /// The crate names of all workspace members, with dashes replaced by
+    /// underscores, like rustc uses them.
+    pub workspace_crates: Vec<String>,
     pub toolchain: Toolchain,
 }
 
 impl Config {
-    pub fn try_base_from(toolchain: Toolchain) -> Result<Self> {
-        Ok(Self {
-            marker_dir: toolchain.find_target_dir()?.join("marker"),
+    /// Creates the base configuration for the given toolchain and the metadata
+    /// of the linted workspace.
+    pub fn base_from(toolchain: Toolchain, metadata: &Metadata) -> Self {
+        let workspace_crates = metadata
+            .workspace_packages()
+            .iter()
+            .flat_map(|package| &package.targets)
+            .filter(|target| {
+                target
+                    .kind
+                    .iter()
+                    .any(|kind| matches!(kind.as_str(), "lib" | "rlib" | "dylib" | "proc-macro"))
+            })
+            .map(|target| target.name.replace('-', "_"))
+            .collect();
+
+        Self {
+            marker_dir: metadata.target_directory.join("marker"),
             lints: BTreeMap::default(),
+            packages: vec![],
             build_rustc_flags: String::new(),
             debug_build: false,
+            report_unsupported: false,
+            unflatten_format_args: false,
+            lock_file: None,
+            locked: false,
+            offline: false,
+            global_cache: None,
+            workspace_crates,
             toolchain,
-        })
+        }
+    }
+
+    /// The arguments, which should be passed to Cargo commands, that resolve
+    /// or fetch the dependencies of lint crates.
+    fn cargo_lock_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if self.locked {
+            args.push("--locked".to_string());
+        }
+        if self.offline {
+            args.push("--offline".to_string());
+        }
+        args
     }
 
+    /// The base directory for the target directories of the lint crates.
     fn markers_target_dir(&self) -> Utf8PathBuf {
         self.marker_dir.join("target")
     }
+}
 
-    fn lint_crate_dir(&self) -> Utf8PathBuf {
-        self.marker_dir.join("lints")
+#[cfg(test)]
+impl Config {
+    /// Creates a configuration for unit tests, which doesn't require a
+    /// toolchain or a Cargo workspace.
+    pub(crate) fn for_tests(marker_dir: &camino::Utf8Path) -> Self {
+        Self {
+            marker_dir: marker_dir.to_path_buf(),
+            lints: BTreeMap::default(),
+            packages: vec![],
+            build_rustc_flags: String::new(),
+            debug_build: true,
+            report_unsupported: false,
+            unflatten_format_args: false,
+            lock_file: None,
+            locked: false,
+            offline: false,
+            global_cache: None,
+            workspace_crates: vec![],
+            toolchain: Toolchain {
+                driver_path: Utf8PathBuf::new(),
+                cargo: cargo::Cargo::default(),
+            },
+        }
     }
 }
 
+/// The lint crates and lint levels, which are used for a package.
+#[derive(Debug)]
+pub struct PackageLints {
+    /// The directory of the `Cargo.toml` file of the package. This is `None`
+    /// for the configuration, which is used for all other packages.
+    pub manifest_dir: Option<Utf8PathBuf>,
+    /// The names of the lint crates, which should be loaded.
+    pub lints: Vec<String>,
+    /// The levels of lints, in the order they should be applied.
+    pub levels: Vec<(String, LintLevel)>,
+}
+
+/// Updates the `Marker.lock` file, by resolving the versions of the lint crates
+/// and their dependencies again. If `packages` is not empty, only the specified
+/// packages will be updated.
+pub fn update_lock_file(config: &Config, packages: &[String]) -> Result {
+    print_stage("updating lock file");
+    lints::update_lock_file(config, packages)
+}
+
 /// This struct contains all information to use rustc as a driver.
 #[derive(Debug)]
 pub struct CheckInfo {
@@ -70,7 +175,7 @@ pub fn prepare_check(config: &Config) -> Result<CheckInfo> {
     print_stage("compiling lints");
     let lints = lints::build_lints(config)?
         .iter()
-        .map(|LintCrate { name, file }| format!("{name}:{file}"))
+        .map(|LintCrate { name, file, .. }| format!("{name}:{file}"))
         .join(";");
 
     #[rustfmt::skip]
@@ -81,10 +186,44 @@ pub fn prepare_check(config: &Config) -> Result<CheckInfo> {
     if let Some(toolchain) = &config.toolchain.cargo.toolchain {
         env.push(("RUSTUP_TOOLCHAIN", toolchain.into()));
     }
+    if config.report_unsupported {
+        env.push(("MARKER_REPORT_UNSUPPORTED", "1".into()));
+    }
+    if config.unflatten_format_args {
+        env.push(("MARKER_UNFLATTEN_FORMAT_ARGS", "1".into()));
+    }
+    if !config.workspace_crates.is_empty() {
+        env.push(("MARKER_WORKSPACE_CRATES", config.workspace_crates.join(",")));
+    }
+    if config.packages.iter().any(|pkg| pkg.manifest_dir.is_some()) {
+        let lints = package_env_value(&config.packages, |pkg| pkg.lints.join(","));
+        env.push(("MARKER_PACKAGE_LINT_CRATES", lints));
+    }
+    if config.packages.iter().any(|pkg| !pkg.levels.is_empty()) {
+        let levels = package_env_value(&config.packages, |pkg| {
+            pkg.levels
+                .iter()
+                .map(|(lint, level)| format!("{}:{lint}", level.as_str()))
+                .join(",")
+        });
+        env.push(("MARKER_LINT_LEVELS", levels));
+    }
 
     Ok(CheckInfo { env })
 }
 
+/// Creates the value of an environment variable with an entry per package.
+/// See the `README.md` of `marker_adapter` for the format.
+fn package_env_value(packages: &[PackageLints], entry: impl Fn(&PackageLints) -> String) -> String {
+    packages
+        .iter()
+        .map(|pkg| {
+            let dir = pkg.manifest_dir.as_ref().map_or("", |dir| dir.as_str());
+            format!("{dir}={}", entry(pkg))
+        })
+        .join("\n")
+}
+
 pub fn run_check(config: &Config, info: CheckInfo, additional_cargo_args: &[String]) -> Result {
     let stage = "linting";
     print_stage(stage);
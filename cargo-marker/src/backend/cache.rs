@@ -0,0 +1,333 @@
+//! This module implements the global cache for compiled lint crates. The cache
+//! is shared between all workspaces, which use the same lint crates, to avoid
+//! compiling them once per workspace.
+//!
+//! Only lint crates, which are fully pinned, are stored in the cache. This is
+//! the case for registry and git crates without `path` dependencies. Every
+//! entry is a directory, containing the dynamic library and an `entry.json`
+//! file, with the information displayed by `cargo marker cache list`. The
+//! name of the directory contains a hash of the crate checksums, the driver
+//! version and the rustc toolchain.
+
+use crate::error::prelude::*;
+use crate::observability::prelude::*;
+use camino::{Utf8Path, Utf8PathBuf};
+
+/// The environment variable, which can be used to specify the directory of
+/// the global cache. Setting it also enables the cache.
+pub const CACHE_DIR_ENV: &str = "MARKER_CACHE_DIR";
+
+const ENTRY_FILE_NAME: &str = "entry.json";
+const TEMP_DIR_PREFIX: &str = ".tmp-";
+
+/// Returns the directory of the global cache, if it's enabled. The cache is
+/// enabled, if it was `requested` or if the `MARKER_CACHE_DIR` environment
+/// variable is set.
+pub fn enabled_cache_dir(requested: bool) -> Result<Option<Utf8PathBuf>> {
+    if requested || std::env::var_os(CACHE_DIR_ENV).is_some() {
+        return cache_dir().map(Some);
+    }
+
+    Ok(None)
+}
+
+/// Returns the directory of the global cache. This is the value of the
+/// `MARKER_CACHE_DIR` environment variable or `$CARGO_HOME/marker` otherwise.
+pub fn cache_dir() -> Result<Utf8PathBuf> {
+    if let Some(dir) = env_path(CACHE_DIR_ENV)? {
+        return Ok(dir);
+    }
+
+    if let Some(cargo_home) = env_path("CARGO_HOME")? {
+        return Ok(cargo_home.join("marker"));
+    }
+
+    let home = match env_path("HOME")? {
+        Some(home) => home,
+        None => env_path("USERPROFILE")?.context(|| {
+            format!(
+                "Failed to determine the Cargo home directory. Try setting the {CACHE_DIR_ENV} environment variable"
+            )
+        })?,
+    };
+
+    Ok(home.join(".cargo").join("marker"))
+}
+
+fn env_path(var: &str) -> Result<Option<Utf8PathBuf>> {
+    let Some(value) = std::env::var_os(var).filter(|value| !value.is_empty()) else {
+        return Ok(None);
+    };
+
+    Utf8PathBuf::from_path_buf(value.into())
+        .map(Some)
+        .map_err(|path| Error::root(format!("The path in `{var}` is not valid UTF-8: {}", path.display())))
+}
+
+/// The information stored for every compiled lint crate in the cache.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct CacheEntry {
+    /// The name of the lint crate
+    pub name: String,
+    /// The version of the lint crate
+    pub version: String,
+    /// The source of the lint crate, as reported by Cargo
+    pub source: String,
+    /// The rustc toolchain, that was used to compile the crate
+    pub toolchain: String,
+    /// The version of the driver, that the crate was compiled for
+    pub driver_version: String,
+    /// The file name of the dynamic library
+    file_name: String,
+    /// The directory of this entry
+    #[serde(skip)]
+    dir: Utf8PathBuf,
+}
+
+impl CacheEntry {
+    pub fn new(name: String, version: String, source: String, toolchain: String, driver_version: String) -> Self {
+        Self {
+            name,
+            version,
+            source,
+            toolchain,
+            driver_version,
+            file_name: String::new(),
+            dir: Utf8PathBuf::new(),
+        }
+    }
+
+    /// The absolute path of the cached dynamic library.
+    pub fn file(&self) -> Utf8PathBuf {
+        self.dir.join(&self.file_name)
+    }
+}
+
+/// The global cache for compiled lint crates.
+#[derive(Debug)]
+pub struct Cache {
+    dir: Utf8PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: Utf8PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn dir(&self) -> &Utf8Path {
+        &self.dir
+    }
+
+    /// Returns the path of the cached dynamic library for the given key, if
+    /// the cache contains it.
+    pub fn lookup(&self, key: &str) -> Option<Utf8PathBuf> {
+        let entry = read_entry(&self.dir.join(key)).ok()?;
+        let file = entry.file();
+        file.is_file().then_some(file)
+    }
+
+    /// Copies the compiled lint crate into the cache and returns the path of
+    /// the cached dynamic library.
+    ///
+    /// The entry is created in a temporary directory first, which is renamed
+    /// afterwards. This prevents other processes from loading incomplete entries.
+    pub fn store(&self, key: &str, mut entry: CacheEntry, file: &Utf8Path) -> Result<Utf8PathBuf> {
+        let entry_dir = self.dir.join(key);
+        let temp_dir = self.dir.join(format!("{TEMP_DIR_PREFIX}{}-{key}", std::process::id()));
+
+        std::fs::create_dir_all(&temp_dir).context(|| format!("Failed to create the cache directory {temp_dir}"))?;
+
+        let result = (|| {
+            entry.file_name = file
+                .file_name()
+                .unwrap_or_else(|| panic!("the compiled lint crate must be a file: {file}"))
+                .to_string();
+            std::fs::copy(file, temp_dir.join(&entry.file_name))
+                .context(|| format!("Failed to copy {file} into the cache directory {temp_dir}"))?;
+
+            let entry_file = temp_dir.join(ENTRY_FILE_NAME);
+            let content = serde_json::to_string_pretty(&entry).expect("the cache entry can be serialized");
+            std::fs::write(&entry_file, content).context(|| format!("Failed to write {entry_file}"))?;
+
+            // Another process might have stored the same crate in the meantime,
+            // in which case the existing entry is used.
+            if std::fs::rename(&temp_dir, &entry_dir).is_err() && !entry_dir.join(ENTRY_FILE_NAME).is_file() {
+                return Err(Error::root(format!(
+                    "Failed to move the cache entry {temp_dir} to {entry_dir}"
+                )));
+            }
+
+            Ok(entry_dir.join(&entry.file_name))
+        })();
+
+        if temp_dir.exists() {
+            remove_dir(&temp_dir)?;
+        }
+
+        result
+    }
+
+    /// Returns all entries of the cache, sorted by their name and version.
+    pub fn entries(&self) -> Result<Vec<CacheEntry>> {
+        let mut entries = vec![];
+        for dir in self.entry_dirs(false)? {
+            match read_entry(&dir) {
+                Ok(entry) => entries.push(entry),
+                Err(err) => warn!(%dir, ?err, "Skipping an invalid cache entry"),
+            }
+        }
+
+        entries.sort_by(|a, b| (&a.name, &a.version, &a.dir).cmp(&(&b.name, &b.version, &b.dir)));
+        Ok(entries)
+    }
+
+    /// Removes the entries of the given lint crates from the cache. All entries
+    /// are removed, if `crates` is empty. Returns the number of removed entries.
+    pub fn clean(&self, crates: &[String]) -> Result<usize> {
+        let mut removed = 0;
+        for dir in self.entry_dirs(true)? {
+            // Incomplete entries of aborted processes are always removed
+            let is_temp = dir.file_name().map_or(false, |name| name.starts_with(TEMP_DIR_PREFIX));
+            let remove =
+                crates.is_empty() || is_temp || read_entry(&dir).map_or(false, |entry| crates.contains(&entry.name));
+
+            if remove {
+                remove_dir(&dir)?;
+                removed += usize::from(!is_temp);
+            }
+        }
+
+        Ok(removed)
+    }
+
+    /// Returns the directories in the cache. Directories of incomplete entries
+    /// are only included, if `include_temp` is `true`.
+    fn entry_dirs(&self, include_temp: bool) -> Result<Vec<Utf8PathBuf>> {
+        if !self.dir.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut dirs = vec![];
+        let read_dir = self
+            .dir
+            .read_dir_utf8()
+            .context(|| format!("Failed to read the cache directory {}", self.dir))?;
+        for dir in read_dir {
+            let dir = dir.context(|| format!("Failed to read the cache directory {}", self.dir))?;
+            if !dir.path().is_dir() {
+                continue;
+            }
+            if !include_temp && dir.file_name().starts_with(TEMP_DIR_PREFIX) {
+                continue;
+            }
+            dirs.push(dir.into_path());
+        }
+
+        Ok(dirs)
+    }
+}
+
+fn read_entry(dir: &Utf8Path) -> Result<CacheEntry> {
+    let entry_file = dir.join(ENTRY_FILE_NAME);
+    let content = std::fs::read_to_string(&entry_file).context(|| format!("Failed to read {entry_file}"))?;
+    let mut entry: CacheEntry =
+        serde_json::from_str(&content).context(|| format!("Failed to parse the cache entry {entry_file}"))?;
+    entry.dir = dir.to_path_buf();
+    Ok(entry)
+}
+
+fn remove_dir(dir: &Utf8Path) -> Result {
+    std::fs::remove_dir_all(dir).context(|| format!("Failed to remove the cache directory {dir}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_entry(name: &str) -> CacheEntry {
+        CacheEntry::new(
+            name.to_string(),
+            "0.1.0".to_string(),
+            "registry".to_string(),
+            "nightly".to_string(),
+            "0.1.0".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_store_and_lookup() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+        let cache = Cache::new(root.join("cache"));
+        let lib = root.join("liblints.so");
+        std::fs::write(&lib, "compiled").unwrap();
+
+        assert_eq!(cache.lookup("lints-key"), None);
+
+        let file = cache.store("lints-key", test_entry("lints"), &lib).unwrap();
+        assert_eq!(file, cache.dir().join("lints-key").join("liblints.so"));
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "compiled");
+        assert_eq!(cache.lookup("lints-key"), Some(file));
+        assert_eq!(cache.lookup("other-key"), None);
+
+        let entries = cache.entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "lints");
+    }
+
+    #[test]
+    fn test_lookup_incomplete_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+        let cache = Cache::new(root.join("cache"));
+        let lib = root.join("liblints.so");
+        std::fs::write(&lib, "compiled").unwrap();
+
+        let file = cache.store("lints-key", test_entry("lints"), &lib).unwrap();
+        std::fs::remove_file(file).unwrap();
+        assert_eq!(cache.lookup("lints-key"), None);
+    }
+
+    #[test]
+    fn test_store_existing_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+        let cache = Cache::new(root.join("cache"));
+        let lib = root.join("liblints.so");
+        std::fs::write(&lib, "compiled").unwrap();
+
+        // Simulate another process, which stored the entry first
+        let first = cache.store("lints-key", test_entry("lints"), &lib).unwrap();
+        std::fs::write(&lib, "recompiled").unwrap();
+
+        let second = cache.store("lints-key", test_entry("lints"), &lib).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(std::fs::read_to_string(&second).unwrap(), "compiled");
+
+        // The temporary directory is removed
+        assert_eq!(cache.entry_dirs(true).unwrap(), vec![cache.dir().join("lints-key")]);
+    }
+
+    #[test]
+    fn test_clean() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+        let cache = Cache::new(root.join("cache"));
+        let lib = root.join("liblints.so");
+        std::fs::write(&lib, "compiled").unwrap();
+
+        cache.store("lints-a", test_entry("lints"), &lib).unwrap();
+        cache.store("lints-b", test_entry("lints"), &lib).unwrap();
+        cache.store("other", test_entry("other"), &lib).unwrap();
+        let temp_dir = cache.dir().join(format!("{TEMP_DIR_PREFIX}1-lints-c"));
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        assert_eq!(cache.clean(&["lints".to_string()]).unwrap(), 2);
+        assert!(!temp_dir.exists());
+        assert_eq!(cache.lookup("lints-a"), None);
+        assert!(cache.lookup("other").is_some());
+
+        assert_eq!(cache.clean(&[]).unwrap(), 1);
+        assert!(cache.entries().unwrap().is_empty());
+    }
+}
@@ -1,22 +1,13 @@
-use crate::error::prelude::*;
-use crate::observability::prelude::*;
-use camino::Utf8PathBuf;
 use cargo_metadata::MetadataCommand;
-use serde::Deserialize;
 use std::process::Command;
 
 #[derive(Debug, Default)]
 pub struct Cargo {
-    /// The rustc toolchain this driver belongs to. This can be `None` during
-    /// execution commands such as `cargo locate-project`
+    /// The rustc toolchain this driver belongs to. This can be `None`, if the
+    /// driver was found next to the `cargo-marker` binary
     pub(crate) toolchain: Option<String>,
 }
 
-#[derive(Deserialize, Debug)]
-struct ProjectLocation {
-    root: Utf8PathBuf,
-}
-
 impl Cargo {
     pub fn with_toolchain(toolchain: impl Into<String>) -> Self {
         Self {
@@ -41,40 +32,11 @@ impl Cargo {
             cmd.args(["run", toolchain, "cargo"]);
             cmd
         } else {
-            // for cargo locate-project - this command can be run without
-            // specified toolchain
+            // Without a toolchain, the Cargo of the current environment is used
             Command::new("cargo")
         }
     }
 
-    pub fn cargo_locate_project(&self) -> Result<Utf8PathBuf> {
-        let mut cmd = self.command();
-
-        cmd.arg("locate-project").arg("--color=always").arg("--workspace");
-        let output = cmd
-            .log()
-            .output()
-            .context(|| "Error locating workspace manifest Cargo.toml")?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(Error::wrap(
-                stderr.trim(),
-                format!("{} failed with {}", cmd.display(), output.status),
-            ));
-        }
-
-        let manifest_location: ProjectLocation = serde_json::from_slice(&output.stdout).context(|| {
-            format!(
-                "Failed to deserialize cargo locate-project output (dumped it on the line bellow)\n\
-                ---\n{}\n---",
-                String::from_utf8_lossy(&output.stdout)
-            )
-        })?;
-
-        Ok(manifest_location.root)
-    }
-
     // Keep self for future changes. It's implemented in such way that clippy
     // doesn't ask to write it as an associative function.
     #[allow(clippy::unused_self)]
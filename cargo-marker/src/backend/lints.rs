@@ -4,6 +4,9 @@ use camino::Utf8PathBuf;
 
 mod build;
 mod fetch;
+pub mod metadata;
+
+pub use metadata::LintCrateMetadata;
 
 /// This struct contains all information of a lint crate required to compile
 /// the crate. See the [fetch] module for how external crates are fetched and
@@ -13,8 +16,27 @@ pub struct LintCrateSource {
     /// The name of the package, for now we can assume that this is the name
     /// that will be used to construct the dynamic library.
     name: String,
+    /// The version of the package
+    version: String,
+    /// The source of the package, as reported by Cargo. This is `None` for
+    /// lint crates with a `path` source.
+    source: Option<String>,
     /// The absolute path to the manifest of this lint crate
     manifest: Utf8PathBuf,
+    /// The package ID specification, used to build this crate as a part of
+    /// Marker's dummy crate. This makes Cargo use the versions pinned in the
+    /// `Marker.lock` file. This is `None` for lint crates with a `path` source,
+    /// which are built from their own manifest instead, to use the lock file
+    /// of their workspace.
+    package_spec: Option<String>,
+    /// The features, which should be enabled for this lint crate.
+    features: Vec<String>,
+    /// Indicates if the default features of this lint crate should be enabled.
+    default_features: bool,
+    /// A description of the exact sources of this crate and its dependencies,
+    /// which is used as a key for the global cache. This is `None`, if the
+    /// crate or one of its dependencies has a `path` source.
+    fingerprint: Option<String>,
 }
 
 /// The information of a compiled lint crate.
@@ -22,6 +44,8 @@ pub struct LintCrateSource {
 pub struct LintCrate {
     /// The name of the crate
     pub name: String,
+    /// The version of the crate
+    pub version: String,
     /// The absolute path of the compiled crate, as a dynamic library.
     pub file: Utf8PathBuf,
 }
@@ -35,3 +59,15 @@ pub fn build_lints(config: &Config) -> Result<Vec<LintCrate>> {
     let sources = fetch::fetch_crates(config)?;
     build::build_lints(&sources, config)
 }
+
+/// This function builds all lints specified in the given [`Config`] and loads
+/// them, to collect the information about the lints they provide.
+pub fn load_lint_metadata(config: &Config) -> Result<Vec<LintCrateMetadata>> {
+    build_lints(config)?.iter().map(LintCrateMetadata::load).collect()
+}
+
+/// This function updates the `Marker.lock` file for the lint crates specified
+/// in the given [`Config`]
+pub fn update_lock_file(config: &Config, packages: &[String]) -> Result {
+    fetch::update_lock_file(config, packages)
+}
@@ -1,11 +1,19 @@
 use super::{LintCrate, LintCrateSource};
+use crate::backend::cache::{Cache, CacheEntry};
+use crate::backend::driver::DriverVersionInfo;
 use crate::backend::Config;
 use crate::error::prelude::*;
+use crate::observability::display;
 use crate::observability::prelude::*;
-use crate::utils::utf8::IntoUtf8;
-use camino::Utf8Path;
-use itertools::Itertools;
-use std::{collections::HashSet, ffi::OsStr};
+use crate::utils;
+use crate::utils::hash::StableHasher;
+use camino::Utf8PathBuf;
+use cargo_metadata::Message;
+use std::io::{BufReader, Read};
+use std::num::NonZeroUsize;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
 use yansi::Paint;
 
 #[cfg(target_os = "linux")]
@@ -15,130 +23,312 @@ const DYNAMIC_LIB_FILE_ENDING: &str = "dylib";
 #[cfg(target_os = "windows")]
 const DYNAMIC_LIB_FILE_ENDING: &str = "dll";
 
-/// A list of file endings which are expected to be inside the lint crate dir.
-/// It's assumed that these can be safely removed.
-const ARTIFACT_ENDINGS: &[&str] = &[
-    DYNAMIC_LIB_FILE_ENDING,
-    #[cfg(target_os = "windows")]
-    "exp",
-    #[cfg(target_os = "windows")]
-    "lib",
-    #[cfg(target_os = "windows")]
-    "pdb",
-];
-
 pub fn build_lints(sources: &[LintCrateSource], config: &Config) -> Result<Vec<LintCrate>> {
-    // By default Cargo doesn't provide the path of the compiled lint crate.
-    // As a work around, we use the `--out-dir` option to make cargo copy all
-    // created binaries into one folder. We then scan that folder as we build our crates
-    // and collect all dynamic libraries that show up, we link the name of the crate we just built
-    // with the file(s) we found after we built it, assuming that they are related.
+    // Every lint crate is built in its own target directory. The directory
+    // is keyed by everything, that would otherwise invalidate Cargo's cache,
+    // when several lint crates or toolchains share a target directory. This
+    // allows Cargo to skip the compilation, if nothing changed.
+    //
+    // The path of the compiled library is retrieved from Cargo's JSON messages.
     //
-    // TODO Look into how to optimize this a bit better:
-    // Another option that would be potentially more performant is if we built each
-    // crate in it's own target dir. this would eliminate the need for HashSet<_> below, without
-    // changing too much else about the implementation.
+    // The lint crates are built in parallel, since the target directories
+    // don't block each other. The output of every build is captured and
+    // printed in one piece, once the build finished.
     //
-    // This would be so much simpler if we could get an output name from Cargo
-
-    // Clear previously build lints
-    let lints_dir = config.lint_crate_dir();
-    clear_lints_dir(&lints_dir)?;
-
-    // Build lint crates and find the output of those builds
-    let mut found_paths = HashSet::new();
-    let ending = DYNAMIC_LIB_FILE_ENDING;
-    let mut lints = Vec::with_capacity(sources.len());
-
-    for lint_src in sources {
-        build_lint(lint_src, config)?;
-        match std::fs::read_dir(&lints_dir) {
-            Ok(dir) => {
-                for file in dir {
-                    let file = file.unwrap().path().into_utf8()?;
-                    if file.extension() == Some(ending) && !found_paths.contains(&file) {
-                        found_paths.insert(file.clone());
-                        lints.push(LintCrate {
-                            file,
-                            name: lint_src.name.clone(),
-                        });
+    // The number of parallel builds is limited by the available parallelism.
+    // The jobs of these builds are divided among them, to prevent every build
+    // from using all cores.
+    let cache = GlobalCache::try_from_config(sources, config)?;
+    let cache = cache.as_ref();
+    let parallelism = std::thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let workers = parallelism.min(sources.len());
+    let jobs = (workers > 1).then(|| parallelism / workers);
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<_> = std::thread::scope(|scope| {
+        let threads: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = vec![];
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(lint_src) = sources.get(index) else {
+                            break results;
+                        };
+                        results.push((index, build_lint(lint_src, config, cache, jobs)));
                     }
-                }
-            },
-            Err(err) => {
-                // This shouldn't really be a point of failure. In this case, I'm
-                // more interested in the HOW?
-                panic!("unable to read lints dir after lint compilation: {lints_dir} ({err:#?})");
-            },
+                })
+            })
+            .collect();
+        threads
+            .into_iter()
+            .flat_map(|thread| thread.join().expect("the thread building lint crates panicked"))
+            .collect()
+    });
+
+    // Keep the order of the given sources
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, lint)| lint).collect()
+}
+
+/// The global cache and the driver information, which is part of the cache keys.
+struct GlobalCache {
+    cache: Cache,
+    driver: DriverVersionInfo,
+}
+
+impl GlobalCache {
+    /// Returns the global cache, if it's enabled and at least one of the
+    /// lint crates can be cached.
+    fn try_from_config(sources: &[LintCrateSource], config: &Config) -> Result<Option<Self>> {
+        let Some(dir) = &config.global_cache else {
+            return Ok(None);
+        };
+
+        // Dev builds of the driver can change without a version bump
+        if utils::is_local_driver() || sources.iter().all(|lint_src| lint_src.fingerprint.is_none()) {
+            return Ok(None);
         }
+
+        // The driver info is requested using a crate without dependencies.
+        // Using the dummy crate would compile all lint crates, before the
+        // driver is invoked.
+        let manifest = config.marker_dir.join("driver-info").join("Cargo.toml");
+        utils::write_to_file(&manifest, DRIVER_INFO_MANIFEST)?;
+        utils::write_to_file(&config.marker_dir.join("driver-info").join("src").join("lib.rs"), "")?;
+
+        Ok(Some(Self {
+            cache: Cache::new(dir.clone()),
+            driver: DriverVersionInfo::try_from_toolchain(&config.toolchain, &manifest)?,
+        }))
+    }
+}
+
+const DRIVER_INFO_MANIFEST: &str = r#"
+# This crate is used by Marker, to request the version of the driver.
+
+[package]
+name = "markers-dummy-crate-for-driver-info"
+version = "0.1.0"
+edition = "2021"
+publish = false
+
+[workspace]
+"#;
+
+impl LintCrateSource {
+    /// The name of the target directory used to build this lint crate. It's
+    /// made up of the crate name and a hash of the source, the selected features
+    /// and the toolchain, which are used for the build.
+    fn target_dir_name(&self, config: &Config) -> String {
+        let hash = StableHasher::new()
+            .write_str(self.manifest.as_str())
+            .write_opt_str(self.package_spec.as_deref())
+            .write_strs(&self.features)
+            .write_bool(self.default_features)
+            .write_opt_str(config.toolchain.cargo.toolchain.as_deref())
+            .write_str(config.toolchain.driver_path.as_str())
+            .finish();
+
+        format!("{}-{hash:016x}", self.name)
+    }
+
+    /// The key of this lint crate in the global cache. It's made up of the
+    /// crate name, version and a hash of the [fingerprint](Self::fingerprint),
+    /// the driver version and the build configuration. This is `None`, if the
+    /// crate can't be cached.
+    fn cache_key(&self, driver: &DriverVersionInfo, config: &Config) -> Option<String> {
+        let fingerprint = self.fingerprint.as_ref()?;
+
+        let hash = StableHasher::new()
+            .write_str(fingerprint)
+            .write_strs(&self.features)
+            .write_bool(self.default_features)
+            .write_str(&driver.toolchain)
+            .write_str(&driver.version)
+            .write_str(&driver.api_version)
+            .write_bool(config.debug_build)
+            .write_str(&config.build_rustc_flags)
+            .finish();
+
+        Some(format!("{}-{}-{hash:016x}", self.name, self.version))
     }
-    Ok(lints)
 }
 
-/// This function clears the `marker/lints` directory holding all compiled lints. This
-/// is required, as Marker uses the content of that directory to determine which lints
-/// should be run.
-///
-/// This is an extra function to not call `delete_dir_all` and just accidentally delete
-/// the entire system.
-fn clear_lints_dir(lints_dir: &Utf8Path) -> Result {
-    // Delete all files
-    let dir = match std::fs::read_dir(lints_dir) {
-        Ok(dir) => dir,
-        Err(err) if std::io::ErrorKind::NotFound == err.kind() => return Ok(()),
-        Err(err) => return Err(Error::wrap(err, "Failed to read lints artifacts directory")),
+fn build_lint(
+    lint_src: &LintCrateSource,
+    config: &Config,
+    cache: Option<&GlobalCache>,
+    jobs: Option<usize>,
+) -> Result<LintCrate> {
+    let start = Instant::now();
+    let version = lint_src.version.as_str();
+    let print_status = |status: &str, output: &str| {
+        let elapsed = start.elapsed().as_secs_f32();
+        eprintln!(
+            "{output}{}",
+            display::status(status, format_args!("{} v{version} in {elapsed:.2}s", lint_src.name))
+        );
+    };
+
+    let Some((cache, key)) = cache.and_then(|cache| Some((cache, lint_src.cache_key(&cache.driver, config)?))) else {
+        let compiled = compile_lint(lint_src, config, jobs)?;
+        print_status(compiled.status(), &compiled.output);
+        return Ok(compiled.lint);
     };
 
-    let endings: Vec<_> = ARTIFACT_ENDINGS.iter().map(OsStr::new).collect();
+    if let Some(file) = cache.cache.lookup(&key) {
+        info!(%file, "Using the lint crate {} from the global cache", lint_src.name);
+        print_status("Cached", "");
+        return Ok(LintCrate {
+            name: lint_src.name.clone(),
+            version: lint_src.version.clone(),
+            file,
+        });
+    }
 
-    let (files, errors): (Vec<_>, Vec<_>) = dir.map(|result| result.map_err(Error::transparent)).partition_result();
+    let mut compiled = compile_lint(lint_src, config, jobs)?;
 
-    if !errors.is_empty() {
-        return Err(Error::many(errors, "Failed to read the lints directory entries"));
+    let entry = CacheEntry::new(
+        lint_src.name.clone(),
+        lint_src.version.clone(),
+        lint_src.source.clone().unwrap_or_default(),
+        cache.driver.toolchain.clone(),
+        cache.driver.version.clone(),
+    );
+    // A failure to store the crate shouldn't prevent the linting
+    match cache.cache.store(&key, entry, &compiled.lint.file) {
+        Ok(file) => compiled.lint.file = file,
+        Err(err) => warn!(
+            ?err,
+            "Failed to store the lint crate {} in the global cache", lint_src.name
+        ),
     }
 
-    for file in files {
-        let file = file.path();
+    print_status(compiled.status(), &compiled.output);
+    Ok(compiled.lint)
+}
+
+/// A lint crate, compiled by Cargo.
+struct CompiledLint {
+    lint: LintCrate,
+    /// Indicates, if Cargo was able to reuse all artifacts of a previous build.
+    fresh: bool,
+    /// The rendered diagnostics, which were emitted during the compilation.
+    output: String,
+}
+
+impl CompiledLint {
+    fn status(&self) -> &'static str {
+        if self.fresh { "Fresh" } else { "Compiled" }
+    }
+}
 
-        let is_expected_ending = file.extension().map(|ending| endings.contains(&ending)) == Some(true);
+/// Compiles the given lint crate. The number of parallel jobs used by Cargo
+/// can be limited with `jobs`.
+fn compile_lint(lint_src: &LintCrateSource, config: &Config, jobs: Option<usize>) -> Result<CompiledLint> {
+    let target_dir = config.markers_target_dir().join(lint_src.target_dir_name(config));
 
-        if !is_expected_ending {
-            return Err(Error::root(format!(
-                "Marker's lint directory contains an unexpected file: {}",
-                file.display()
-            )));
+    // External lint crates are built as a part of the dummy crate, to use the
+    // versions pinned by the `Marker.lock` file.
+    let mut cmd = if let Some(spec) = &lint_src.package_spec {
+        let mut cmd = config
+            .toolchain
+            .cargo_build_command(config, &config.marker_dir.join("Cargo.toml"), &target_dir);
+        cmd.arg("--package");
+        cmd.arg(spec);
+        cmd
+    } else {
+        // The features of external lint crates are selected by the dummy crate.
+        // Crates built from their own manifest require them as arguments.
+        let mut cmd = config
+            .toolchain
+            .cargo_build_command(config, &lint_src.manifest, &target_dir);
+        if !lint_src.features.is_empty() {
+            cmd.arg("--features");
+            cmd.arg(lint_src.features.join(","));
         }
+        if !lint_src.default_features {
+            cmd.arg("--no-default-features");
+        }
+        cmd
+    };
 
-        std::fs::remove_file(&file)
-            .context(|| format!("Failed to remove the lint artifact file {}", file.display()))?;
+    if let Some(jobs) = jobs {
+        cmd.arg("--jobs");
+        cmd.arg(jobs.to_string());
     }
 
-    // The dir should now be empty
-    std::fs::remove_dir(lints_dir).context(|| format!("Failed to remove lints directory {lints_dir}"))
-}
+    // Cargo reports the paths of the compiled artifacts in JSON messages.
+    // Diagnostics are included as pre-rendered strings in these messages.
+    cmd.arg("--message-format=json-diagnostic-rendered-ansi");
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
 
-fn build_lint(lint_src: &LintCrateSource, config: &Config) -> Result {
-    let mut cmd = config.toolchain.cargo_build_command(config, &lint_src.manifest);
+    let mut child = cmd.log().spawn().expect("could not run cargo");
+    let stdout = child.stdout.take().expect("stdout of cargo was piped");
+    let mut stderr = child.stderr.take().expect("stderr of cargo was piped");
 
-    // Set output dir. This currently requires unstable options
-    cmd.arg("-Z");
-    cmd.arg("unstable-options");
-    cmd.arg("--out-dir");
-    cmd.arg(config.lint_crate_dir().as_os_str());
+    // The output of Cargo is only relevant, if the build failed. It has to be
+    // read concurrently, to prevent Cargo from blocking on a full pipe.
+    let stderr_reader = std::thread::spawn(move || {
+        let mut output = String::new();
+        let _ = stderr.read_to_string(&mut output);
+        output
+    });
 
-    let exit_status = cmd
-        .log()
-        .spawn()
-        .expect("could not run cargo")
-        .wait()
-        .expect("failed to wait for cargo?");
+    let mut file = None;
+    let mut fresh = true;
+    let mut output = String::new();
+    for message in Message::parse_stream(BufReader::new(stdout)) {
+        match message.context(|| "Failed to read the output of cargo")? {
+            Message::CompilerArtifact(artifact) => {
+                fresh &= artifact.fresh;
+                if artifact.manifest_path == lint_src.manifest
+                    && artifact.target.kind.iter().any(|kind| kind == "cdylib")
+                {
+                    file = artifact
+                        .filenames
+                        .into_iter()
+                        .find(|file| file.extension() == Some(DYNAMIC_LIB_FILE_ENDING));
+                }
+            },
+            Message::CompilerMessage(msg) => {
+                if let Some(rendered) = msg.message.rendered {
+                    output.push_str(&rendered);
+                }
+            },
+            _ => {},
+        }
+    }
 
-    if exit_status.success() {
-        return Ok(());
+    let exit_status = child.wait().expect("failed to wait for cargo?");
+    let cargo_output = stderr_reader
+        .join()
+        .expect("the thread reading cargo's output panicked");
+
+    if !exit_status.success() {
+        eprint!("{output}{cargo_output}");
+        return Err(Error::root(format!(
+            "Failed to compile the lint crate {}",
+            lint_src.name.red().bold()
+        )));
     }
 
-    Err(Error::root(format!(
-        "Failed to compile the lint crate {}",
-        lint_src.name.red().bold()
-    )))
+    let file: Utf8PathBuf = file.context(|| {
+        format!(
+            "Cargo didn't report a dynamic library for the lint crate {}. \
+            Make sure that the crate type is set to `cdylib`",
+            lint_src.name.red().bold()
+        )
+    })?;
+
+    Ok(CompiledLint {
+        lint: LintCrate {
+            name: lint_src.name.clone(),
+            version: lint_src.version.clone(),
+            file,
+        },
+        fresh,
+        output,
+    })
 }
@@ -9,14 +9,23 @@
 //! specified lint crates as dependencies. Then `cargo fetch` is called, which
 //! will download the crates into Cargo's cache. The absolute path to the lints
 //! can then be retrieved from `cargo metadata`.
+//!
+//! The `Cargo.lock` file of the dummy crate is persisted as the `Marker.lock`
+//! file in the workspace root. It's copied into the dummy crate before Cargo is
+//! invoked, to make the resolved dependency graph reproducible.
 
 use super::LintCrateSource;
 use crate::error::prelude::*;
 use crate::observability::prelude::*;
-use crate::{backend::Config, config::LintDependencyEntry};
+use crate::{
+    backend::Config,
+    config::{LintDependencyEntry, Source},
+    utils,
+};
 use camino::{Utf8Path, Utf8PathBuf};
-use cargo_metadata::Metadata;
-use std::collections::BTreeMap;
+use cargo_metadata::{Metadata, PackageId};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
 
 /// This function fetches and locates all lint crates specified in the given
 /// configuration.
@@ -25,47 +34,135 @@ pub fn fetch_crates(config: &Config) -> Result<Vec<LintCrateSource>> {
     // local dependency.
 
     let manifest = setup_dummy_crate(config)?;
+    restore_lock_file(config)?;
 
     call_cargo_fetch(&manifest, config)?;
 
     let metadata = call_cargo_metadata(&manifest, config)?;
+    persist_lock_file(config)?;
+
+    let sources = extract_lint_crate_sources(&metadata, config)?;
+
+    // Lint crates with a `path` source are built from their own manifest. Their
+    // dependencies are resolved and fetched here, to prevent the parallel builds
+    // from doing it concurrently.
+    for lint_src in sources.iter().filter(|lint_src| lint_src.package_spec.is_none()) {
+        call_cargo_fetch(&lint_src.manifest, config)?;
+    }
+
+    Ok(sources)
+}
+
+/// This function resolves the lint crates specified in the given configuration
+/// again and stores the updated versions in the `Marker.lock` file.
+pub fn update_lock_file(config: &Config, packages: &[String]) -> Result {
+    let manifest = setup_dummy_crate(config)?;
+    restore_lock_file(config)?;
+
+    call_cargo_update(&manifest, config, packages)?;
+
+    persist_lock_file(config)
+}
+
+/// Returns `true`, if at least one lint crate isn't a `path` dependency.
+fn has_external_lints(config: &Config) -> bool {
+    config
+        .lints
+        .values()
+        .any(|dep| !matches!(dep.source, Source::Path { .. }))
+}
+
+fn dummy_lock_file(config: &Config) -> Utf8PathBuf {
+    config.marker_dir.join("Cargo.lock")
+}
+
+/// This function copies the `Marker.lock` file into the dummy crate, to make
+/// Cargo use the pinned versions. The lock file of a previous run is removed,
+/// if the `Marker.lock` file doesn't exist or isn't used, to resolve all
+/// dependencies from scratch.
+fn restore_lock_file(config: &Config) -> Result {
+    let dummy_lock = dummy_lock_file(config);
+    let Some(lock_file) = &config.lock_file else {
+        return remove_lock_file(&dummy_lock);
+    };
+
+    if lock_file.is_file() {
+        std::fs::copy(lock_file, &dummy_lock)
+            .context(|| format!("Failed to copy the lock file {lock_file} to {dummy_lock}"))?;
+        return Ok(());
+    }
+
+    if !has_external_lints(config) {
+        return Ok(());
+    }
+
+    if config.locked {
+        return Err(Error::from_kind(ErrorKind::LockFileNotFound {
+            lock_file: lock_file.clone(),
+        }));
+    }
+
+    remove_lock_file(&dummy_lock)
+}
+
+fn remove_lock_file(lock_file: &Utf8Path) -> Result {
+    match std::fs::remove_file(lock_file) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+            Err(Error::wrap(err, format!("Failed to remove the lock file {lock_file}")))
+        },
+        _ => Ok(()),
+    }
+}
 
-    Ok(extract_lint_crate_sources(&metadata, config))
+/// This function copies the `Cargo.lock` file of the dummy crate to the
+/// `Marker.lock` file, if the content changed. The file is only created, if
+/// there is at least one lint crate, which isn't a `path` dependency.
+fn persist_lock_file(config: &Config) -> Result {
+    let Some(lock_file) = &config.lock_file else {
+        return Ok(());
+    };
+    if !lock_file.exists() && !has_external_lints(config) {
+        return Ok(());
+    }
+    let dummy_lock = dummy_lock_file(config);
+
+    let content = std::fs::read_to_string(&dummy_lock)
+        .context(|| format!("Failed to read the lock file of the lint crates at {dummy_lock}"))?;
+    if std::fs::read_to_string(lock_file).ok().as_deref() == Some(content.as_str()) {
+        return Ok(());
+    }
+
+    info!(%lock_file, "Updating the lock file");
+    std::fs::write(lock_file, content).context(|| format!("Failed to write the lock file {lock_file}"))
 }
 
 /// This function sets up the dummy crate with all the lints listed as dependencies.
 /// It returns the path of the manifest, if everything was successful.
 fn setup_dummy_crate(config: &Config) -> Result<Utf8PathBuf> {
-    /// A small hack, to have the lints namespaced under the `[dependencies]` section
-    #[derive(serde::Serialize)]
-    struct DepNamespace<'a> {
-        dependencies: &'a BTreeMap<String, LintDependencyEntry>,
-    }
-
     // Manifest
-    let lints_as_deps = toml::to_string(&DepNamespace {
-        dependencies: &config.lints,
-    })
-    .expect("DepNamespace can be represented as TOML");
-
-    let manifest_content = format!("{DUMMY_MANIFEST_TEMPLATE}{lints_as_deps}");
     let manifest_path = config.marker_dir.join("Cargo.toml");
-    write_to_file(&manifest_path, &manifest_content)?;
+    utils::write_to_file(&manifest_path, &dummy_manifest(&config.lints))?;
 
     // `./src/main.rs` file
-    write_to_file(&config.marker_dir.join("src").join("main.rs"), DUMMY_MAIN_CONTENT)?;
+    utils::write_to_file(&config.marker_dir.join("src").join("main.rs"), DUMMY_MAIN_CONTENT)?;
 
     Ok(manifest_path)
 }
 
-fn write_to_file(path: &Utf8Path, content: &str) -> Result {
-    let parent = path
-        .parent()
-        .unwrap_or_else(|| panic!("The file must have a parent directory. Path: {path}"));
+/// This function creates the manifest of the dummy crate. The lints are added
+/// as normal dependencies, including the selected features. Cargo will report
+/// an error during the resolution, if a requested feature doesn't exist.
+fn dummy_manifest(lints: &BTreeMap<String, LintDependencyEntry>) -> String {
+    /// A small hack, to have the lints namespaced under the `[dependencies]` section
+    #[derive(serde::Serialize)]
+    struct DepNamespace<'a> {
+        dependencies: &'a BTreeMap<String, LintDependencyEntry>,
+    }
 
-    std::fs::create_dir_all(parent).context(|| format!("Failed to create the directory structure for {parent}"))?;
+    let lints_as_deps =
+        toml::to_string(&DepNamespace { dependencies: lints }).expect("DepNamespace can be represented as TOML");
 
-    std::fs::write(path, content).context(|| format!("Failed to write a file at {path}"))
+    format!("{DUMMY_MANIFEST_TEMPLATE}{lints_as_deps}")
 }
 
 const DUMMY_MANIFEST_TEMPLATE: &str = r#"
@@ -105,6 +202,7 @@ fn call_cargo_fetch(manifest: &Utf8Path, config: &Config) -> Result {
         cmd.arg("--target");
         cmd.arg(target);
     }
+    cmd.args(config.cargo_lock_args());
 
     let status = cmd
         .log()
@@ -120,24 +218,347 @@ fn call_cargo_fetch(manifest: &Utf8Path, config: &Config) -> Result {
     Err(Error::root("cargo fetch failed for lint crates"))
 }
 
+fn call_cargo_update(manifest: &Utf8Path, config: &Config, packages: &[String]) -> Result {
+    let mut cmd = config.toolchain.cargo.command();
+    cmd.arg("update");
+    cmd.arg("--manifest-path");
+    cmd.arg(manifest.as_os_str());
+    for package in packages {
+        cmd.arg("--package");
+        cmd.arg(package);
+    }
+    if config.offline {
+        cmd.arg("--offline");
+    }
+
+    let status = cmd
+        .log()
+        .spawn()
+        .expect("unable to start `cargo update` to update lint crates")
+        .wait()
+        .expect("unable to wait for `cargo update` to update lint crates");
+
+    if status.success() {
+        return Ok(());
+    }
+
+    Err(Error::root("cargo update failed for lint crates"))
+}
+
 fn call_cargo_metadata(manifest: &Utf8Path, config: &Config) -> Result<Metadata> {
     config
         .toolchain
         .cargo
         .metadata()
         .manifest_path(manifest)
+        .other_options(config.cargo_lock_args())
         .exec()
         .context(|| format!("Failed to get cargo metadata for the lint crates at {manifest}"))
 }
 
-fn extract_lint_crate_sources(metadata: &Metadata, marker_config: &Config) -> Vec<LintCrateSource> {
-    metadata
+fn extract_lint_crate_sources(metadata: &Metadata, marker_config: &Config) -> Result<Vec<LintCrateSource>> {
+    let checksums = read_lock_file_checksums(&dummy_lock_file(marker_config))?;
+
+    Ok(metadata
         .packages
         .iter()
-        .filter(|pkg| marker_config.lints.contains_key(&pkg.name))
-        .map(|pkg| LintCrateSource {
-            name: pkg.name.clone(),
-            manifest: pkg.manifest_path.clone(),
+        .filter_map(|pkg| {
+            let dep = marker_config.lints.get(&pkg.name)?;
+            Some(LintCrateSource {
+                name: pkg.name.clone(),
+                version: pkg.version.to_string(),
+                source: pkg.source.as_ref().map(|source| source.repr.clone()),
+                manifest: pkg.manifest_path.clone(),
+                // Path dependencies don't have a source
+                package_spec: pkg.source.as_ref().map(|_| format!("{}@{}", pkg.name, pkg.version)),
+                features: dep.features.clone().unwrap_or_default(),
+                default_features: dep.default_features.unwrap_or(true),
+                fingerprint: fingerprint(metadata, &pkg.id, &checksums),
+            })
         })
-        .collect()
+        .collect())
+}
+
+/// The key of a package in a `Cargo.lock` file: the name, version and source
+type LockedPackageKey = (String, String, String);
+
+/// This function reads the checksums of all registry packages from the given
+/// `Cargo.lock` file.
+fn read_lock_file_checksums(lock_file: &Utf8Path) -> Result<HashMap<LockedPackageKey, String>> {
+    #[derive(serde::Deserialize)]
+    struct LockFile {
+        #[serde(default)]
+        package: Vec<LockedPackage>,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct LockedPackage {
+        name: String,
+        version: String,
+        source: Option<String>,
+        checksum: Option<String>,
+    }
+
+    if !lock_file.is_file() {
+        return Ok(HashMap::new());
+    }
+
+    let content = std::fs::read_to_string(lock_file).context(|| format!("Failed to read the lock file {lock_file}"))?;
+    let lock: LockFile = toml::from_str(&content).context(|| format!("Failed to parse the lock file {lock_file}"))?;
+
+    Ok(lock
+        .package
+        .into_iter()
+        .filter_map(|pkg| Some(((pkg.name, pkg.version, pkg.source?), pkg.checksum?)))
+        .collect())
+}
+
+/// This function creates a fingerprint of the given lint crate, which is used
+/// as a key for the global cache. It contains the exact source, checksum and
+/// enabled features of the crate and every dependency, that it's compiled with.
+///
+/// The fingerprint is `None`, if the crate or any of its dependencies has a
+/// `path` source, since these can change without a version bump.
+fn fingerprint(
+    metadata: &Metadata,
+    lint_crate: &PackageId,
+    checksums: &HashMap<LockedPackageKey, String>,
+) -> Option<String> {
+    let resolve = metadata.resolve.as_ref()?;
+    let nodes: HashMap<_, _> = resolve.nodes.iter().map(|node| (&node.id, node)).collect();
+    let packages: HashMap<_, _> = metadata.packages.iter().map(|pkg| (&pkg.id, pkg)).collect();
+
+    let mut reachable = BTreeSet::new();
+    let mut queue = vec![lint_crate];
+    while let Some(id) = queue.pop() {
+        if reachable.insert(id) {
+            queue.extend(nodes.get(id)?.dependencies.iter());
+        }
+    }
+
+    let mut fingerprint = String::new();
+    for id in reachable {
+        let pkg = packages.get(id)?;
+        let source = &pkg.source.as_ref()?.repr;
+        let version = pkg.version.to_string();
+        let checksum = checksums
+            .get(&(pkg.name.clone(), version.clone(), source.clone()))
+            .map_or("", String::as_str);
+        let mut features = nodes.get(id)?.features.clone();
+        features.sort();
+
+        writeln!(
+            fingerprint,
+            "{} {version} {source} {checksum} [{}]",
+            pkg.name,
+            features.join(",")
+        )
+        .expect("writing to a string can't fail");
+    }
+
+    Some(fingerprint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::error::ErrorKind;
+    use expect_test::expect;
+
+    fn lints_from_str(lints: &str) -> BTreeMap<String, LintDependencyEntry> {
+        let config = format!("[workspace.metadata.marker.lints]\n{lints}");
+        let config = Config::try_from_str(&config, Utf8Path::new("./Cargo.toml"))
+            .unwrap()
+            .unwrap();
+        config
+            .lints
+            .into_iter()
+            .map(|(name, dep)| (name, dep.into_dep_entry()))
+            .collect()
+    }
+
+    #[test]
+    fn test_restore_lock_file_locked_without_lock_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+        let mut config = crate::backend::Config::for_tests(&root.join("marker"));
+        config.lints = lints_from_str(r#"simple = "0.1.0""#);
+        config.lock_file = Some(root.join("Marker.lock"));
+        std::fs::create_dir_all(&config.marker_dir).unwrap();
+        config.locked = true;
+
+        let err = restore_lock_file(&config).unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            Some(ErrorKind::LockFileNotFound { lock_file }) if Some(lock_file) == config.lock_file.as_ref()
+        ));
+    }
+
+    #[test]
+    fn test_lock_file_skipped_for_path_lints() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+        let mut config = crate::backend::Config::for_tests(&root.join("marker"));
+        config.lints = lints_from_str(r#"local = { path = "." }"#);
+        config.lock_file = Some(root.join("Marker.lock"));
+        std::fs::create_dir_all(&config.marker_dir).unwrap();
+        config.locked = true;
+        std::fs::write(dummy_lock_file(&config), "# dummy").unwrap();
+
+        restore_lock_file(&config).unwrap();
+        persist_lock_file(&config).unwrap();
+        assert!(!config.lock_file.unwrap().exists());
+    }
+
+    #[test]
+    fn test_persist_lock_file_unchanged_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+        let mut config = crate::backend::Config::for_tests(&root.join("marker"));
+        config.lints = lints_from_str(r#"simple = "0.1.0""#);
+        config.lock_file = Some(root.join("Marker.lock"));
+        std::fs::create_dir_all(&config.marker_dir).unwrap();
+        let lock_file = config.lock_file.as_ref().unwrap();
+        std::fs::write(lock_file, "# lock").unwrap();
+        let modified = std::fs::metadata(lock_file).unwrap().modified().unwrap();
+
+        restore_lock_file(&config).unwrap();
+        assert_eq!(std::fs::read_to_string(dummy_lock_file(&config)).unwrap(), "# lock");
+
+        persist_lock_file(&config).unwrap();
+        assert_eq!(std::fs::metadata(lock_file).unwrap().modified().unwrap(), modified);
+
+        std::fs::write(dummy_lock_file(&config), "# updated").unwrap();
+        persist_lock_file(&config).unwrap();
+        assert_eq!(std::fs::read_to_string(lock_file).unwrap(), "# updated");
+    }
+
+    #[test]
+    fn test_restore_lock_file_removes_dummy_lock_without_lock_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+        let mut config = crate::backend::Config::for_tests(&root.join("marker"));
+        config.lints = lints_from_str(r#"simple = "0.1.0""#);
+        std::fs::create_dir_all(&config.marker_dir).unwrap();
+        std::fs::write(dummy_lock_file(&config), "# stale").unwrap();
+
+        restore_lock_file(&config).unwrap();
+        persist_lock_file(&config).unwrap();
+        assert!(!dummy_lock_file(&config).exists());
+    }
+
+    #[test]
+    fn test_dummy_manifest_features() {
+        let config = r#"
+            [workspace.metadata.marker.lints]
+            simple = "0.1.0"
+            featured = { version = "0.2.0", features = ["expensive", "pedantic"], default-features = false }
+        "#;
+        let config = Config::try_from_str(config, Utf8Path::new("./Cargo.toml"))
+            .unwrap()
+            .unwrap();
+        let lints = config
+            .lints
+            .into_iter()
+            .map(|(name, dep)| (name, dep.into_dep_entry()))
+            .collect();
+
+        let manifest = dummy_manifest(&lints);
+        let deps = &manifest[manifest.find("[dependencies").unwrap()..];
+        expect![[r#"
+            [dependencies.featured]
+            version = "0.2.0"
+            default-features = false
+            features = ["expensive", "pedantic"]
+
+            [dependencies.simple]
+            version = "0.1.0"
+        "#]]
+        .assert_eq(deps);
+    }
+
+    fn package_json(name: &str, source: Option<&str>) -> serde_json::Value {
+        serde_json::json!({
+            "name": name,
+            "version": "0.1.0",
+            "id": format!("{name} 0.1.0"),
+            "source": source,
+            "dependencies": [],
+            "targets": [],
+            "features": {},
+            "manifest_path": format!("/{name}/Cargo.toml"),
+        })
+    }
+
+    fn metadata(packages: &[(&str, Option<&str>)], nodes: &[(&str, &[&str], &[&str])]) -> Metadata {
+        let nodes: Vec<_> = nodes
+            .iter()
+            .map(|(name, deps, features)| {
+                serde_json::json!({
+                    "id": format!("{name} 0.1.0"),
+                    "dependencies": deps.iter().map(|dep| format!("{dep} 0.1.0")).collect::<Vec<_>>(),
+                    "features": features,
+                })
+            })
+            .collect();
+        serde_json::from_value(serde_json::json!({
+            "packages": packages.iter().map(|(name, source)| package_json(name, *source)).collect::<Vec<_>>(),
+            "workspace_members": [],
+            "resolve": { "nodes": nodes, "root": null },
+            "workspace_root": "/",
+            "target_directory": "/target",
+            "version": 1,
+        }))
+        .unwrap()
+    }
+
+    const REGISTRY: &str = "registry+https://github.com/rust-lang/crates.io-index";
+
+    #[test]
+    fn test_fingerprint() {
+        let metadata = metadata(
+            &[
+                ("lints", Some(REGISTRY)),
+                ("dep", Some(REGISTRY)),
+                ("unrelated", Some(REGISTRY)),
+            ],
+            &[
+                ("lints", &["dep"], &["pedantic", "default"]),
+                ("dep", &[], &[]),
+                ("unrelated", &[], &[]),
+            ],
+        );
+        let checksums = HashMap::from([(
+            ("lints".to_string(), "0.1.0".to_string(), REGISTRY.to_string()),
+            "1234".to_string(),
+        )]);
+
+        let fingerprint = fingerprint(
+            &metadata,
+            &PackageId {
+                repr: "lints 0.1.0".into(),
+            },
+            &checksums,
+        )
+        .unwrap();
+        expect![[r"
+            dep 0.1.0 registry+https://github.com/rust-lang/crates.io-index  []
+            lints 0.1.0 registry+https://github.com/rust-lang/crates.io-index 1234 [default,pedantic]
+        "]]
+        .assert_eq(&fingerprint);
+    }
+
+    #[test]
+    fn test_fingerprint_path_dependency() {
+        let metadata = metadata(
+            &[("lints", Some(REGISTRY)), ("local", None)],
+            &[("lints", &["local"], &[]), ("local", &[], &[])],
+        );
+
+        let id = PackageId {
+            repr: "lints 0.1.0".into(),
+        };
+        assert_eq!(fingerprint(&metadata, &id, &HashMap::new()), None);
+    }
 }
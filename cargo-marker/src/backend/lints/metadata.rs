@@ -0,0 +1,214 @@
+//! This module loads compiled lint crates with `marker_adapter`, to collect the
+//! information about the lints, which they provide. The information is copied
+//! into owned values, to make it independent of the loaded libraries.
+
+use super::LintCrate;
+use crate::config::LintLevel;
+use crate::error::prelude::*;
+use itertools::Itertools;
+use marker_api::common::{Level, MacroReport};
+use marker_api::ffi::FfiStr;
+use marker_api::{Lint, LintGroup};
+use serde::Serialize;
+
+/// The information about a lint crate and the lints it provides.
+#[derive(Debug, Serialize)]
+pub struct LintCrateMetadata {
+    /// The name of the lint crate
+    pub name: String,
+    /// The version of the lint crate
+    pub version: String,
+    /// The lints of this crate, sorted by their name
+    pub lints: Vec<LintMetadata>,
+    /// The lint groups of this crate, sorted by their name
+    pub groups: Vec<LintGroupMetadata>,
+}
+
+/// The information about a single lint, as declared with `declare_lint!`.
+#[derive(Debug, Serialize)]
+pub struct LintMetadata {
+    /// The name of the lint, like `marker::lint_crate::lint_name`. It's
+    /// lowercase, like the names used in lint attributes.
+    pub name: String,
+    /// The name of the lint crate, which provides this lint
+    #[serde(rename = "crate")]
+    pub krate: String,
+    /// The level of the lint, if it's not configured by the user
+    pub default_level: LintLevel,
+    /// A description of the macros, in which the lint can be emitted
+    pub report_in_macro: String,
+    /// The names of the lint groups, which contain this lint
+    pub groups: Vec<String>,
+    /// The doc comment of the lint, which is formatted with Markdown
+    pub docs: String,
+}
+
+/// The information about a lint group, as declared with `declare_lint_group!`.
+#[derive(Debug, Serialize)]
+pub struct LintGroupMetadata {
+    /// The lowercase name of the group, like `marker::lint_crate::group_name`
+    pub name: String,
+    /// The names of the lints in this group
+    pub lints: Vec<String>,
+    /// The doc comment of the group, which is formatted with Markdown
+    pub docs: String,
+}
+
+impl LintCrateMetadata {
+    /// Loads the given compiled lint crate, to collect the information about
+    /// its lints.
+    pub fn load(krate: &LintCrate) -> Result<Self> {
+        let info = marker_adapter::LintCrateInfo {
+            name: krate.name.clone(),
+            path: krate.file.clone(),
+        };
+        let adapter = marker_adapter::Adapter::new(&[info])
+            .map_err(|err| Error::wrap(err, format!("Failed to load the lint crate {}", krate.name)))?;
+
+        let groups: Vec<_> = adapter
+            .marker_lint_groups()
+            .into_iter()
+            .map(LintGroupMetadata::from_group)
+            .sorted_by(|a, b| a.name.cmp(&b.name))
+            .collect();
+        let lints = adapter
+            .marker_lints()
+            .into_iter()
+            .map(|lint| LintMetadata::from_lint(lint, &krate.name, &groups))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .sorted_by(|a, b| a.name.cmp(&b.name))
+            .collect();
+
+        // Lint crates can register thread local destructors, which would be
+        // called after the library has been unloaded. The library is therefore
+        // kept loaded, like it would be in the driver.
+        std::mem::forget(adapter);
+
+        Ok(Self {
+            name: krate.name.clone(),
+            version: krate.version.clone(),
+            lints,
+            groups,
+        })
+    }
+}
+
+impl LintMetadata {
+    fn from_lint(lint: &Lint, krate: &str, groups: &[LintGroupMetadata]) -> Result<Self> {
+        let name = lint.name.to_ascii_lowercase();
+        let groups = groups
+            .iter()
+            .filter(|group| group.lints.contains(&name))
+            .map(|group| group.name.clone())
+            .collect();
+
+        Ok(Self {
+            krate: krate.to_string(),
+            default_level: lint_level(lint)?,
+            report_in_macro: macro_report(lint)?,
+            groups,
+            docs: docs(lint.explanation),
+            name,
+        })
+    }
+
+    /// Returns the first paragraph of the documentation, which isn't a heading.
+    pub fn summary(&self) -> String {
+        self.docs
+            .lines()
+            .skip_while(|line| line.trim().is_empty() || line.starts_with('#'))
+            .take_while(|line| !line.trim().is_empty())
+            .map(str::trim)
+            .join(" ")
+    }
+}
+
+impl LintGroupMetadata {
+    fn from_group(group: &LintGroup) -> Self {
+        Self {
+            name: group.name.to_ascii_lowercase(),
+            lints: group
+                .lints
+                .get()
+                .iter()
+                .map(|lint| lint.name.to_ascii_lowercase())
+                .collect(),
+            docs: docs(group.explanation),
+        }
+    }
+}
+
+fn lint_level(lint: &Lint) -> Result<LintLevel> {
+    match lint.default_level {
+        Level::Allow => Ok(LintLevel::Allow),
+        Level::Warn => Ok(LintLevel::Warn),
+        Level::Deny => Ok(LintLevel::Deny),
+        Level::Forbid => Ok(LintLevel::Forbid),
+        level => Err(unsupported_value(lint, "default level", &level)),
+    }
+}
+
+fn macro_report(lint: &Lint) -> Result<String> {
+    match lint.report_in_macro {
+        MacroReport::No => Ok("no".to_string()),
+        MacroReport::Local => Ok("local".to_string()),
+        MacroReport::Crates(crates) => Ok(format!("local, {}", crates.get().iter().map(FfiStr::get).join(", "))),
+        MacroReport::All => Ok("all".to_string()),
+        report => Err(unsupported_value(lint, "macro report", &report)),
+    }
+}
+
+/// The API enums are non-exhaustive. Lint crates compiled against a newer
+/// version of `marker_api` could use values, which are unknown to this version.
+fn unsupported_value(lint: &Lint, field: &str, value: &dyn std::fmt::Debug) -> Error {
+    Error::root(format!(
+        "The lint {} uses the {field} `{value:?}`, which isn't supported by this version of cargo-marker",
+        lint.name
+    ))
+}
+
+/// Doc comments are collected with the space after the `///`, which is removed
+/// from every line here.
+fn docs(explanation: &str) -> String {
+    explanation
+        .lines()
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint_with_docs(docs: &str) -> LintMetadata {
+        LintMetadata {
+            name: "marker::lint_crate::lint_name".to_string(),
+            krate: "lint_crate".to_string(),
+            default_level: LintLevel::Warn,
+            report_in_macro: "no".to_string(),
+            groups: vec![],
+            docs: docs.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_summary() {
+        let lint = lint_with_docs("### What it does\n\nChecks for things,\n  which are bad.\n\n### Example\nmore text");
+        assert_eq!(lint.summary(), "Checks for things, which are bad.");
+
+        let lint = lint_with_docs("A short description.\n\nDetails");
+        assert_eq!(lint.summary(), "A short description.");
+
+        let lint = lint_with_docs("### Only a heading");
+        assert_eq!(lint.summary(), "");
+        assert_eq!(lint_with_docs("").summary(), "");
+    }
+
+    #[test]
+    fn test_docs() {
+        assert_eq!(docs(" # Title\n Text\n\n  indented\n"), "# Title\nText\n\n indented");
+    }
+}
@@ -3,6 +3,7 @@ use crate::observability::prelude::*;
 use crate::utils::{is_local_driver, utf8::IntoUtf8};
 use crate::Result;
 use camino::{Utf8Path, Utf8PathBuf};
+use cargo_metadata::Metadata;
 use std::process::Command;
 use yansi::Paint;
 
@@ -30,7 +31,7 @@ impl Toolchain {
         cmd
     }
 
-    pub fn cargo_build_command(&self, config: &Config, manifest: &Utf8Path) -> Command {
+    pub fn cargo_build_command(&self, config: &Config, manifest: &Utf8Path, target_dir: &Utf8Path) -> Command {
         let mut cmd = self.cargo.command();
         cmd.arg("build");
 
@@ -40,27 +41,31 @@ impl Toolchain {
 
         // Target dir
         cmd.arg("--target-dir");
-        cmd.arg(config.markers_target_dir().as_os_str());
+        cmd.arg(target_dir.as_os_str());
 
         // Potential "--release" flag
         if !config.debug_build {
             cmd.arg("--release");
         }
 
+        // Potential "--locked" and "--offline" flags
+        cmd.args(config.cargo_lock_args());
+
         // Environment
         cmd.env("RUSTFLAGS", &config.build_rustc_flags);
 
         cmd
     }
 
-    pub fn find_target_dir(&self) -> Result<Utf8PathBuf> {
-        let metadata = self
-            .cargo
+    /// Returns the metadata of the current workspace, without dependencies.
+    /// This contains the target directory, the workspace members and their
+    /// `[package.metadata]` sections.
+    pub fn workspace_metadata(&self) -> Result<Metadata> {
+        self.cargo
             .metadata()
+            .no_deps()
             .exec()
-            .context(|| "Coudln't find the target directory")?;
-
-        Ok(metadata.target_directory)
+            .context(|| "Failed to get the metadata of the workspace")
     }
 
     pub fn try_find_toolchain() -> Result<Toolchain> {
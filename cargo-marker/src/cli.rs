@@ -1,9 +1,17 @@
+mod cache;
 mod check;
+mod doc;
+mod explain;
+mod lints;
+mod markdown;
 mod setup;
 mod test_setup;
+mod update;
 
+use crate::backend::{self, toolchain::Toolchain};
 use crate::config::Config;
 use crate::error::prelude::*;
+use crate::utils;
 use clap::{Parser, Subcommand};
 
 /// Marker's CLI interface
@@ -43,6 +51,21 @@ pub(crate) enum CliCommand {
     /// Setup the rustc driver for Marker
     Setup(setup::SetupCommand),
 
+    /// List the lints of the configured lint crates
+    Lints(lints::LintsCommand),
+
+    /// Print the documentation of a lint
+    Explain(explain::ExplainCommand),
+
+    /// Generate the documentation of the lints of the configured lint crates
+    Doc(doc::DocCommand),
+
+    /// Inspect or clean the global cache for compiled lint crates
+    Cache(cache::CacheCommand),
+
+    /// Update the lint crates and their dependencies in the `Marker.lock` file
+    Update(update::UpdateCommand),
+
     /// **UNSTABLE** Setup the specified lint crate for ui tests
     #[command(hide = true)]
     TestSetup(test_setup::TestSetupCommand),
@@ -56,16 +79,44 @@ impl MarkerCli {
     }
 
     pub(crate) fn run(self) -> Result {
-        let manifest_path = crate::backend::cargo::Cargo::default().cargo_locate_project()?;
-        let config = Config::try_from_manifest(&manifest_path)?;
+        // The global cache is shared between workspaces, the command
+        // doesn't require a manifest.
+        if let Some(CliCommand::Cache(cmd)) = &self.command {
+            return cmd.run();
+        }
+
+        // The setup only installs the driver. It shouldn't fail due to an
+        // invalid configuration of the workspace.
+        let command = match self.command {
+            Some(CliCommand::Setup(cmd)) => return cmd.run(),
+            command => command,
+        };
+
+        // If this is a dev build, we want to rebuild the driver before checking
+        if utils::is_local_driver() {
+            backend::driver::install_driver(false, None)?;
+        }
+
+        // The metadata of the workspace is requested once. It's used to load
+        // the configurations of the workspace and its members and to create
+        // the base configuration of the backend.
+        let toolchain = Toolchain::try_find_toolchain()?;
+        let metadata = toolchain.workspace_metadata()?;
+        let config = Config::try_from_metadata(&metadata)?;
+        let base = backend::Config::base_from(toolchain, &metadata);
 
-        let Some(command) = self.command else {
-            return self.check.run(config);
+        let Some(command) = command else {
+            return self.check.run(config, base);
         };
         match command {
-            CliCommand::Setup(cmd) => cmd.run(),
-            CliCommand::Check(cmd) => cmd.run(config),
-            CliCommand::TestSetup(cmd) => cmd.run(config),
+            CliCommand::Setup(_) => unreachable!("the setup command is handled above"),
+            CliCommand::Check(cmd) => cmd.run(config, base),
+            CliCommand::Lints(cmd) => cmd.run(config, base),
+            CliCommand::Explain(cmd) => cmd.run(config, base),
+            CliCommand::Doc(cmd) => cmd.run(config, base),
+            CliCommand::Cache(_) => unreachable!("the cache command is handled above"),
+            CliCommand::Update(cmd) => cmd.run(config, base),
+            CliCommand::TestSetup(cmd) => cmd.run(config, base),
         }
     }
 }
@@ -73,6 +124,7 @@ impl MarkerCli {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::LintLevel;
     #[test]
     fn verify_cli() {
         use clap::CommandFactory;
@@ -94,6 +146,58 @@ mod tests {
         assert!(cli.check.cargo_args[0] == "ducks");
         assert!(cli.check.cargo_args[1] == "penguins");
 
+        let cli = MarkerCli::parse_from(["cargo-marker", "--frozen"]);
+        assert!(cli.check.lint_crates.frozen);
+
+        let cli = MarkerCli::parse_from(["cargo-marker", "update", "-p", "marker_lints"]);
+        assert!(matches!(cli.command, Some(CliCommand::Update(_))));
+
+        let cli = MarkerCli::parse_from([
+            "cargo-marker",
+            "-W",
+            "marker::a::b",
+            "-A",
+            "marker::a::*",
+            "--deny=marker::c::d",
+        ]);
+        assert_eq!(
+            cli.check.levels.levels,
+            [
+                ("marker::a::b".to_string(), LintLevel::Warn),
+                ("marker::a::*".to_string(), LintLevel::Allow),
+                ("marker::c::d".to_string(), LintLevel::Deny),
+            ]
+        );
+
+        let cli = MarkerCli::parse_from(["cargo-marker", "--global-cache"]);
+        assert!(cli.check.lint_crates.global_cache);
+
+        let cli = MarkerCli::parse_from(["cargo-marker", "lints", "--format", "json", "--frozen"]);
+        if let Some(CliCommand::Lints(lints)) = cli.command {
+            assert_eq!(lints.format, lints::LintsFormat::Json);
+            assert!(lints.lint_crates.frozen);
+        } else {
+            panic!("the `lints` subcommand was not detected");
+        }
+
+        let cli = MarkerCli::parse_from([
+            "cargo-marker",
+            "explain",
+            "marker::marker_lints::not_using_has_span_trait",
+        ]);
+        assert!(matches!(cli.command, Some(CliCommand::Explain(_))));
+
+        let cli = MarkerCli::parse_from(["cargo-marker", "doc", "--format", "markdown", "-o", "lints.md"]);
+        if let Some(CliCommand::Doc(doc)) = cli.command {
+            assert_eq!(doc.format, doc::DocFormat::Markdown);
+            assert_eq!(doc.output.as_deref(), Some(camino::Utf8Path::new("lints.md")));
+        } else {
+            panic!("the `doc` subcommand was not detected");
+        }
+
+        let cli = MarkerCli::parse_from(["cargo-marker", "cache", "clean", "marker_lints"]);
+        assert!(matches!(cli.command, Some(CliCommand::Cache(_))));
+
         let cli = MarkerCli::parse_from(["cargo-marker", "check", "--", "ducks", "penguins"]);
         assert!(cli.check.cargo_args.is_empty());
         if let Some(CliCommand::Check(check_args)) = cli.command {
@@ -0,0 +1,59 @@
+use crate::backend::cache::{self, Cache};
+use crate::error::prelude::*;
+use clap::{Args, Subcommand};
+use yansi::Paint;
+
+#[derive(Args, Debug)]
+pub(crate) struct CacheCommand {
+    #[command(subcommand)]
+    pub(crate) command: CacheSubcommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum CacheSubcommand {
+    /// List the lint crates stored in the global cache
+    List,
+
+    /// Remove lint crates from the global cache
+    Clean {
+        /// Only remove the entries of these lint crates. All entries will be
+        /// removed, if this isn't specified
+        #[arg(value_name = "CRATE")]
+        crates: Vec<String>,
+    },
+}
+
+impl CacheCommand {
+    pub(crate) fn run(&self) -> Result {
+        let cache = Cache::new(cache::cache_dir()?);
+
+        match &self.command {
+            CacheSubcommand::List => {
+                let entries = cache.entries()?;
+                if entries.is_empty() {
+                    println!("The global cache at {} is empty", cache.dir());
+                    return Ok(());
+                }
+
+                println!("The global cache at {} contains:", cache.dir());
+                for entry in entries {
+                    println!(
+                        "    {} v{} ({}) for marker v{} on {}",
+                        entry.name.bold(),
+                        entry.version,
+                        entry.source,
+                        entry.driver_version,
+                        entry.toolchain,
+                    );
+                    println!("        {}", entry.file());
+                }
+            },
+            CacheSubcommand::Clean { crates } => {
+                let removed = cache.clean(crates)?;
+                println!("Removed {removed} entries from the global cache at {}", cache.dir());
+            },
+        }
+
+        Ok(())
+    }
+}
@@ -1,56 +1,142 @@
-use crate::config::{Config, LintDependency};
+use crate::backend;
+use crate::config::{self, Config, LintDependency, LintDependencyEntry, LintLevel};
 use crate::error::prelude::*;
-use crate::{backend, utils};
 use camino::Utf8Path;
-use clap::Args;
+use clap::{Arg, ArgAction, ArgMatches, Args, Command, FromArgMatches};
 use std::collections::BTreeMap;
+use yansi::Paint;
 
 #[derive(Args, Debug)]
 #[command(override_usage = "cargo marker check [OPTIONS] -- <CARGO ARGS>")]
+#[allow(clippy::struct_excessive_bools)]
 pub(crate) struct CheckCommand {
-    /// Specifies lint crates which should be used. (Lints in `Cargo.toml` will be ignored)
-    #[arg(short, long)]
-    pub(crate) lints: Vec<String>,
+    #[command(flatten)]
+    pub(crate) lint_crates: LintCrateArgs,
 
     /// Forward the current `RUSTFLAGS` value during the lint crate compilation
     #[arg(long)]
     pub(crate) forward_rust_flags: bool,
 
+    /// Report every construct in the linted crate, which can't be represented
+    /// by Marker's API yet and was replaced by a placeholder
+    #[arg(long)]
+    pub(crate) report_unsupported: bool,
+
+    /// Keep nested `format_args!` calls and literal format arguments, which
+    /// rustc would otherwise flatten and inline during the lowering
+    #[arg(long)]
+    pub(crate) unflatten_format_args: bool,
+
+    #[command(flatten)]
+    pub(crate) levels: LintLevelArgs,
+
     /// Arguments which will be forwarded to Cargo. See `cargo check --help`
     #[clap(last = true)]
     pub(crate) cargo_args: Vec<String>,
 }
 
-impl CheckCommand {
-    pub(crate) fn run(self, config: Option<Config>) -> Result {
-        self.compile_lints(config)?.lint()
+/// The arguments, which select and compile the lint crates. These are shared
+/// by all commands, which require compiled lint crates.
+#[derive(Args, Debug)]
+#[allow(clippy::struct_excessive_bools)]
+pub(crate) struct LintCrateArgs {
+    /// Specifies lint crates which should be used. (Lints in `Cargo.toml` will be ignored)
+    #[arg(short, long)]
+    pub(crate) lints: Vec<String>,
+
+    /// Require the `Marker.lock` file to be up to date
+    #[arg(long)]
+    pub(crate) locked: bool,
+
+    /// Require the `Marker.lock` file to be up to date and run without
+    /// accessing the network. This is equivalent to `--locked --offline`
+    #[arg(long)]
+    pub(crate) frozen: bool,
+
+    /// Run without accessing the network
+    #[arg(long)]
+    pub(crate) offline: bool,
+
+    /// Use the global cache for compiled lint crates, which is shared between
+    /// workspaces. The cache is also enabled by setting `MARKER_CACHE_DIR`
+    #[arg(long)]
+    pub(crate) global_cache: bool,
+}
+
+/// The lint levels specified with `-A`, `-W`, `-D` and `-F`. The levels are
+/// kept in the order of the arguments, to allow later arguments to override
+/// earlier ones, like in rustc. Clap's derive macro would group the values
+/// by flag, which is why the arguments are defined manually.
+#[derive(Debug, Default)]
+pub(crate) struct LintLevelArgs {
+    pub(crate) levels: Vec<(String, LintLevel)>,
+}
+
+/// The ID, short flag, level and help message of the lint level arguments.
+const LINT_LEVEL_ARGS: [(&str, char, LintLevel, &str); 4] = [
+    (
+        "allow",
+        'A',
+        LintLevel::Allow,
+        "Set the level of a lint to `allow`. All lints of a lint crate can be selected with `marker::lint_crate::*`",
+    ),
+    ("warn", 'W', LintLevel::Warn, "Set the level of a lint to `warn`"),
+    ("deny", 'D', LintLevel::Deny, "Set the level of a lint to `deny`"),
+    ("forbid", 'F', LintLevel::Forbid, "Set the level of a lint to `forbid`"),
+];
+
+impl Args for LintLevelArgs {
+    fn augment_args(cmd: Command) -> Command {
+        LINT_LEVEL_ARGS.iter().fold(cmd, |cmd, (id, short, _, help)| {
+            cmd.arg(
+                Arg::new(*id)
+                    .short(*short)
+                    .long(*id)
+                    .value_name("LINT")
+                    .action(ArgAction::Append)
+                    .help(*help),
+            )
+        })
     }
 
-    pub(crate) fn compile_lints(self, config: Option<Config>) -> Result<CompiledLints> {
-        // determine lints
-        let lints: BTreeMap<_, _> = self
-            .lints_from_cli()?
-            .or_else(|| config.map(|config| config.lints))
-            .into_iter()
-            .flatten()
-            .map(|(name, dep)| (name, dep.into_dep_entry()))
-            .collect();
+    fn augment_args_for_update(cmd: Command) -> Command {
+        Self::augment_args(cmd)
+    }
+}
 
-        // Validation
-        if lints.is_empty() {
-            return Err(Error::from_kind(ErrorKind::LintsNotFound));
+impl FromArgMatches for LintLevelArgs {
+    fn from_arg_matches(matches: &ArgMatches) -> std::result::Result<Self, clap::Error> {
+        let mut levels = vec![];
+        for (id, _, level, _) in LINT_LEVEL_ARGS {
+            let (Some(indices), Some(lints)) = (matches.indices_of(id), matches.get_many::<String>(id)) else {
+                continue;
+            };
+            levels.extend(indices.zip(lints).map(|(index, lint)| (index, lint.clone(), level)));
         }
+        levels.sort_by_key(|(index, ..)| *index);
 
-        // If this is a dev build, we want to rebuild the driver before checking
-        if utils::is_local_driver() {
-            backend::driver::install_driver(false, None)?;
-        }
+        Ok(Self {
+            levels: levels.into_iter().map(|(_, lint, level)| (lint, level)).collect(),
+        })
+    }
 
-        // Configure backend
-        let toolchain = backend::toolchain::Toolchain::try_find_toolchain()?;
+    fn update_from_arg_matches(&mut self, matches: &ArgMatches) -> std::result::Result<(), clap::Error> {
+        *self = Self::from_arg_matches(matches)?;
+        Ok(())
+    }
+}
+
+impl CheckCommand {
+    pub(crate) fn run(self, config: Option<Config>, base: backend::Config) -> Result {
+        self.compile_lints(config, base)?.lint()
+    }
+
+    pub(crate) fn compile_lints(self, config: Option<Config>, base: backend::Config) -> Result<CompiledLints> {
+        let cli_levels = self.levels_from_cli()?;
         let backend_conf = backend::Config {
-            lints,
-            ..backend::Config::try_base_from(toolchain)?
+            report_unsupported: self.report_unsupported,
+            unflatten_format_args: self.unflatten_format_args,
+            ..self.lint_crates.backend_config(config, cli_levels, base)?
         };
 
         // Prepare backend
@@ -63,6 +149,64 @@ impl CheckCommand {
         })
     }
 
+    /// Returns the lint levels specified with `-A`, `-W`, `-D` and `-F`, in
+    /// the order of the arguments. Like in rustc, a later argument overrides
+    /// earlier ones, even if it selects all lints of a lint crate.
+    fn levels_from_cli(&self) -> Result<Vec<(String, LintLevel)>> {
+        self.levels
+            .levels
+            .iter()
+            .map(|(lint, level)| Ok((config::normalize_lint_name(lint)?, *level)))
+            .collect()
+    }
+}
+
+impl LintCrateArgs {
+    /// Creates the configuration of the backend, with the selected lint crates
+    /// and the given levels from the command line. All other values are taken
+    /// from the `base` configuration.
+    pub(crate) fn backend_config(
+        &self,
+        config: Option<Config>,
+        cli_levels: Vec<(String, LintLevel)>,
+        base: backend::Config,
+    ) -> Result<backend::Config> {
+        // determine lints. The `Marker.lock` file and the configurations of
+        // packages are only used for lints specified in the `Cargo.toml` file.
+        let (lints, lock_file, packages) = match (self.lints_from_cli()?, config) {
+            (Some(lints), _) => {
+                let lints = lints
+                    .into_iter()
+                    .map(|(name, dep)| (name, dep.into_dep_entry()))
+                    .collect();
+                let packages = package_lints(&lints, None, cli_levels)?;
+                (lints, None, packages)
+            },
+            (None, Some(config)) => {
+                let lints = config.all_lints()?;
+                let packages = package_lints(&lints, Some(&config), cli_levels)?;
+                (lints, Some(config.lock_file), packages)
+            },
+            (None, None) => Default::default(),
+        };
+
+        // Validation
+        if lints.is_empty() {
+            return Err(Error::from_kind(ErrorKind::LintsNotFound));
+        }
+
+        // Configure backend
+        Ok(backend::Config {
+            lints,
+            packages,
+            lock_file,
+            locked: self.locked || self.frozen,
+            offline: self.offline || self.frozen,
+            global_cache: backend::cache::enabled_cache_dir(self.global_cache)?,
+            ..base
+        })
+    }
+
     fn lints_from_cli(&self) -> Result<Option<BTreeMap<String, LintDependency>>> {
         if self.lints.is_empty() {
             return Ok(None);
@@ -76,7 +220,7 @@ impl CheckCommand {
 
         let path = Utf8Path::new(".");
 
-        let Config { lints } = Config::try_from_str(&virtual_manifest, path)?.unwrap_or_else(|| {
+        let Config { lints, .. } = Config::try_from_str(&virtual_manifest, path)?.unwrap_or_else(|| {
             panic!(
                 "BUG: the config must definitely contain the marker metadata:\
                 \n---\n{virtual_manifest}\n---"
@@ -87,6 +231,85 @@ impl CheckCommand {
     }
 }
 
+/// Returns the lint crates and levels of the packages, which have their own
+/// configuration, together with the default for all other packages.
+///
+/// The levels are applied in the following order, to allow more specific
+/// levels to override others: The levels of the workspace, the levels of the
+/// package and the levels from the command line.
+fn package_lints(
+    lints: &BTreeMap<String, LintDependencyEntry>,
+    config: Option<&Config>,
+    cli_levels: Vec<(String, LintLevel)>,
+) -> Result<Vec<backend::PackageLints>> {
+    let table_levels = |levels: &BTreeMap<String, LintLevel>| {
+        let mut levels: Vec<_> = levels.iter().map(|(lint, level)| (lint.clone(), *level)).collect();
+        config::sort_levels(&mut levels);
+        levels
+    };
+
+    let mut packages = vec![];
+    let Some(config) = config else {
+        packages.push(backend::PackageLints {
+            manifest_dir: None,
+            lints: lints.keys().cloned().collect(),
+            levels: cli_levels,
+        });
+        return validate_levels(packages, lints);
+    };
+
+    let workspace_levels = table_levels(&config.levels);
+    packages.push(backend::PackageLints {
+        manifest_dir: None,
+        lints: config.lints.keys().cloned().collect(),
+        levels: [workspace_levels.clone(), cli_levels.clone()].concat(),
+    });
+    for package in &config.packages {
+        packages.push(backend::PackageLints {
+            manifest_dir: Some(package.manifest_dir.clone()),
+            lints: package.lint_crates(&config.lints),
+            levels: [
+                workspace_levels.clone(),
+                table_levels(&package.levels),
+                cli_levels.clone(),
+            ]
+            .concat(),
+        });
+    }
+
+    validate_levels(packages, lints)
+}
+
+/// This function checks, that all levels belong to a known lint crate. Levels
+/// of lint crates, that aren't used by a package, are removed. Otherwise,
+/// rustc would report them as unknown lints.
+fn validate_levels(
+    mut packages: Vec<backend::PackageLints>,
+    lints: &BTreeMap<String, LintDependencyEntry>,
+) -> Result<Vec<backend::PackageLints>> {
+    let crate_name = |name: &str| name.replace('-', "_");
+
+    for package in &mut packages {
+        if let Some((lint, _)) = package
+            .levels
+            .iter()
+            .find(|(lint, _)| !lints.keys().any(|name| crate_name(name) == config::lint_crate_of(lint)))
+        {
+            return Err(Error::root(format!(
+                "The lint {} doesn't belong to any of the specified lint crates",
+                lint.red().bold()
+            )));
+        }
+
+        let crates: Vec<_> = package.lints.iter().map(|name| crate_name(name)).collect();
+        package
+            .levels
+            .retain(|(lint, _)| crates.iter().any(|krate| krate == config::lint_crate_of(lint)));
+    }
+
+    Ok(packages)
+}
+
 /// The result of discovering and compiling the lint libraries
 #[derive(Debug)]
 pub(crate) struct CompiledLints {
@@ -100,3 +323,46 @@ impl CompiledLints {
         backend::run_check(&self.backend_conf, self.info, &self.cargo_args)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn test_levels_from_cli_order() {
+        #[derive(Parser)]
+        struct Cli {
+            #[command(flatten)]
+            check: CheckCommand,
+        }
+
+        let cli = Cli::parse_from([
+            "check",
+            "-D",
+            "marker::a::lint",
+            "-A",
+            "marker::a::*",
+            "-W",
+            "marker::b::lint-name",
+            "--allow=marker::a::lint",
+            "-F",
+            "marker::b::*",
+            "-D",
+            "marker::a::*",
+        ]);
+        let levels = cli.check.levels_from_cli().unwrap();
+        let levels: Vec<_> = levels.iter().map(|(lint, level)| (lint.as_str(), *level)).collect();
+        assert_eq!(
+            levels,
+            [
+                ("marker::a::lint", LintLevel::Deny),
+                ("marker::a::*", LintLevel::Allow),
+                ("marker::b::lint_name", LintLevel::Warn),
+                ("marker::a::lint", LintLevel::Allow),
+                ("marker::b::*", LintLevel::Forbid),
+                ("marker::a::*", LintLevel::Deny),
+            ]
+        );
+    }
+}
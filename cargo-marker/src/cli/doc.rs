@@ -0,0 +1,57 @@
+mod html;
+
+use super::check::LintCrateArgs;
+use super::markdown;
+use crate::backend;
+use crate::config::Config;
+use crate::error::prelude::*;
+use crate::observability::display;
+use camino::Utf8PathBuf;
+use clap::{Args, ValueEnum};
+
+#[derive(Args, Debug)]
+pub(crate) struct DocCommand {
+    /// The format of the generated documentation
+    #[arg(long, value_enum, default_value_t = DocFormat::Html)]
+    pub(crate) format: DocFormat,
+
+    /// The file, which the documentation should be written to. This defaults
+    /// to `lints.html` or `lints.md` in the `target/marker/doc` directory
+    #[arg(short, long, value_name = "PATH")]
+    pub(crate) output: Option<Utf8PathBuf>,
+
+    #[command(flatten)]
+    pub(crate) lint_crates: LintCrateArgs,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DocFormat {
+    /// A single HTML page, which can be searched and filtered
+    Html,
+    /// A Markdown document
+    Markdown,
+}
+
+impl DocCommand {
+    pub(crate) fn run(self, config: Option<Config>, base: backend::Config) -> Result {
+        let backend_conf = self.lint_crates.backend_config(config, vec![], base)?;
+        let crates = backend::lints::load_lint_metadata(&backend_conf)?;
+
+        let (content, file_name) = match self.format {
+            DocFormat::Html => (html::page(&crates), "lints.html"),
+            DocFormat::Markdown => (markdown::render(&crates), "lints.md"),
+        };
+
+        let output = self
+            .output
+            .unwrap_or_else(|| backend_conf.marker_dir.join("doc").join(file_name));
+        if let Some(dir) = output.parent().filter(|dir| !dir.as_str().is_empty()) {
+            std::fs::create_dir_all(dir).context(|| format!("Failed to create the directory {dir}"))?;
+        }
+        std::fs::write(&output, content).context(|| format!("Failed to write the documentation to {output}"))?;
+
+        eprintln!("{}", display::status("Generated", &output));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,434 @@
+//! This module generates a single, self-contained HTML page, which lists all
+//! lints and lint groups. The page contains a small script, to search the lints
+//! by their name, crate, groups and summary and to filter them by their level.
+//!
+//! The documentation of lints is written in Markdown. It's converted to HTML
+//! by a simple converter, which supports the subset of Markdown that is
+//! commonly used in lint descriptions.
+
+use crate::backend::lints::LintCrateMetadata;
+use crate::cli::markdown::nest_headings;
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+const STYLE: &str = r"
+body { font-family: sans-serif; margin: 0; color: #222; background: #fafafa; }
+header { position: sticky; top: 0; padding: 1em 2em; background: #fff; border-bottom: 1px solid #ddd; }
+header h1 { margin: 0 0 0.3em 0; }
+header p { margin: 0 0 0.6em 0; color: #666; }
+main { max-width: 60em; margin: 0 auto; padding: 1em 2em; }
+input, select { font-size: 1em; padding: 0.3em; }
+#search { width: 25em; }
+article { margin: 1em 0; padding: 0.5em 1.5em; background: #fff; border: 1px solid #ddd; border-radius: 4px; }
+article h2 a { color: inherit; text-decoration: none; font-family: monospace; }
+.meta { display: flex; flex-wrap: wrap; gap: 0.5em; margin-bottom: 1em; }
+.meta span { padding: 0.1em 0.5em; background: #eee; border-radius: 3px; font-size: 0.9em; }
+.level-allow { background: #e6f4ea !important; }
+.level-warn { background: #fff4e5 !important; }
+.level-deny, .level-forbid { background: #fde8e8 !important; }
+pre { padding: 0.8em; background: #f4f4f4; overflow-x: auto; }
+code { font-family: monospace; }
+.hidden { display: none; }
+";
+
+const SCRIPT: &str = r#"
+const search = document.getElementById("search");
+const level = document.getElementById("level");
+function filterLints() {
+    const terms = search.value.toLowerCase().split(/\s+/).filter(term => term.length > 0);
+    for (const lint of document.querySelectorAll("article.lint")) {
+        const text = lint.dataset.search;
+        const visible = terms.every(term => text.includes(term))
+            && (level.value === "" || lint.dataset.level === level.value);
+        lint.classList.toggle("hidden", !visible);
+    }
+}
+search.addEventListener("input", filterLints);
+level.addEventListener("change", filterLints);
+filterLints();
+"#;
+
+/// Creates the HTML page, with the documentation of all lints and lint groups.
+pub(super) fn page(crates: &[LintCrateMetadata]) -> String {
+    let crate_list = crates
+        .iter()
+        .map(|krate| format!("{} v{}", escape(&krate.name), escape(&krate.version)))
+        .join(", ");
+
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    out.push_str("<title>Marker Lints</title>\n");
+    let _ = writeln!(out, "<style>{STYLE}</style>");
+    out.push_str("</head>\n<body>\n<header>\n<h1>Marker Lints</h1>\n");
+    let _ = writeln!(
+        out,
+        "<p>Lint crates: {crate_list}. Generated by cargo-marker v{}.</p>",
+        env!("CARGO_PKG_VERSION")
+    );
+    out.push_str("<input id=\"search\" type=\"search\" placeholder=\"Search lints, crates and groups\" autofocus>\n");
+    out.push_str("<select id=\"level\">\n<option value=\"\">All levels</option>\n");
+    for level in ["allow", "warn", "deny", "forbid"] {
+        let _ = writeln!(out, "<option value=\"{level}\">{level}</option>");
+    }
+    out.push_str("</select>\n</header>\n<main>\n");
+
+    for krate in crates {
+        for lint in &krate.lints {
+            let level = lint.default_level.as_str();
+            let summary = lint.summary();
+            let search = [lint.name.as_str(), krate.name.as_str(), summary.as_str()]
+                .into_iter()
+                .chain(lint.groups.iter().map(String::as_str))
+                .join(" ")
+                .to_lowercase();
+            let name = escape(&lint.name);
+
+            let _ = writeln!(
+                out,
+                "<article class=\"lint\" id=\"{name}\" data-level=\"{level}\" data-search=\"{}\">",
+                escape(&search)
+            );
+            let _ = writeln!(out, "<h2><a href=\"#{name}\">{name}</a></h2>");
+            out.push_str("<div class=\"meta\">\n");
+            let _ = writeln!(out, "<span class=\"level-{level}\">Level: {level}</span>");
+            let _ = writeln!(
+                out,
+                "<span>Crate: {} v{}</span>",
+                escape(&krate.name),
+                escape(&krate.version)
+            );
+            let _ = writeln!(
+                out,
+                "<span>Reported in macros: {}</span>",
+                escape(&lint.report_in_macro)
+            );
+            for group in &lint.groups {
+                let group = escape(group);
+                let _ = writeln!(out, "<span>Group: <a href=\"#{group}\">{group}</a></span>");
+            }
+            out.push_str("</div>\n");
+            out.push_str(&markdown_to_html(&nest_headings(&lint.docs, 3)));
+            out.push_str("</article>\n");
+        }
+    }
+
+    if crates.iter().any(|krate| !krate.groups.is_empty()) {
+        out.push_str("<h1>Lint Groups</h1>\n");
+    }
+    for krate in crates {
+        for group in &krate.groups {
+            let name = escape(&group.name);
+            let _ = writeln!(out, "<article class=\"group\" id=\"{name}\">");
+            let _ = writeln!(out, "<h2><a href=\"#{name}\">{name}</a></h2>");
+            out.push_str("<div class=\"meta\">\n");
+            let _ = writeln!(
+                out,
+                "<span>Crate: {} v{}</span>",
+                escape(&krate.name),
+                escape(&krate.version)
+            );
+            out.push_str("</div>\n");
+            out.push_str(&markdown_to_html(&nest_headings(&group.docs, 3)));
+            out.push_str("<ul>\n");
+            for lint in &group.lints {
+                let lint = escape(lint);
+                let _ = writeln!(out, "<li><a href=\"#{lint}\"><code>{lint}</code></a></li>");
+            }
+            out.push_str("</ul>\n</article>\n");
+        }
+    }
+
+    let _ = writeln!(out, "</main>\n<script>{SCRIPT}</script>\n</body>\n</html>");
+    out
+}
+
+/// Converts the given Markdown into HTML. This supports headings, paragraphs,
+/// lists, code blocks, inline code, emphasis and links.
+fn markdown_to_html(markdown: &str) -> String {
+    // Link reference definitions, like `[label]: <https://example.com>`
+    let mut references = HashMap::new();
+    let mut in_code_block = false;
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+        } else if !in_code_block {
+            if let Some((label, url)) = reference_definition(line) {
+                references.insert(label, url);
+            }
+        }
+    }
+
+    let mut out = String::new();
+    let mut paragraph: Vec<&str> = vec![];
+    let mut list: Option<(&str, Vec<String>)> = None;
+    let mut code_block: Option<Vec<&str>> = None;
+
+    let flush = |out: &mut String, paragraph: &mut Vec<&str>, list: &mut Option<(&str, Vec<String>)>| {
+        if !paragraph.is_empty() {
+            let _ = writeln!(out, "<p>{}</p>", inline(&paragraph.join(" "), &references));
+            paragraph.clear();
+        }
+        if let Some((tag, items)) = list.take() {
+            let _ = writeln!(out, "<{tag}>");
+            for item in items {
+                let _ = writeln!(out, "<li>{}</li>", inline(&item, &references));
+            }
+            let _ = writeln!(out, "</{tag}>");
+        }
+    };
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+
+        if let Some(code) = &mut code_block {
+            if trimmed.starts_with("```") {
+                let _ = writeln!(out, "<pre><code>{}</code></pre>", escape(&code.join("\n")));
+                code_block = None;
+            } else {
+                code.push(line);
+            }
+            continue;
+        }
+
+        if trimmed.starts_with("```") {
+            flush(&mut out, &mut paragraph, &mut list);
+            code_block = Some(vec![]);
+        } else if trimmed.is_empty() || reference_definition(line).is_some() {
+            flush(&mut out, &mut paragraph, &mut list);
+        } else if line.starts_with('#') {
+            flush(&mut out, &mut paragraph, &mut list);
+            let level = line.chars().take_while(|c| *c == '#').count().min(6);
+            let text = line.trim_start_matches('#').trim();
+            let _ = writeln!(out, "<h{level}>{}</h{level}>", inline(text, &references));
+        } else if let Some((tag, item)) = list_item(trimmed) {
+            if !paragraph.is_empty() || list.as_ref().map_or(false, |(list_tag, _)| *list_tag != tag) {
+                flush(&mut out, &mut paragraph, &mut list);
+            }
+            list.get_or_insert_with(|| (tag, vec![])).1.push(item.to_string());
+        } else if let Some((_, items)) = &mut list {
+            // A continuation of the last list item
+            let item = items.last_mut().expect("lists are created with an item");
+            item.push(' ');
+            item.push_str(trimmed);
+        } else {
+            paragraph.push(trimmed);
+        }
+    }
+
+    if let Some(code) = code_block {
+        let _ = writeln!(out, "<pre><code>{}</code></pre>", escape(&code.join("\n")));
+    }
+    flush(&mut out, &mut paragraph, &mut list);
+
+    out
+}
+
+/// Returns the tag of the list and the content of the item, if the line is
+/// an item of an ordered or unordered list.
+fn list_item(line: &str) -> Option<(&'static str, &str)> {
+    if let Some(item) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+        return Some(("ul", item));
+    }
+
+    let (number, item) = line.split_once(". ")?;
+    (!number.is_empty() && number.chars().all(|c| c.is_ascii_digit())).then_some(("ol", item))
+}
+
+/// Parses a link reference definition, like `[label]: <https://example.com>`.
+/// The label is returned in lowercase, as labels are case-insensitive.
+fn reference_definition(line: &str) -> Option<(String, String)> {
+    let rest = line.trim().strip_prefix('[')?;
+    let (label, url) = rest.split_once("]:")?;
+    let url = url.trim().trim_start_matches('<').trim_end_matches('>');
+    (!url.is_empty() && !url.contains(' ')).then(|| (label.to_lowercase(), url.to_string()))
+}
+
+/// Converts the inline elements of the given text into HTML.
+fn inline(text: &str, references: &HashMap<String, String>) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        match c {
+            '`' => {
+                if let Some(end) = rest[1..].find('`') {
+                    let _ = write!(out, "<code>{}</code>", escape(&rest[1..=end]));
+                    rest = &rest[end + 2..];
+                    continue;
+                }
+            },
+            '*' if rest.starts_with("**") => {
+                if let Some(end) = rest[2..].find("**") {
+                    let _ = write!(out, "<strong>{}</strong>", inline(&rest[2..end + 2], references));
+                    rest = &rest[end + 4..];
+                    continue;
+                }
+            },
+            // Underscores are ignored, as they're common in lint names
+            '*' if !rest[1..].starts_with(char::is_whitespace) => {
+                if let Some(end) = rest[1..].find('*').filter(|end| *end > 0) {
+                    let _ = write!(out, "<em>{}</em>", inline(&rest[1..=end], references));
+                    rest = &rest[end + 2..];
+                    continue;
+                }
+            },
+            '<' => {
+                if let Some(end) = rest.find('>') {
+                    let url = &rest[1..end];
+                    if url.starts_with("https://") || url.starts_with("http://") {
+                        let url = escape(url);
+                        let _ = write!(out, "<a href=\"{url}\">{url}</a>");
+                        rest = &rest[end + 1..];
+                        continue;
+                    }
+                }
+            },
+            '[' => {
+                if let Some(end) = rest.find(']') {
+                    let label = &rest[1..end];
+                    let after = &rest[end + 1..];
+                    let (url, remaining) = match after.strip_prefix('(').and_then(|after| after.split_once(')')) {
+                        Some((url, remaining)) => (Some(url), remaining),
+                        None => (references.get(&label.to_lowercase()).map(String::as_str), after),
+                    };
+                    // Intra-doc links, like [`LintPass`], can't be resolved. Links
+                    // with other schemes, like `javascript:`, are dropped.
+                    if let Some(url) = url.filter(|url| is_allowed_url(url)) {
+                        let _ = write!(out, "<a href=\"{}\">{}</a>", escape(url), inline(label, references));
+                    } else {
+                        out.push_str(&inline(label, references));
+                    }
+                    rest = remaining;
+                    continue;
+                }
+            },
+            _ => {},
+        }
+
+        out.push_str(&escape(&rest[..c.len_utf8()]));
+        rest = &rest[c.len_utf8()..];
+    }
+
+    out
+}
+
+/// Returns `true`, if the URL can be used as a link target. Only `http(s)`
+/// URLs, fragments and relative URLs are allowed.
+fn is_allowed_url(url: &str) -> bool {
+    if url.starts_with("https://") || url.starts_with("http://") {
+        return true;
+    }
+
+    // Relative URLs don't have a scheme, which would end with a `:` before
+    // the first `/`, `?` or `#`
+    let end = url.find(['/', '?', '#']).unwrap_or(url.len());
+    !url[..end].contains(':')
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::lints::metadata::{LintGroupMetadata, LintMetadata};
+    use crate::config::LintLevel;
+
+    #[test]
+    fn test_markdown_to_html() {
+        let markdown = "\
+### What it does
+Checks for `Box<T>`, see [the docs] and [`LintPass`].
+
+Use **this** instead:
+```rust
+let x = vec![1] < 2;
+```
+- first
+  item
+- second <https://example.com>
+
+[the docs]: <https://example.com/docs>";
+
+        expect_test::expect![[r#"
+            <h3>What it does</h3>
+            <p>Checks for <code>Box&lt;T&gt;</code>, see <a href="https://example.com/docs">the docs</a> and <code>LintPass</code>.</p>
+            <p>Use <strong>this</strong> instead:</p>
+            <pre><code>let x = vec![1] &lt; 2;</code></pre>
+            <ul>
+            <li>first item</li>
+            <li>second <a href="https://example.com">https://example.com</a></li>
+            </ul>
+        "#]]
+        .assert_eq(&markdown_to_html(markdown));
+    }
+
+    #[test]
+    fn test_markdown_link_urls() {
+        let markdown = "\
+[web](https://example.com), [section](#example), [file](../lints.md?x=1#a) and [protocol](//example.com)
+[script](javascript:run), [data](data:text/html,x) and [reference]
+
+[reference]: <vbscript:msgbox>";
+
+        expect_test::expect![[r##"
+            <p><a href="https://example.com">web</a>, <a href="#example">section</a>, <a href="../lints.md?x=1#a">file</a> and <a href="//example.com">protocol</a> script, data and reference</p>
+        "##]]
+        .assert_eq(&markdown_to_html(markdown));
+    }
+
+    #[test]
+    fn test_page_escaping() {
+        let crates = [LintCrateMetadata {
+            name: "lint_crate".to_string(),
+            version: "0.1.0".to_string(),
+            lints: vec![LintMetadata {
+                name: "marker::lint_crate::<b>\"lint\"".to_string(),
+                krate: "lint_crate".to_string(),
+                default_level: LintLevel::Warn,
+                report_in_macro: "no".to_string(),
+                groups: vec!["marker::lint_crate::'group'&".to_string()],
+                docs: "Checks for <b>things</b>.".to_string(),
+            }],
+            groups: vec![LintGroupMetadata {
+                name: "marker::lint_crate::'group'&".to_string(),
+                lints: vec!["marker::lint_crate::<b>\"lint\"".to_string()],
+                docs: "A group".to_string(),
+            }],
+        }];
+
+        let page = page(&crates);
+        let main = &page[page.find("<main>").unwrap()..page.find("</main>").unwrap()];
+        expect_test::expect![[r##"
+            <main>
+            <article class="lint" id="marker::lint_crate::&lt;b&gt;&quot;lint&quot;" data-level="warn" data-search="marker::lint_crate::&lt;b&gt;&quot;lint&quot; lint_crate checks for &lt;b&gt;things&lt;/b&gt;. marker::lint_crate::&#39;group&#39;&amp;">
+            <h2><a href="#marker::lint_crate::&lt;b&gt;&quot;lint&quot;">marker::lint_crate::&lt;b&gt;&quot;lint&quot;</a></h2>
+            <div class="meta">
+            <span class="level-warn">Level: warn</span>
+            <span>Crate: lint_crate v0.1.0</span>
+            <span>Reported in macros: no</span>
+            <span>Group: <a href="#marker::lint_crate::&#39;group&#39;&amp;">marker::lint_crate::&#39;group&#39;&amp;</a></span>
+            </div>
+            <p>Checks for &lt;b&gt;things&lt;/b&gt;.</p>
+            </article>
+            <h1>Lint Groups</h1>
+            <article class="group" id="marker::lint_crate::&#39;group&#39;&amp;">
+            <h2><a href="#marker::lint_crate::&#39;group&#39;&amp;">marker::lint_crate::&#39;group&#39;&amp;</a></h2>
+            <div class="meta">
+            <span>Crate: lint_crate v0.1.0</span>
+            </div>
+            <p>A group</p>
+            <ul>
+            <li><a href="#marker::lint_crate::&lt;b&gt;&quot;lint&quot;"><code>marker::lint_crate::&lt;b&gt;&quot;lint&quot;</code></a></li>
+            </ul>
+            </article>
+        "##]].assert_eq(main);
+    }
+}
@@ -0,0 +1,65 @@
+use super::check::LintCrateArgs;
+use crate::backend;
+use crate::config::Config;
+use crate::error::prelude::*;
+use crate::observability::display;
+use clap::Args;
+use itertools::Itertools;
+use yansi::Paint;
+
+#[derive(Args, Debug)]
+pub(crate) struct ExplainCommand {
+    /// The name of the lint, like `marker::lint_crate::lint_name`. The
+    /// `marker::` prefix and the name of the lint crate can be omitted
+    #[arg(value_name = "LINT")]
+    pub(crate) lint: String,
+
+    #[command(flatten)]
+    pub(crate) lint_crates: LintCrateArgs,
+}
+
+impl ExplainCommand {
+    pub(crate) fn run(self, config: Option<Config>, base: backend::Config) -> Result {
+        let backend_conf = self.lint_crates.backend_config(config, vec![], base)?;
+        let crates = backend::lints::load_lint_metadata(&backend_conf)?;
+
+        // Lint names are case insensitive and can use dashes, like in rustc
+        let name = self.lint.to_ascii_lowercase().replace('-', "_");
+        let matches: Vec<_> = crates
+            .iter()
+            .flat_map(|krate| krate.lints.iter().map(move |lint| (krate, lint)))
+            .filter(|(_, lint)| lint.name == name || lint.name.ends_with(&format!("::{name}")))
+            .collect();
+
+        let (krate, lint) = match matches.as_slice() {
+            [found] => *found,
+            [] => {
+                return Err(Error::root(format!(
+                    "The lint {} isn't provided by any of the lint crates: {}",
+                    self.lint.red().bold(),
+                    crates.iter().map(|krate| &krate.name).join(", ")
+                )));
+            },
+            _ => {
+                return Err(Error::root(format!(
+                    "The lint {} is ambiguous, it can refer to:\n{}",
+                    self.lint.red().bold(),
+                    matches.iter().map(|(_, lint)| format!("- {}", lint.name)).join("\n")
+                )));
+            },
+        };
+
+        println!("{}", lint.name.bold());
+        println!();
+        println!("Crate:              {} v{}", krate.name, krate.version);
+        println!("Default level:      {}", lint.default_level.as_str());
+        println!("Reported in macros: {}", lint.report_in_macro);
+        if !lint.groups.is_empty() {
+            println!("Groups:             {}", lint.groups.join(", "));
+        }
+        println!();
+        println!("{}", display::markdown(&lint.docs));
+
+        Ok(())
+    }
+}
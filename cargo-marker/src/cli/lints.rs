@@ -0,0 +1,71 @@
+use super::check::LintCrateArgs;
+use super::markdown;
+use crate::backend::{self, lints::LintCrateMetadata};
+use crate::config::Config;
+use crate::error::prelude::*;
+use clap::{Args, ValueEnum};
+use yansi::Paint;
+
+#[derive(Args, Debug)]
+pub(crate) struct LintsCommand {
+    /// The format, in which the lints should be printed
+    #[arg(long, value_enum, default_value_t = LintsFormat::Text)]
+    pub(crate) format: LintsFormat,
+
+    #[command(flatten)]
+    pub(crate) lint_crates: LintCrateArgs,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LintsFormat {
+    /// A short summary of every lint
+    Text,
+    /// A JSON array with the information of every lint
+    Json,
+    /// A Markdown document with the documentation of every lint
+    Markdown,
+}
+
+impl LintsCommand {
+    pub(crate) fn run(self, config: Option<Config>, base: backend::Config) -> Result {
+        let backend_conf = self.lint_crates.backend_config(config, vec![], base)?;
+        let crates = backend::lints::load_lint_metadata(&backend_conf)?;
+
+        match self.format {
+            LintsFormat::Text => print_text(&crates),
+            LintsFormat::Json => {
+                let lints: Vec<_> = crates.iter().flat_map(|krate| &krate.lints).collect();
+                let json = serde_json::to_string_pretty(&lints).expect("the lint information can be serialized");
+                println!("{json}");
+            },
+            LintsFormat::Markdown => print!("{}", markdown::render(&crates)),
+        }
+
+        Ok(())
+    }
+}
+
+fn print_text(crates: &[LintCrateMetadata]) {
+    for krate in crates {
+        for lint in &krate.lints {
+            println!("{}", lint.name.bold());
+            print!(
+                "    level: {}, macros: {}, crate: {} v{}",
+                lint.default_level.as_str(),
+                lint.report_in_macro,
+                krate.name,
+                krate.version
+            );
+            if lint.groups.is_empty() {
+                println!();
+            } else {
+                println!(", groups: {}", lint.groups.join(", "));
+            }
+            let summary = lint.summary();
+            if !summary.is_empty() {
+                println!("    {summary}");
+            }
+            println!();
+        }
+    }
+}
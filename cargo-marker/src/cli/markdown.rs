@@ -0,0 +1,136 @@
+//! This module renders the documentation of lints and lint groups as Markdown.
+//! It's used by the `lints` and `doc` commands.
+
+use crate::backend::lints::LintCrateMetadata;
+use itertools::Itertools;
+
+/// Creates a Markdown document, with the documentation of all lints and
+/// lint groups.
+pub(crate) fn render(crates: &[LintCrateMetadata]) -> String {
+    let mut out = String::from("# Lints\n");
+
+    for krate in crates {
+        for lint in &krate.lints {
+            let groups = if lint.groups.is_empty() {
+                "-".to_string()
+            } else {
+                lint.groups.iter().map(|group| format!("`{group}`")).join(", ")
+            };
+
+            out.push_str(&format!("\n## `{}`\n\n", lint.name));
+            out.push_str("| Crate | Default level | Reported in macros | Groups |\n");
+            out.push_str("|-------|---------------|--------------------|--------|\n");
+            out.push_str(&format!(
+                "| {} v{} | {} | {} | {groups} |\n",
+                krate.name,
+                krate.version,
+                lint.default_level.as_str(),
+                lint.report_in_macro,
+            ));
+            if !lint.docs.is_empty() {
+                out.push('\n');
+                out.push_str(&nest_headings(&lint.docs, 3));
+                out.push('\n');
+            }
+        }
+    }
+
+    if crates.iter().any(|krate| !krate.groups.is_empty()) {
+        out.push_str("\n# Lint groups\n");
+    }
+    for krate in crates {
+        for group in &krate.groups {
+            out.push_str(&format!("\n## `{}`\n\n", group.name));
+            out.push_str(&format!("Crate: {} v{}\n\n", krate.name, krate.version));
+            out.push_str(&format!(
+                "Lints: {}\n",
+                group.lints.iter().map(|lint| format!("`{lint}`")).join(", ")
+            ));
+            if !group.docs.is_empty() {
+                out.push('\n');
+                out.push_str(&nest_headings(&group.docs, 3));
+                out.push('\n');
+            }
+        }
+    }
+
+    out
+}
+
+/// Moves the headings of the documentation, to make the top most heading
+/// have the given level. This nests them below the heading of the lint.
+pub(crate) fn nest_headings(docs: &str, level: usize) -> String {
+    let is_code_fence = |line: &str| line.trim_start().starts_with("```");
+    let mut in_code_block = false;
+    let headings: Vec<_> = docs
+        .lines()
+        .map(|line| {
+            if is_code_fence(line) {
+                in_code_block = !in_code_block;
+            }
+            !in_code_block && line.starts_with('#')
+        })
+        .collect();
+
+    let Some(top_level) = docs
+        .lines()
+        .zip(&headings)
+        .filter(|(_, is_heading)| **is_heading)
+        .map(|(line, _)| line.chars().take_while(|c| *c == '#').count())
+        .min()
+    else {
+        return docs.to_string();
+    };
+
+    docs.lines()
+        .zip(&headings)
+        .map(|(line, is_heading)| {
+            if *is_heading {
+                format!("{}{}", "#".repeat(level), &line[top_level..])
+            } else {
+                line.to_string()
+            }
+        })
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nest_headings() {
+        let docs = "\
+## What it does
+Checks for things.
+
+```rust
+# use std::fmt;
+#[allow(unused)]
+let x = 1;
+```
+
+### Example
+Text";
+
+        expect_test::expect![[r"
+            ### What it does
+            Checks for things.
+
+            ```rust
+            # use std::fmt;
+            #[allow(unused)]
+            let x = 1;
+            ```
+
+            #### Example
+            Text"]]
+        .assert_eq(&nest_headings(docs, 3));
+    }
+
+    #[test]
+    fn test_nest_headings_without_headings() {
+        let docs = "Checks for things.\n\n```\n# hidden\n```";
+        assert_eq!(nest_headings(docs, 3), docs);
+    }
+}
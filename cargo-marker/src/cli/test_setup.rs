@@ -1,5 +1,5 @@
 use super::check::CheckCommand;
-use crate::backend::driver::DriverVersionInfo;
+use crate::backend::{self, driver::DriverVersionInfo};
 use crate::config::Config;
 use crate::error::prelude::*;
 use clap::Args;
@@ -11,8 +11,8 @@ pub(crate) struct TestSetupCommand {
 }
 
 impl TestSetupCommand {
-    pub(crate) fn run(self, config: Option<Config>) -> Result {
-        let lints = self.check.compile_lints(config)?;
+    pub(crate) fn run(self, config: Option<Config>, base: backend::Config) -> Result {
+        let lints = self.check.compile_lints(config, base)?;
 
         for (name, value) in lints.info.env {
             println!("env:{name}={value}");
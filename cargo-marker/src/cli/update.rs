@@ -0,0 +1,35 @@
+use crate::backend;
+use crate::config::Config;
+use crate::error::prelude::*;
+use clap::Args;
+
+#[derive(Args, Debug)]
+pub(crate) struct UpdateCommand {
+    /// Only update the specified packages and their dependencies. All lint
+    /// crates and dependencies will be updated, if this isn't specified
+    #[arg(short, long = "package", value_name = "SPEC")]
+    pub(crate) packages: Vec<String>,
+
+    /// Run without accessing the network
+    #[arg(long)]
+    pub(crate) offline: bool,
+}
+
+impl UpdateCommand {
+    pub(crate) fn run(self, config: Option<Config>, base: backend::Config) -> Result {
+        let Some(config) = config else {
+            return Err(Error::from_kind(ErrorKind::LintsNotFound));
+        };
+
+        let lints = config.all_lints()?;
+
+        let backend_conf = backend::Config {
+            lints,
+            lock_file: Some(config.lock_file),
+            offline: self.offline,
+            ..base
+        };
+
+        backend::update_lock_file(&backend_conf, &self.packages)
+    }
+}
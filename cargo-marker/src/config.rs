@@ -1,5 +1,5 @@
 //! This module is responsible for translating the `[workspace.metadata.marker]`
-//! section in `Cargo.toml` files.
+//! and `[package.metadata.marker]` sections in `Cargo.toml` files.
 //!
 //! The TOML format specifies that every TOML file must be a valid UTF-8.
 //! ([source](https://toml.io/en/v1.0.0)) This allows Marker to just use
@@ -7,11 +7,16 @@
 
 use crate::error::prelude::*;
 use crate::observability::display;
-use camino::Utf8Path;
+use camino::{Utf8Path, Utf8PathBuf};
+use cargo_metadata::Metadata;
 use serde::{Deserialize, Serialize};
 use std::{collections::BTreeMap, fs};
 use yansi::Paint;
 
+/// The name of the lock file, which pins the versions of the lint crates and
+/// their dependencies.
+pub const LOCK_FILE_NAME: &str = "Marker.lock";
+
 #[derive(Deserialize, Debug)]
 struct CargoToml {
     workspace: Option<Workspace>,
@@ -36,9 +41,64 @@ struct WorkspaceMetadata {
 pub struct Config {
     /// A list of lints.
     pub lints: BTreeMap<String, LintDependency>,
+    /// The levels of lints, like `"marker::lint_crate::lint_name" = "allow"`.
+    /// All lints of a lint crate can be selected with `marker::lint_crate::*`.
+    #[serde(default)]
+    pub levels: BTreeMap<String, LintLevel>,
+    /// The path of the `Marker.lock` file, next to the `Cargo.toml` file of
+    /// the workspace.
+    #[serde(skip)]
+    pub lock_file: Utf8PathBuf,
+    /// The configurations of workspace members, which have a
+    /// `[package.metadata.marker]` section.
+    #[serde(skip)]
+    pub packages: Vec<PackageConfig>,
+}
+
+/// Markers metadata section `package.metadata.marker` in the `Cargo.toml` file
+/// of a workspace member. It's merged with the configuration of the workspace.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct PackageConfig {
+    /// The name of the package.
+    #[serde(skip)]
+    pub name: String,
+    /// The directory of the `Cargo.toml` file of the package.
+    #[serde(skip)]
+    pub manifest_dir: Utf8PathBuf,
+    /// Lint crates, which are used in addition to the lint crates of the workspace.
+    #[serde(default)]
+    pub lints: BTreeMap<String, LintDependency>,
+    /// Lint crates of the workspace, which shouldn't be used for this package.
+    #[serde(default)]
+    pub exclude_lints: Vec<String>,
+    /// The levels of lints, like `"marker::lint_crate::lint_name" = "allow"`.
+    /// All lints of a lint crate can be selected with `marker::lint_crate::*`.
+    #[serde(default)]
+    pub levels: BTreeMap<String, LintLevel>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    Allow,
+    Warn,
+    Deny,
+    Forbid,
+}
+
+impl LintLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LintLevel::Allow => "allow",
+            LintLevel::Warn => "warn",
+            LintLevel::Deny => "deny",
+            LintLevel::Forbid => "forbid",
+        }
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(untagged)]
 pub enum LintDependency {
     /// Version string like: `lint = "0.0.1"`
@@ -73,18 +133,17 @@ impl LintDependency {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct LintDependencyEntry {
     #[serde(flatten)]
     pub(crate) source: Source,
     pub(crate) package: Option<String>,
-    // FIXME: Features are not supported yet, see https://github.com/rust-marker/marker/issues/81
     #[serde(rename = "default-features")]
     pub(crate) default_features: Option<bool>,
     pub(crate) features: Option<Vec<String>>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(untagged)]
 pub enum Source {
     /// A registry dependency, like `lint_crate = "1.0"`
@@ -120,7 +179,7 @@ impl Source {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum GitRef {
     Rev(String),
@@ -129,10 +188,45 @@ pub enum GitRef {
 }
 
 impl Config {
-    pub fn try_from_manifest(path: &Utf8Path) -> Result<Option<Config>> {
+    /// Loads the configuration of the workspace and its members, described by
+    /// the given metadata.
+    pub fn try_from_metadata(metadata: &Metadata) -> Result<Option<Config>> {
+        let path = &metadata.workspace_root.join("Cargo.toml");
         let config_str = fs::read_to_string(path).context(|| format!("Failed to read config at {}", path.red()))?;
 
-        Self::try_from_str(&config_str, path)
+        let config = Self::try_from_str(&config_str, path)?;
+
+        let packages = PackageConfig::try_from_metadata(metadata)?;
+        if packages.is_empty() {
+            return Ok(config);
+        }
+
+        // Packages can use Marker, without a `[workspace.metadata.marker]` section
+        let mut config = config.unwrap_or_else(|| Config {
+            lints: BTreeMap::new(),
+            levels: BTreeMap::new(),
+            lock_file: path
+                .parent()
+                .expect("path must have a parent after reading the `Cargo.toml` file")
+                .join(LOCK_FILE_NAME),
+            packages: vec![],
+        });
+        for package in &packages {
+            if let Some(name) = package
+                .exclude_lints
+                .iter()
+                .find(|name| !config.lints.contains_key(*name))
+            {
+                return Err(Error::root(format!(
+                    "The package {} excludes the lint crate {}, which isn't specified for the workspace",
+                    package.name.cyan(),
+                    name.red().bold()
+                )));
+            }
+        }
+        config.packages = packages;
+
+        Ok(Some(config))
     }
 
     pub(crate) fn try_from_str(config_str: &str, path: &Utf8Path) -> Result<Option<Config>> {
@@ -147,6 +241,7 @@ impl Config {
             .parent()
             .expect("path must have a parent after reading the `Cargo.toml` file");
         config.normalize(workspace_path)?;
+        config.lock_file = workspace_path.join(LOCK_FILE_NAME);
 
         Ok(Some(config))
     }
@@ -157,6 +252,361 @@ impl Config {
         for (package, lint) in &mut self.lints {
             lint.normalize(package, workspace_path)?;
         }
+        self.levels = normalize_levels(std::mem::take(&mut self.levels))?;
         Ok(())
     }
+
+    /// Returns all lint crates, which are used by the workspace or by any of
+    /// its members. This returns an error, if a lint crate is specified with
+    /// different sources.
+    pub fn all_lints(&self) -> Result<BTreeMap<String, LintDependencyEntry>> {
+        let mut all_lints: BTreeMap<String, LintDependencyEntry> = BTreeMap::new();
+        let package_lints = self.packages.iter().flat_map(|package| &package.lints);
+        for (name, dep) in self.lints.iter().chain(package_lints) {
+            let dep = dep.clone().into_dep_entry();
+            match all_lints.get(name) {
+                Some(existing) if *existing != dep => {
+                    return Err(Error::root(format!(
+                        "The lint crate {} is specified with different sources in the workspace",
+                        name.red().bold()
+                    )));
+                },
+                Some(_) => {},
+                None => {
+                    all_lints.insert(name.clone(), dep);
+                },
+            }
+        }
+        Ok(all_lints)
+    }
+}
+
+impl PackageConfig {
+    /// This function loads the configurations of all workspace members, which
+    /// have a `[package.metadata.marker]` section.
+    fn try_from_metadata(metadata: &Metadata) -> Result<Vec<PackageConfig>> {
+        let mut packages = vec![];
+        for package in metadata.workspace_packages() {
+            let Some(marker) = package.metadata.get("marker") else {
+                continue;
+            };
+
+            let mut config = PackageConfig::deserialize(marker).context(|| {
+                format!(
+                    "Couldn't parse the {} section at {}",
+                    "[package.metadata.marker]".cyan(),
+                    package.manifest_path.red()
+                )
+            })?;
+
+            config.name = package.name.clone();
+            config.manifest_dir = package
+                .manifest_path
+                .parent()
+                .expect("the manifest path must have a parent")
+                .to_path_buf();
+            for (name, lint) in &mut config.lints {
+                lint.normalize(name, &config.manifest_dir)?;
+            }
+            config.levels = normalize_levels(std::mem::take(&mut config.levels))?;
+
+            packages.push(config);
+        }
+
+        Ok(packages)
+    }
+
+    /// Returns the names of the lint crates, which are used for this package.
+    pub fn lint_crates(&self, workspace_lints: &BTreeMap<String, LintDependency>) -> Vec<String> {
+        workspace_lints
+            .keys()
+            .filter(|name| !self.exclude_lints.contains(name))
+            .chain(self.lints.keys())
+            .cloned()
+            .collect()
+    }
+}
+
+fn normalize_levels(levels: BTreeMap<String, LintLevel>) -> Result<BTreeMap<String, LintLevel>> {
+    levels
+        .into_iter()
+        .map(|(lint, level)| Ok((normalize_lint_name(&lint)?, level)))
+        .collect()
+}
+
+/// This function normalizes the given lint name, by replacing dashes with
+/// underscores, like rustc does. It returns an error, if the lint doesn't
+/// belong to Marker. Valid names have the format `marker::lint_crate::lint_name`
+/// or `marker::lint_crate::*`, to select all lints of a lint crate.
+pub fn normalize_lint_name(lint: &str) -> Result<String> {
+    let lint = lint.replace('-', "_");
+    let is_valid = lint
+        .strip_prefix("marker::")
+        .and_then(|lint| lint.split_once("::"))
+        .map_or(false, |(krate, name)| {
+            !krate.is_empty() && !name.is_empty() && !name.contains("::")
+        });
+    if is_valid {
+        return Ok(lint);
+    }
+
+    Err(Error::root(format!(
+        "The lint name {} is invalid, lints of Marker have the format {} or {}",
+        lint.red(),
+        "marker::lint_crate::lint_name".cyan(),
+        "marker::lint_crate::*".cyan()
+    )))
+}
+
+/// Returns the name of the lint crate, from a lint name, which has been
+/// normalized by [`normalize_lint_name`].
+pub fn lint_crate_of(lint: &str) -> &str {
+    lint.split("::").nth(1).expect("the lint name has been normalized")
+}
+
+/// Sorts lint levels, to apply the levels of whole lint crates, before the
+/// levels of specific lints. This allows specific lints to override the level
+/// of their lint crate. The order is otherwise kept.
+pub fn sort_levels(levels: &mut [(String, LintLevel)]) {
+    levels.sort_by_key(|(lint, _)| !lint.ends_with("::*"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_config(manifest: &Utf8Path) -> Result<Option<Config>> {
+        let metadata = cargo_metadata::MetadataCommand::new()
+            .manifest_path(manifest)
+            .no_deps()
+            .exec()
+            .unwrap();
+        Config::try_from_metadata(&metadata)
+    }
+
+    #[test]
+    fn test_package_config_merging() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap().canonicalize_utf8().unwrap();
+        std::fs::create_dir_all(root.join("lints")).unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            r#"
+            [workspace]
+            members = ["member", "other"]
+
+            [workspace.metadata.marker.lints]
+            shared = "0.1.0"
+            excluded = "0.2.0"
+            "#,
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("member/src")).unwrap();
+        std::fs::write(root.join("member/src/lib.rs"), "").unwrap();
+        std::fs::write(
+            root.join("member/Cargo.toml"),
+            r#"
+            [package]
+            name = "member"
+            version = "0.1.0"
+
+            [package.metadata.marker]
+            exclude-lints = ["excluded"]
+            lints = { local = { path = "../lints" } }
+            "#,
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("other/src")).unwrap();
+        std::fs::write(root.join("other/src/lib.rs"), "").unwrap();
+        std::fs::write(
+            root.join("other/Cargo.toml"),
+            "[package]\nname = \"other\"\nversion = \"0.1.0\"\n\n[package.metadata.marker]\n",
+        )
+        .unwrap();
+
+        let config = load_config(&root.join("Cargo.toml")).unwrap().unwrap();
+        assert_eq!(config.lock_file, root.join(LOCK_FILE_NAME));
+        assert_eq!(config.packages.len(), 2);
+
+        let member = config.packages.iter().find(|pkg| pkg.name == "member").unwrap();
+        assert_eq!(member.manifest_dir, root.join("member"));
+        assert_eq!(member.lint_crates(&config.lints), ["shared", "local"]);
+        let LintDependency::Full(LintDependencyEntry {
+            source: Source::Path { path },
+            ..
+        }) = &member.lints["local"]
+        else {
+            panic!("the lint crate should have a path source");
+        };
+        assert_eq!(*path, root.join("lints"));
+
+        let other = config.packages.iter().find(|pkg| pkg.name == "other").unwrap();
+        assert_eq!(other.lint_crates(&config.lints), ["excluded", "shared"]);
+
+        let all_lints = config.all_lints().unwrap();
+        assert_eq!(all_lints.keys().collect::<Vec<_>>(), ["excluded", "local", "shared"]);
+    }
+
+    #[test]
+    fn test_package_config_without_workspace_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap().canonicalize_utf8().unwrap();
+        std::fs::create_dir_all(root.join("lints")).unwrap();
+        std::fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = [\"member\"]\n").unwrap();
+        std::fs::create_dir_all(root.join("member/src")).unwrap();
+        std::fs::write(root.join("member/src/lib.rs"), "").unwrap();
+        std::fs::write(
+            root.join("member/Cargo.toml"),
+            r#"
+            [package]
+            name = "member"
+            version = "0.1.0"
+
+            [package.metadata.marker]
+            lints = { local = { path = "../lints" } }
+            "#,
+        )
+        .unwrap();
+
+        let config = load_config(&root.join("Cargo.toml")).unwrap().unwrap();
+        assert!(config.lints.is_empty());
+        assert_eq!(config.all_lints().unwrap().keys().collect::<Vec<_>>(), ["local"]);
+    }
+
+    #[test]
+    fn test_exclude_unknown_lint_crate() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            r#"
+            [workspace]
+            members = ["member"]
+
+            [workspace.metadata.marker.lints]
+            shared = "0.1.0"
+            "#,
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("member/src")).unwrap();
+        std::fs::write(root.join("member/src/lib.rs"), "").unwrap();
+        std::fs::write(
+            root.join("member/Cargo.toml"),
+            r#"
+            [package]
+            name = "member"
+            version = "0.1.0"
+
+            [package.metadata.marker]
+            exclude-lints = ["unknown"]
+            "#,
+        )
+        .unwrap();
+
+        let err = load_config(&root.join("Cargo.toml")).unwrap_err();
+        assert!(format!("{err:?}").contains("unknown"), "{err:?}");
+    }
+
+    #[test]
+    fn test_conflicting_lint_sources() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            r#"
+            [workspace]
+            members = ["same", "different"]
+
+            [workspace.metadata.marker.lints]
+            shared = "0.1.0"
+            "#,
+        )
+        .unwrap();
+        for (name, version) in [("same", "0.1.0"), ("different", "0.2.0")] {
+            std::fs::create_dir_all(root.join(name).join("src")).unwrap();
+            std::fs::write(root.join(name).join("src/lib.rs"), "").unwrap();
+            std::fs::write(
+                root.join(name).join("Cargo.toml"),
+                format!(
+                    "[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n\n\
+                    [package.metadata.marker]\nlints = {{ shared = \"{version}\" }}\n"
+                ),
+            )
+            .unwrap();
+        }
+
+        let config = load_config(&root.join("Cargo.toml")).unwrap().unwrap();
+        let err = config.all_lints().unwrap_err();
+        assert!(format!("{err:?}").contains("shared"), "{err:?}");
+    }
+
+    #[test]
+    fn test_normalize_lint_name() {
+        assert_eq!(
+            normalize_lint_name("marker::lint-crate::lint-name").unwrap(),
+            "marker::lint_crate::lint_name"
+        );
+        assert_eq!(
+            normalize_lint_name("marker::lint_crate::*").unwrap(),
+            "marker::lint_crate::*"
+        );
+        assert!(normalize_lint_name("clippy::lint_name").is_err());
+        assert!(normalize_lint_name("marker::lint_crate").is_err());
+        assert!(normalize_lint_name("marker::lint_crate::nested::lint").is_err());
+    }
+
+    #[test]
+    fn test_levels_are_normalized() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            r#"
+            [workspace]
+            members = ["member"]
+
+            [workspace.metadata.marker.lints]
+            lint-crate = "0.1.0"
+
+            [workspace.metadata.marker.levels]
+            "marker::lint-crate::*" = "warn"
+            "#,
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("member/src")).unwrap();
+        std::fs::write(root.join("member/src/lib.rs"), "").unwrap();
+        std::fs::write(
+            root.join("member/Cargo.toml"),
+            r#"
+            [package]
+            name = "member"
+            version = "0.1.0"
+
+            [package.metadata.marker]
+            levels = { "marker::lint-crate::some-lint" = "deny" }
+            "#,
+        )
+        .unwrap();
+
+        let config = load_config(&root.join("Cargo.toml")).unwrap().unwrap();
+        assert_eq!(config.levels.get("marker::lint_crate::*"), Some(&LintLevel::Warn));
+        assert_eq!(
+            config.packages[0].levels.get("marker::lint_crate::some_lint"),
+            Some(&LintLevel::Deny)
+        );
+
+        std::fs::write(
+            root.join("member/Cargo.toml"),
+            r#"
+            [package]
+            name = "member"
+            version = "0.1.0"
+
+            [package.metadata.marker]
+            levels = { "clippy::lint" = "deny" }
+            "#,
+        )
+        .unwrap();
+        assert!(load_config(&root.join("Cargo.toml")).is_err());
+    }
 }
@@ -10,6 +10,7 @@ pub(crate) type Result<Ok = (), Kind = ErrorKind> = marker_error::Result<Ok, Kin
 pub(crate) type Error = marker_error::Error<ErrorKind>;
 
 use crate::observability::display;
+use camino::Utf8PathBuf;
 use yansi::Paint;
 
 /// The enum of all categorized errors for this crate.
@@ -48,6 +49,14 @@ or:
         display::cli("rustup toolchain install {toolchain} --component rustc-dev llvm-tools")
     ))]
     BuildDriver,
+
+    #[error("The lock file {} doesn't exist, but {} was specified", lock_file.red(), "--locked".blue())]
+    #[diagnostic(help(
+        "Run {} to generate the lock file, or remove the {} flag",
+        display::cli("cargo marker update"),
+        "--locked".blue(),
+    ))]
+    LockFileNotFound { lock_file: Utf8PathBuf },
 }
 
 // region replace marker version stable
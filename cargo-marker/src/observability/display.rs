@@ -45,3 +45,34 @@ pub(crate) fn print_stage(name: &str) {
 pub(crate) fn stage(name: &str) -> impl fmt::Display + '_ {
     name.white().bold()
 }
+
+/// Displays the status of a single unit of work, like the compilation of a
+/// crate. This is right aligned, like Cargo's `Compiling` or `Finished` lines.
+pub(crate) fn status(status: &str, message: impl fmt::Display) -> String {
+    format!("{} {message}", format!("{status:>12}").green().bold())
+}
+
+/// Lightweight renderer for the Markdown documentation of lints. Headings are
+/// highlighted and code blocks are indented, everything else is printed as is.
+pub(crate) fn markdown(markdown: &str) -> String {
+    let mut in_code_block = false;
+    markdown
+        .lines()
+        .filter_map(|line| {
+            if line.trim_start().starts_with("```") {
+                in_code_block = !in_code_block;
+                return None;
+            }
+
+            if in_code_block {
+                return Some(format!("    {}", line.cyan()));
+            }
+
+            if line.starts_with('#') {
+                return Some(line.trim_start_matches('#').trim().bold().underline().to_string());
+            }
+
+            Some(line.to_string())
+        })
+        .join("\n")
+}
@@ -1,6 +1,22 @@
+pub mod hash;
 pub mod utf8;
 
+use crate::error::prelude::*;
+use camino::Utf8Path;
+
 /// Use local dev build of driver nearby `cargo-marker` executable
 pub fn is_local_driver() -> bool {
     std::env::var("MARKER_NO_LOCAL_DRIVER").is_err() && cfg!(debug_assertions)
 }
+
+/// Writes the given content to the file at `path`. Missing parent directories
+/// are created.
+pub fn write_to_file(path: &Utf8Path, content: &str) -> Result {
+    let parent = path
+        .parent()
+        .unwrap_or_else(|| panic!("The file must have a parent directory. Path: {path}"));
+
+    std::fs::create_dir_all(parent).context(|| format!("Failed to create the directory structure for {parent}"))?;
+
+    std::fs::write(path, content).context(|| format!("Failed to write a file at {path}"))
+}
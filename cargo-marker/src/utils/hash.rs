@@ -0,0 +1,90 @@
+/// A hasher, which produces the same hash on every platform and for every
+/// version of Rust. The [`std::hash::Hash`] implementations and the default
+/// hasher of the standard library don't give this guarantee, which is
+/// required for names of directories, that are reused between runs.
+///
+/// This uses the 64-bit FNV-1a algorithm. Every value is prefixed by its
+/// length, to make the hash of a sequence of values unambiguous.
+#[derive(Debug, Clone)]
+pub struct StableHasher {
+    state: u64,
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self {
+            state: FNV_OFFSET_BASIS,
+        }
+    }
+}
+
+impl StableHasher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= u64::from(*byte);
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    pub fn write_str(&mut self, value: &str) -> &mut Self {
+        self.write_bytes(&(value.len() as u64).to_le_bytes());
+        self.write_bytes(value.as_bytes());
+        self
+    }
+
+    pub fn write_bool(&mut self, value: bool) -> &mut Self {
+        self.write_bytes(&[u8::from(value)]);
+        self
+    }
+
+    pub fn write_opt_str(&mut self, value: Option<&str>) -> &mut Self {
+        self.write_bool(value.is_some());
+        if let Some(value) = value {
+            self.write_str(value);
+        }
+        self
+    }
+
+    pub fn write_strs(&mut self, values: &[impl AsRef<str>]) -> &mut Self {
+        self.write_bytes(&(values.len() as u64).to_le_bytes());
+        for value in values {
+            self.write_str(value.as_ref());
+        }
+        self
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stable_hash_values() {
+        assert_eq!(StableHasher::new().finish(), FNV_OFFSET_BASIS);
+
+        let hash = StableHasher::new().write_str("marker").write_bool(true).finish();
+        assert_eq!(hash, 0xb3f2_3a2a_131b_7640);
+    }
+
+    #[test]
+    fn test_stable_hash_is_unambiguous() {
+        let joined = StableHasher::new().write_str("ab").write_str("c").finish();
+        let split = StableHasher::new().write_str("a").write_str("bc").finish();
+        assert_ne!(joined, split);
+
+        let none = StableHasher::new().write_opt_str(None).write_str("").finish();
+        let empty = StableHasher::new().write_opt_str(Some("")).finish();
+        assert_ne!(none, empty);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::error::prelude::*;
+use marker_api::common::Level;
+
+use super::LINT_LEVELS_ENV;
+
+/// The lint level, that should be set for a lint or several lints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintLevelInfo {
+    /// The name of the lint, like `marker::lint_crate::lint_name`.
+    pub lint: String,
+    pub level: Level,
+}
+
+impl LintLevelInfo {
+    /// This function tries to load the lint levels for the currently compiled
+    /// package from the [`LINT_LEVELS_ENV`] environment value.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the value can't be read or the
+    /// content is malformed. The `README.md` of this adapter contains the
+    /// format definition.
+    pub fn list_from_env() -> Result<Vec<LintLevelInfo>> {
+        let Some(entry) = package_entry_from_env(LINT_LEVELS_ENV)? else {
+            return Ok(vec![]);
+        };
+
+        Self::list_from_str(&entry)
+    }
+
+    /// Parses the lint levels from an entry of the [`LINT_LEVELS_ENV`]
+    /// environment value, like `allow:marker::lint_crate::*,deny:marker::lint_crate::lint`.
+    fn list_from_str(entry: &str) -> Result<Vec<LintLevelInfo>> {
+        let mut levels = vec![];
+        for item in entry.split(',').filter(|item| !item.is_empty()) {
+            let (level, lint) = item.split_once(':').context(|| {
+                format!("The lint level `{item}` in the `{LINT_LEVELS_ENV}` environment variable is malformed")
+            })?;
+            let level = match level {
+                "allow" => Level::Allow,
+                "warn" => Level::Warn,
+                "deny" => Level::Deny,
+                "forbid" => Level::Forbid,
+                _ => {
+                    return Err(Error::root(format!(
+                        "The level `{level}` in the `{LINT_LEVELS_ENV}` environment variable is unknown"
+                    )));
+                },
+            };
+            levels.push(LintLevelInfo {
+                lint: lint.to_string(),
+                level,
+            });
+        }
+
+        Ok(levels)
+    }
+}
+
+/// This function returns the entry for the currently compiled package, from an
+/// environment value with per-package entries.
+///
+/// Every line of the value contains the manifest directory of a package and the
+/// entry, separated by the last `=`. The entry of a line without a directory is
+/// used for all other packages. The package is identified by the `CARGO_MANIFEST_DIR`
+/// environment value, which is set by Cargo.
+pub(crate) fn package_entry_from_env(var: &str) -> Result<Option<String>> {
+    let Ok(env_str) = std::env::var(var) else {
+        return Ok(None);
+    };
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+
+    package_entry_from_str(var, &env_str, &manifest_dir)
+}
+
+/// This function returns the entry for the package in `manifest_dir`, from the
+/// value `env_str` of the environment variable `var`. See [`package_entry_from_env`].
+fn package_entry_from_str(var: &str, env_str: &str, manifest_dir: &str) -> Result<Option<String>> {
+    let mut default = None;
+    for line in env_str.lines() {
+        let (dir, entry) = line.rsplit_once('=').context(|| {
+            format!(
+                "The content of the `{var}` environment variable is malformed. \
+                Dumped its content on the next line:\n---\n{env_str}\n---",
+            )
+        })?;
+
+        if dir.is_empty() {
+            default = Some(entry);
+        } else if dir == manifest_dir {
+            return Ok(Some(entry.to_string()));
+        }
+    }
+
+    Ok(default.map(ToString::to_string))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_entry_selection() {
+        let env_str = "=default\n/ws/a=entry-a\n/ws/b=entry-b";
+        let entry = |dir| package_entry_from_str("VAR", env_str, dir).unwrap();
+
+        assert_eq!(entry("/ws/a").as_deref(), Some("entry-a"));
+        assert_eq!(entry("/ws/b").as_deref(), Some("entry-b"));
+        assert_eq!(entry("/ws/c").as_deref(), Some("default"));
+        assert_eq!(entry("").as_deref(), Some("default"));
+
+        let entry = package_entry_from_str("VAR", "/ws/a=entry-a", "/ws/c").unwrap();
+        assert_eq!(entry, None);
+    }
+
+    #[test]
+    fn test_package_entry_splits_at_last_eq() {
+        let entry = package_entry_from_str("VAR", "/ws/a=b=entry", "/ws/a=b").unwrap();
+        assert_eq!(entry.as_deref(), Some("entry"));
+    }
+
+    #[test]
+    fn test_package_entry_malformed() {
+        assert!(package_entry_from_str("VAR", "=default\nmalformed", "/ws/a").is_err());
+    }
+
+    #[test]
+    fn test_lint_levels_from_str() {
+        let levels = LintLevelInfo::list_from_str("allow:marker::a::*,,forbid:marker::a::lint").unwrap();
+        assert_eq!(
+            levels,
+            vec![
+                LintLevelInfo {
+                    lint: "marker::a::*".to_string(),
+                    level: Level::Allow,
+                },
+                LintLevelInfo {
+                    lint: "marker::a::lint".to_string(),
+                    level: Level::Forbid,
+                },
+            ]
+        );
+        assert!(LintLevelInfo::list_from_str("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_lint_levels_malformed() {
+        assert!(LintLevelInfo::list_from_str("marker::a::lint").is_err());
+        assert!(LintLevelInfo::list_from_str("loud:marker::a::lint").is_err());
+    }
+}
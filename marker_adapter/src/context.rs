@@ -92,12 +92,12 @@ mod map;
 pub use map::*;
 
 use marker_api::{
-    common::{ExpnId, ExprId, SpanId, SymbolId},
+    common::{ExpnId, ExprId, MacroId, NodeId, SpanId, SymbolId},
     context::{MarkerContextCallbacks, MarkerContextData},
     diagnostic::Diagnostic,
     ffi::{self, FfiOption},
     prelude::*,
-    span::{ExpnInfo, FileInfo, FilePos, SpanPos, SpanSource},
+    span::{Comment, ExpnInfo, FileInfo, FilePos, MacroInfo, SourceFile, SpanPos, SpanSource, Token},
 };
 
 /// ### Safety
@@ -127,10 +127,24 @@ impl<'ast> MarkerContextWrapper<'ast> {
             data: unsafe { &*(self as *const MarkerContextWrapper).cast::<MarkerContextData>() },
             emit_diag,
             resolve_ty_ids,
+            impls_of_trait,
+            inherent_impls_of,
+            trait_impl_for,
+            predicates_of,
+            macro_info,
+            comments_in,
+            comments_before,
+            doc_comments,
+            source_files,
             expr_ty,
             span,
             span_snippet,
             span_source,
+            span_tokens,
+            span_to,
+            span_between,
+            span_source_callsite,
+            span_expand_to_lines,
             span_pos_to_file_loc,
             span_expn_info,
             symbol_str,
@@ -143,11 +157,25 @@ pub trait MarkerContextDriver<'ast> {
     fn emit_diag(&'ast self, diag: &Diagnostic<'_, 'ast>);
 
     fn resolve_ty_ids(&'ast self, path: &str) -> &'ast [TyDefId];
+    fn impls_of_trait(&'ast self, trait_id: TyDefId) -> &'ast [ItemId];
+    fn inherent_impls_of(&'ast self, ty_id: TyDefId) -> &'ast [ItemId];
+    fn trait_impl_for(&'ast self, ty: marker_api::sem::TyKind<'ast>, trait_id: TyDefId) -> Option<ItemId>;
+    fn predicates_of(&'ast self, item: ItemId) -> &'ast [marker_api::sem::PredicateKind<'ast>];
+    fn macro_info(&'ast self, id: MacroId) -> &'ast MacroInfo<'ast>;
+    fn comments_in(&'ast self, span: &Span<'ast>) -> &'ast [Comment<'ast>];
+    fn comments_before(&'ast self, span: &Span<'ast>) -> &'ast [Comment<'ast>];
+    fn doc_comments(&'ast self, node: NodeId) -> &'ast [Comment<'ast>];
+    fn source_files(&'ast self) -> &'ast [SourceFile<'ast>];
 
     fn expr_ty(&'ast self, expr: ExprId) -> marker_api::sem::TyKind<'ast>;
     fn span(&'ast self, owner: SpanId) -> &'ast Span<'ast>;
     fn span_snippet(&'ast self, span: &Span<'_>) -> Option<&'ast str>;
     fn span_source(&'ast self, span: &Span<'_>) -> SpanSource<'ast>;
+    fn span_tokens(&'ast self, span: &Span<'ast>) -> &'ast [Token<'ast>];
+    fn span_to(&'ast self, span: &Span<'ast>, end: &Span<'ast>) -> Span<'ast>;
+    fn span_between(&'ast self, span: &Span<'ast>, end: &Span<'ast>) -> Span<'ast>;
+    fn span_source_callsite(&'ast self, span: &Span<'ast>) -> Span<'ast>;
+    fn span_expand_to_lines(&'ast self, span: &Span<'ast>) -> Span<'ast>;
     fn span_expn_info(&'ast self, expn_id: ExpnId) -> Option<&'ast ExpnInfo<'ast>>;
     fn span_pos_to_file_loc(&'ast self, file: &FileInfo<'ast>, pos: SpanPos) -> Option<FilePos<'ast>>;
     fn symbol_str(&'ast self, api_id: SymbolId) -> &'ast str;
@@ -165,6 +193,59 @@ extern "C" fn resolve_ty_ids<'ast>(
     unsafe { as_driver(data) }.resolve_ty_ids((&path).into()).into()
 }
 
+extern "C" fn impls_of_trait<'ast>(data: &'ast MarkerContextData, trait_id: TyDefId) -> ffi::FfiSlice<'ast, ItemId> {
+    unsafe { as_driver(data) }.impls_of_trait(trait_id).into()
+}
+
+extern "C" fn inherent_impls_of<'ast>(data: &'ast MarkerContextData, ty_id: TyDefId) -> ffi::FfiSlice<'ast, ItemId> {
+    unsafe { as_driver(data) }.inherent_impls_of(ty_id).into()
+}
+
+// False positive because `SemTyKind` is non-exhaustive
+#[allow(improper_ctypes_definitions)]
+extern "C" fn trait_impl_for<'ast>(
+    data: &'ast MarkerContextData,
+    ty: marker_api::sem::TyKind<'ast>,
+    trait_id: TyDefId,
+) -> FfiOption<ItemId> {
+    unsafe { as_driver(data) }.trait_impl_for(ty, trait_id).into()
+}
+
+extern "C" fn predicates_of<'ast>(
+    data: &'ast MarkerContextData,
+    item: ItemId,
+) -> ffi::FfiSlice<'ast, marker_api::sem::PredicateKind<'ast>> {
+    unsafe { as_driver(data) }.predicates_of(item).into()
+}
+
+extern "C" fn macro_info<'ast>(data: &'ast MarkerContextData, id: MacroId) -> &'ast MacroInfo<'ast> {
+    unsafe { as_driver(data) }.macro_info(id)
+}
+
+extern "C" fn comments_in<'ast>(
+    data: &'ast MarkerContextData,
+    span: &Span<'ast>,
+) -> ffi::FfiSlice<'ast, Comment<'ast>> {
+    unsafe { as_driver(data) }.comments_in(span).into()
+}
+
+extern "C" fn comments_before<'ast>(
+    data: &'ast MarkerContextData,
+    span: &Span<'ast>,
+) -> ffi::FfiSlice<'ast, Comment<'ast>> {
+    unsafe { as_driver(data) }.comments_before(span).into()
+}
+
+// False positive because `NodeId` is non-exhaustive
+#[allow(improper_ctypes_definitions)]
+extern "C" fn doc_comments<'ast>(data: &'ast MarkerContextData, node: NodeId) -> ffi::FfiSlice<'ast, Comment<'ast>> {
+    unsafe { as_driver(data) }.doc_comments(node).into()
+}
+
+extern "C" fn source_files<'ast>(data: &'ast MarkerContextData) -> ffi::FfiSlice<'ast, SourceFile<'ast>> {
+    unsafe { as_driver(data) }.source_files().into()
+}
+
 // False positive because `SemTyKind` is non-exhaustive
 #[allow(improper_ctypes_definitions)]
 extern "C" fn expr_ty<'ast>(data: &'ast MarkerContextData, expr: ExprId) -> marker_api::sem::TyKind<'ast> {
@@ -188,6 +269,26 @@ extern "C" fn span_source<'ast>(data: &'ast MarkerContextData, span: &Span<'_>)
     unsafe { as_driver(data) }.span_source(span)
 }
 
+extern "C" fn span_tokens<'ast>(data: &'ast MarkerContextData, span: &Span<'ast>) -> ffi::FfiSlice<'ast, Token<'ast>> {
+    unsafe { as_driver(data) }.span_tokens(span).into()
+}
+
+extern "C" fn span_to<'ast>(data: &'ast MarkerContextData, span: &Span<'ast>, end: &Span<'ast>) -> Span<'ast> {
+    unsafe { as_driver(data) }.span_to(span, end)
+}
+
+extern "C" fn span_between<'ast>(data: &'ast MarkerContextData, span: &Span<'ast>, end: &Span<'ast>) -> Span<'ast> {
+    unsafe { as_driver(data) }.span_between(span, end)
+}
+
+extern "C" fn span_source_callsite<'ast>(data: &'ast MarkerContextData, span: &Span<'ast>) -> Span<'ast> {
+    unsafe { as_driver(data) }.span_source_callsite(span)
+}
+
+extern "C" fn span_expand_to_lines<'ast>(data: &'ast MarkerContextData, span: &Span<'ast>) -> Span<'ast> {
+    unsafe { as_driver(data) }.span_expand_to_lines(span)
+}
+
 extern "C" fn span_pos_to_file_loc<'ast>(
     data: &'ast MarkerContextData,
     file: &FileInfo<'ast>,
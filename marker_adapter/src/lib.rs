@@ -3,25 +3,29 @@
 #![warn(clippy::index_refutable_slice)]
 #![allow(clippy::module_name_repetitions)]
 
+mod config;
 mod error;
 mod loader;
 
 pub mod context;
 
+pub use config::LintLevelInfo;
 pub use error::{Error, Result};
 pub use loader::LintCrateInfo;
 
 use loader::LintCrateRegistry;
-use marker_api::Lint;
 use marker_api::{
     ast::{Body, Crate, EnumVariant, ExprKind, ItemField, ItemKind, StmtKind},
     context::MarkerContext,
     LintPass, LintPassInfo,
 };
+use marker_api::{Lint, LintGroup};
 use marker_utils::visitor::{self, Visitor};
 use std::{cell::RefCell, ops::ControlFlow};
 
 pub const LINT_CRATES_ENV: &str = "MARKER_LINT_CRATES";
+pub const PACKAGE_LINT_CRATES_ENV: &str = "MARKER_PACKAGE_LINT_CRATES";
+pub const LINT_LEVELS_ENV: &str = "MARKER_LINT_LEVELS";
 
 /// This struct is the interface used by lint drivers to load lint crates, pass
 /// `marker_api` objects to external lint passes and all other magic you can think of.
@@ -63,6 +67,14 @@ impl Adapter {
             .collect()
     }
 
+    pub fn marker_lint_groups(&self) -> Vec<&'static LintGroup> {
+        self.lint_pass_infos()
+            .iter()
+            .flat_map(marker_api::LintPassInfo::groups)
+            .copied()
+            .collect()
+    }
+
     #[must_use]
     fn lint_pass_infos(&self) -> Vec<LintPassInfo> {
         self.inner.borrow().external_lint_crates.collect_lint_pass_info()
@@ -74,6 +86,9 @@ impl Adapter {
         inner.external_lint_crates.set_ast_context(cx);
 
         inner.external_lint_crates.check_crate(cx, krate);
+        for file in cx.source_files() {
+            inner.external_lint_crates.check_file(cx, file);
+        }
         visitor::traverse_item::<()>(cx, inner, ItemKind::Mod(krate.root_mod()));
     }
 }
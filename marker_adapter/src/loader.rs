@@ -5,7 +5,8 @@ use libloading::Library;
 use marker_api::{LintCrateBindings, MarkerContext};
 use marker_api::{LintPass, LintPassInfo, MARKER_API_VERSION};
 
-use super::LINT_CRATES_ENV;
+use super::config::package_entry_from_env;
+use super::{LINT_CRATES_ENV, PACKAGE_LINT_CRATES_ENV};
 
 /// A struct describing a lint crate that can be loaded.
 #[derive(Debug, Clone)]
@@ -18,7 +19,9 @@ pub struct LintCrateInfo {
 
 impl LintCrateInfo {
     /// This function tries to load the list of [`LintCrateInfo`]s from the
-    /// [`LINT_CRATES_ENV`] environment value.
+    /// [`LINT_CRATES_ENV`] environment value. The list is filtered by the
+    /// [`PACKAGE_LINT_CRATES_ENV`] environment value, if it's set, to only
+    /// contain the lint crates, which are selected for the current package.
     ///
     /// # Errors
     ///
@@ -44,6 +47,12 @@ impl LintCrateInfo {
                 path: path.into(),
             });
         }
+
+        if let Some(selection) = package_entry_from_env(PACKAGE_LINT_CRATES_ENV)? {
+            let selection: Vec<_> = selection.split(',').collect();
+            lint_crates.retain(|krate| selection.contains(&krate.name.as_str()));
+        }
+
         Ok(Some(lint_crates))
     }
 }
@@ -65,20 +74,47 @@ impl LintCrateRegistry {
 
         let lint_passes = new_self.collect_lint_pass_info();
 
-        let errors = lint_passes
+        // Lint groups share the namespace with lints, the names therefore
+        // have to be unique across both.
+        let lint_defs = lint_passes
             .iter()
             .flat_map(LintPassInfo::lints)
-            .into_group_map_by(|lint| lint.name.to_ascii_lowercase())
+            .map(|lint| (lint.name, lint.fqn));
+        let group_defs = lint_passes
+            .iter()
+            .flat_map(LintPassInfo::groups)
+            .map(|group| (group.name, group.fqn));
+        let errors = lint_defs
+            .chain(group_defs)
+            .into_group_map_by(|(name, _)| name.to_ascii_lowercase())
             .into_iter()
-            .filter(|(_, lints)| lints.len() > 1)
-            .map(|(lint_name, lints)| {
-                let defs = lints.iter().map(|lint| format!("- {}", lint.fqn)).format("\n");
+            .filter(|(_, defs)| defs.len() > 1)
+            .map(|(lint_name, defs)| {
+                let defs = defs.iter().map(|(_, fqn)| format!("- {fqn}")).format("\n");
 
                 Error::root(format!("The lint `{lint_name}` is defined multiple times:\n{defs}",))
             });
 
         Error::try_many(errors, "Found several lint name conflicts")?;
 
+        let errors = lint_passes.iter().flat_map(|info| {
+            info.groups().iter().flat_map(move |group| {
+                group
+                    .lints
+                    .get()
+                    .iter()
+                    .filter(|lint| !info.lints().contains(lint))
+                    .map(move |lint| {
+                        Error::root(format!(
+                            "The lint group `{}` contains the lint `{}`, which isn't registered by the lint crate",
+                            group.fqn, lint.fqn
+                        ))
+                    })
+            })
+        });
+
+        Error::try_many(errors, "Found invalid lint groups")?;
+
         Ok(new_self)
     }
 
@@ -105,6 +141,12 @@ impl LintPass for LintCrateRegistry {
         }
     }
 
+    fn check_file<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, file: &'ast marker_api::span::SourceFile<'ast>) {
+        for lp in &self.passes {
+            (lp.bindings.check_file)(cx, file);
+        }
+    }
+
     fn check_item<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, item: marker_api::ast::ItemKind<'ast>) {
         for lp in &self.passes {
             (lp.bindings.check_item)(cx, item);
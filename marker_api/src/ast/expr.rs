@@ -9,20 +9,26 @@ use crate::{
 
 use std::{fmt::Debug, marker::PhantomData};
 
+mod asm_expr;
 mod block_expr;
 mod call_exprs;
 mod control_flow_expr;
 mod ctor_expr;
+mod format_args_expr;
 mod lit_expr;
+mod offset_of_expr;
 mod op_exprs;
 mod path_expr;
 mod place_expr;
 mod unstable_expr;
+pub use asm_expr::*;
 pub use block_expr::*;
 pub use call_exprs::*;
 pub use control_flow_expr::*;
 pub use ctor_expr::*;
+pub use format_args_expr::*;
 pub use lit_expr::*;
+pub use offset_of_expr::*;
 pub use op_exprs::*;
 pub use path_expr::*;
 pub use place_expr::*;
@@ -60,6 +66,7 @@ pub enum ExprKind<'ast> {
     BoolLit(&'ast BoolLitExpr<'ast>),
     Block(&'ast BlockExpr<'ast>),
     Closure(&'ast ClosureExpr<'ast>),
+    Coroutine(&'ast CoroutineExpr<'ast>),
     UnaryOp(&'ast UnaryOpExpr<'ast>),
     Ref(&'ast RefExpr<'ast>),
     BinaryOp(&'ast BinaryOpExpr<'ast>),
@@ -85,6 +92,9 @@ pub enum ExprKind<'ast> {
     Loop(&'ast LoopExpr<'ast>),
     While(&'ast WhileExpr<'ast>),
     Await(&'ast AwaitExpr<'ast>),
+    FormatArgs(&'ast FormatArgsExpr<'ast>),
+    InlineAsm(&'ast InlineAsmExpr<'ast>),
+    OffsetOf(&'ast OffsetOfExpr<'ast>),
     Unstable(&'ast UnstableExpr<'ast>),
 }
 
@@ -171,6 +181,9 @@ pub enum ExprPrecedence {
     Loop = 0x1400_0005,
     While = 0x1400_0006,
     Await = 0x1400_0007,
+    /// Macro calls, which are represented by a dedicated expression, like
+    /// [`FormatArgsExpr`]
+    Macro = 0x1400_0008,
 
     Path = 0x1300_0000,
 
@@ -254,13 +267,13 @@ macro_rules! impl_expr_kind_fn {
     (ExprKind: $method:ident () -> $return_ty:ty) => {
         impl_expr_kind_fn!((ExprKind) $method() -> $return_ty,
             IntLit, FloatLit, StrLit, CharLit, BoolLit,
-            Block, Closure,
+            Block, Closure, Coroutine,
             UnaryOp, Ref, BinaryOp, Try, As, Assign,
             Path, Index, Field,
             Call, Method,
             Array, Tuple, Ctor, Range,
             If, Let, Match, Break, Return, Continue, For, Loop, While,
-            Await,
+            Await, FormatArgs, InlineAsm, OffsetOf,
             Unstable
         );
     };
@@ -388,6 +401,7 @@ mod test {
         assert_size_of::<BoolLitExpr<'_>>(&expect!["24"]);
         assert_size_of::<BlockExpr<'_>>(&expect!["96"]);
         assert_size_of::<ClosureExpr<'_>>(&expect!["72"]);
+        assert_size_of::<CoroutineExpr<'_>>(&expect!["80"]);
         assert_size_of::<UnaryOpExpr<'_>>(&expect!["40"]);
         assert_size_of::<RefExpr<'_>>(&expect!["40"]);
         assert_size_of::<BinaryOpExpr<'_>>(&expect!["56"]);
@@ -412,6 +426,8 @@ mod test {
         assert_size_of::<ForExpr<'_>>(&expect!["88"]);
         assert_size_of::<LoopExpr<'_>>(&expect!["56"]);
         assert_size_of::<WhileExpr<'_>>(&expect!["72"]);
-        assert_size_of::<UnstableExpr<'_>>(&expect!["24"]);
+        assert_size_of::<InlineAsmExpr<'_>>(&expect!["80"]);
+        assert_size_of::<OffsetOfExpr<'_>>(&expect!["48"]);
+        assert_size_of::<UnstableExpr<'_>>(&expect!["48"]);
     }
 }
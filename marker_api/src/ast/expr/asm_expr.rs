@@ -0,0 +1,493 @@
+use std::marker::PhantomData;
+
+use crate::{
+    common::{ItemId, SpanId, SymbolId},
+    context::with_cx,
+    ffi::{FfiOption, FfiSlice, FfiStr},
+    span::Span,
+};
+
+use super::{CommonExprData, ConstExpr, ExprKind, ExprPrecedence};
+
+/// An inline assembly expression, created by the [`asm!`](std::arch::asm) macro.
+///
+/// ```
+/// # #[cfg(target_arch = "x86_64")]
+/// # unsafe {
+/// # let input: u64 = 1;
+/// let output: u64;
+/// std::arch::asm!(
+///     "mov {0}, {1}",          // The template
+///     "add {0}, 5",            // The template is continued
+///     out(reg) output,         // An output operand
+///     in(reg) input,           // An input operand
+///     options(pure, nomem),    // Options
+/// );
+/// # }
+/// ```
+///
+/// See: <https://doc.rust-lang.org/reference/inline-assembly.html>
+#[repr(C)]
+#[derive(Debug)]
+pub struct InlineAsmExpr<'ast> {
+    data: CommonExprData<'ast>,
+    template: FfiSlice<'ast, AsmPieceKind<'ast>>,
+    operands: FfiSlice<'ast, AsmOperandKind<'ast>>,
+    clobber_abis: FfiSlice<'ast, AsmClobberAbi<'ast>>,
+    options: AsmOptions,
+}
+
+impl<'ast> InlineAsmExpr<'ast> {
+    /// The literal pieces and placeholders of the template. Template strings,
+    /// which are passed as separate arguments, are joined with a newline.
+    pub fn template(&self) -> &[AsmPieceKind<'ast>] {
+        self.template.get()
+    }
+
+    /// The operands of this expression, in the order in which they are written.
+    /// Explicit register operands, like `out("eax") _`, are also included. The
+    /// registers clobbered by a [`clobber_abi`](Self::clobber_abis) are added
+    /// as clobber operands, with the [span](AsmClobberAbi::span) of the ABI.
+    pub fn operands(&self) -> &[AsmOperandKind<'ast>] {
+        self.operands.get()
+    }
+
+    /// The ABIs specified with `clobber_abi(..)`, like the `"C"` in
+    /// `clobber_abi("C")`.
+    ///
+    /// ```
+    /// # #[cfg(target_arch = "x86_64")]
+    /// # unsafe {
+    /// std::arch::asm!(
+    ///     "/* {} */",
+    ///     in(reg) 1,
+    ///     clobber_abi("sysv64", "win64"), // Two ABIs in one argument
+    /// );
+    /// # }
+    /// ```
+    pub fn clobber_abis(&self) -> &[AsmClobberAbi<'ast>] {
+        self.clobber_abis.get()
+    }
+
+    /// The options of this expression, like `nomem` in `options(nomem)`.
+    pub fn options(&self) -> &AsmOptions {
+        &self.options
+    }
+}
+
+super::impl_expr_data!(
+    InlineAsmExpr<'ast>,
+    InlineAsm,
+    fn precedence(&self) -> ExprPrecedence {
+        ExprPrecedence::Macro
+    }
+);
+
+#[cfg(feature = "driver-api")]
+impl<'ast> InlineAsmExpr<'ast> {
+    pub fn new(
+        data: CommonExprData<'ast>,
+        template: &'ast [AsmPieceKind<'ast>],
+        operands: &'ast [AsmOperandKind<'ast>],
+        clobber_abis: &'ast [AsmClobberAbi<'ast>],
+        options: AsmOptions,
+    ) -> Self {
+        Self {
+            data,
+            template: template.into(),
+            operands: operands.into(),
+            clobber_abis: clobber_abis.into(),
+            options,
+        }
+    }
+}
+
+/// An ABI of a `clobber_abi(..)` argument, like the `"C"` in `clobber_abi("C")`.
+/// All registers, which are not preserved by the ABI, are marked as clobbered.
+#[repr(C)]
+#[derive(Debug)]
+pub struct AsmClobberAbi<'ast> {
+    _lifetime: PhantomData<&'ast ()>,
+    name: SymbolId,
+    span: SpanId,
+}
+
+impl<'ast> AsmClobberAbi<'ast> {
+    /// The name of the ABI, like `C` or `system`, without quotes.
+    pub fn name(&self) -> &str {
+        with_cx(self, |cx| cx.symbol_str(self.name))
+    }
+
+    /// The [`Span`] of the `clobber_abi("C")` argument. If one argument
+    /// specifies several ABIs, like `clobber_abi("C", "system")`, this is the
+    /// span of the quoted ABI name instead.
+    pub fn span(&self) -> &Span<'ast> {
+        with_cx(self, |cx| cx.span(self.span))
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> AsmClobberAbi<'ast> {
+    pub fn new(name: SymbolId, span: SpanId) -> Self {
+        Self {
+            _lifetime: PhantomData,
+            name,
+            span,
+        }
+    }
+}
+
+/// A piece of an inline assembly template, which is either a literal or a placeholder.
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone)]
+pub enum AsmPieceKind<'ast> {
+    Lit(&'ast AsmLitPiece<'ast>),
+    Placeholder(&'ast AsmPlaceholder<'ast>),
+}
+
+/// A literal piece of an inline assembly template, like the `mov ` in `"mov {0}, 1"`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct AsmLitPiece<'ast> {
+    value: FfiStr<'ast>,
+}
+
+impl<'ast> AsmLitPiece<'ast> {
+    /// The text of this piece. Escaped curly braces, like `{{`, have already
+    /// been replaced by the characters they represent.
+    pub fn value(&self) -> &str {
+        self.value.get()
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> AsmLitPiece<'ast> {
+    pub fn new(value: &'ast str) -> Self {
+        Self { value: value.into() }
+    }
+}
+
+/// A placeholder of an inline assembly template, like `{}`, `{0}` or `{x:e}`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct AsmPlaceholder<'ast> {
+    _lifetime: PhantomData<&'ast ()>,
+    operand: usize,
+    modifier: FfiOption<char>,
+    span: SpanId,
+}
+
+impl<'ast> AsmPlaceholder<'ast> {
+    /// The index of the referenced operand in [`InlineAsmExpr::operands`].
+    pub fn operand(&self) -> usize {
+        self.operand
+    }
+
+    /// The template modifier, like the `e` in `{x:e}`.
+    pub fn modifier(&self) -> Option<char> {
+        self.modifier.copy()
+    }
+
+    pub fn span(&self) -> &Span<'ast> {
+        with_cx(self, |cx| cx.span(self.span))
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> AsmPlaceholder<'ast> {
+    pub fn new(operand: usize, modifier: Option<char>, span: SpanId) -> Self {
+        Self {
+            _lifetime: PhantomData,
+            operand,
+            modifier: modifier.into(),
+            span,
+        }
+    }
+}
+
+/// An operand of an [`InlineAsmExpr`].
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone)]
+pub enum AsmOperandKind<'ast> {
+    /// A register operand, like `in(reg) x`, `out("eax") _` or `inout(reg) x => y`.
+    Reg(&'ast AsmRegOperand<'ast>),
+    /// A constant operand, like `const 5`.
+    Const(&'ast AsmConstOperand<'ast>),
+    /// A function symbol operand, like `sym function`. The path of the function
+    /// is stored as a constant expression.
+    SymFn(&'ast AsmConstOperand<'ast>),
+    /// A static symbol operand, like `sym STATIC`.
+    SymStatic(&'ast AsmSymStaticOperand<'ast>),
+}
+
+impl<'ast> AsmOperandKind<'ast> {
+    /// The [`Span`] of the entire operand.
+    pub fn span(&self) -> &Span<'ast> {
+        match self {
+            AsmOperandKind::Reg(operand) => operand.span(),
+            AsmOperandKind::Const(operand) | AsmOperandKind::SymFn(operand) => operand.span(),
+            AsmOperandKind::SymStatic(operand) => operand.span(),
+        }
+    }
+}
+
+/// A register operand of an [`InlineAsmExpr`].
+///
+/// ```
+/// # #[cfg(target_arch = "x86_64")]
+/// # unsafe {
+/// # let (a, b): (u64, u64) = (1, 2);
+/// # let (c, d): (u64, u64);
+/// # let mut e: u64 = 3;
+/// std::arch::asm!(
+///     "/* {} {} {} {} */",
+///     in(reg) a,              // Direction: `In`
+///     lateout(reg) c,         // Direction: `Out`, late
+///     inout(reg) e,           // Direction: `InOut`
+///     inout(reg) b => d,      // Direction: `SplitInOut`
+///     out("rax") _,           // Direction: `Out`, a clobbered register
+/// );
+/// # }
+/// ```
+#[repr(C)]
+#[derive(Debug)]
+#[cfg_attr(feature = "driver-api", derive(typed_builder::TypedBuilder))]
+pub struct AsmRegOperand<'ast> {
+    span: SpanId,
+    direction: AsmDirection,
+    reg: AsmReg<'ast>,
+    #[cfg_attr(feature = "driver-api", builder(default))]
+    late: bool,
+    #[cfg_attr(feature = "driver-api", builder(setter(into), default))]
+    in_expr: FfiOption<ExprKind<'ast>>,
+    #[cfg_attr(feature = "driver-api", builder(setter(into), default))]
+    out_expr: FfiOption<ExprKind<'ast>>,
+}
+
+impl<'ast> AsmRegOperand<'ast> {
+    /// The [`Span`] of the entire operand, like `inout(reg) x => y`. For
+    /// registers clobbered by a `clobber_abi(..)`, this is the span of the
+    /// [`AsmClobberAbi`].
+    pub fn span(&self) -> &Span<'ast> {
+        with_cx(self, |cx| cx.span(self.span))
+    }
+
+    /// The [`AsmDirection`] of this operand, which determines if the register
+    /// is read, written or both.
+    pub fn direction(&self) -> AsmDirection {
+        self.direction
+    }
+
+    /// The register or register class used by this operand.
+    pub fn reg(&self) -> &AsmReg<'ast> {
+        &self.reg
+    }
+
+    /// Returns `true`, if the register is only allocated after all inputs have
+    /// been read, like for `lateout(reg) x`.
+    pub fn is_late(&self) -> bool {
+        self.late
+    }
+
+    /// The input expression, like the `x` in `in(reg) x` or `inout(reg) x => y`.
+    /// This is [`None`] for [`AsmDirection::Out`] operands.
+    pub fn in_expr(&self) -> Option<ExprKind<'ast>> {
+        self.in_expr.copy()
+    }
+
+    /// The output expression, like the `y` in `out(reg) y` or `inout(reg) x => y`.
+    /// This is [`None`] for [`AsmDirection::In`] operands and for outputs, which
+    /// are discarded with an underscore. For [`AsmDirection::InOut`] operands,
+    /// this is the same expression as the input.
+    pub fn out_expr(&self) -> Option<ExprKind<'ast>> {
+        self.out_expr.copy()
+    }
+
+    /// Returns `true`, if this operand marks an explicit register as clobbered,
+    /// like `out("eax") _`.
+    pub fn is_clobber(&self) -> bool {
+        self.direction == AsmDirection::Out && self.reg.is_explicit() && self.out_expr.get().is_none()
+    }
+}
+
+/// The direction of an [`AsmRegOperand`], which describes how the register
+/// is used by the assembly code.
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AsmDirection {
+    /// `in(<reg>) <expr>`
+    In,
+    /// `out(<reg>) <expr>` or `lateout(<reg>) <expr>`
+    Out,
+    /// `inout(<reg>) <expr>` or `inlateout(<reg>) <expr>`
+    InOut,
+    /// `inout(<reg>) <in expr> => <out expr>`
+    SplitInOut,
+}
+
+/// The register of an [`AsmRegOperand`]. This can either be an explicit register,
+/// like the `"eax"` in `in("eax") x`, or a register class, like the `reg` in
+/// `in(reg) x`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct AsmReg<'ast> {
+    name: FfiStr<'ast>,
+    is_explicit: bool,
+}
+
+impl<'ast> AsmReg<'ast> {
+    /// The name of the register or register class, like `eax` or `reg`.
+    pub fn name(&self) -> &str {
+        self.name.get()
+    }
+
+    /// Returns `true`, if this is an explicit register, like `"eax"`, and not
+    /// a register class.
+    pub fn is_explicit(&self) -> bool {
+        self.is_explicit
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> AsmReg<'ast> {
+    pub fn new(name: &'ast str, is_explicit: bool) -> Self {
+        Self {
+            name: name.into(),
+            is_explicit,
+        }
+    }
+}
+
+/// A constant operand of an [`InlineAsmExpr`], like `const 5` or `sym function`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct AsmConstOperand<'ast> {
+    span: SpanId,
+    expr: ConstExpr<'ast>,
+}
+
+impl<'ast> AsmConstOperand<'ast> {
+    pub fn span(&self) -> &Span<'ast> {
+        with_cx(self, |cx| cx.span(self.span))
+    }
+
+    pub fn expr(&self) -> &ConstExpr<'ast> {
+        &self.expr
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> AsmConstOperand<'ast> {
+    pub fn new(span: SpanId, expr: ConstExpr<'ast>) -> Self {
+        Self { span, expr }
+    }
+}
+
+/// A static symbol operand of an [`InlineAsmExpr`], like `sym STATIC`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct AsmSymStaticOperand<'ast> {
+    _lifetime: PhantomData<&'ast ()>,
+    span: SpanId,
+    item: ItemId,
+}
+
+impl<'ast> AsmSymStaticOperand<'ast> {
+    pub fn span(&self) -> &Span<'ast> {
+        with_cx(self, |cx| cx.span(self.span))
+    }
+
+    /// The [`ItemId`] of the referenced static item.
+    pub fn item(&self) -> ItemId {
+        self.item
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> AsmSymStaticOperand<'ast> {
+    pub fn new(span: SpanId, item: ItemId) -> Self {
+        Self {
+            _lifetime: PhantomData,
+            span,
+            item,
+        }
+    }
+}
+
+/// The options of an [`InlineAsmExpr`], like `options(pure, nomem)`.
+///
+/// See: <https://doc.rust-lang.org/reference/inline-assembly.html#options>
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "driver-api", derive(typed_builder::TypedBuilder))]
+#[allow(clippy::struct_excessive_bools)]
+pub struct AsmOptions {
+    #[cfg_attr(feature = "driver-api", builder(default))]
+    pure: bool,
+    #[cfg_attr(feature = "driver-api", builder(default))]
+    nomem: bool,
+    #[cfg_attr(feature = "driver-api", builder(default))]
+    readonly: bool,
+    #[cfg_attr(feature = "driver-api", builder(default))]
+    preserves_flags: bool,
+    #[cfg_attr(feature = "driver-api", builder(default))]
+    noreturn: bool,
+    #[cfg_attr(feature = "driver-api", builder(default))]
+    nostack: bool,
+    #[cfg_attr(feature = "driver-api", builder(default))]
+    att_syntax: bool,
+    #[cfg_attr(feature = "driver-api", builder(default))]
+    raw: bool,
+    #[cfg_attr(feature = "driver-api", builder(default))]
+    may_unwind: bool,
+}
+
+impl AsmOptions {
+    /// `options(pure)`
+    pub fn is_pure(&self) -> bool {
+        self.pure
+    }
+
+    /// `options(nomem)`
+    pub fn is_nomem(&self) -> bool {
+        self.nomem
+    }
+
+    /// `options(readonly)`
+    pub fn is_readonly(&self) -> bool {
+        self.readonly
+    }
+
+    /// `options(preserves_flags)`
+    pub fn preserves_flags(&self) -> bool {
+        self.preserves_flags
+    }
+
+    /// `options(noreturn)`
+    pub fn is_noreturn(&self) -> bool {
+        self.noreturn
+    }
+
+    /// `options(nostack)`
+    pub fn is_nostack(&self) -> bool {
+        self.nostack
+    }
+
+    /// `options(att_syntax)`
+    pub fn is_att_syntax(&self) -> bool {
+        self.att_syntax
+    }
+
+    /// `options(raw)`
+    pub fn is_raw(&self) -> bool {
+        self.raw
+    }
+
+    /// `options(may_unwind)`
+    pub fn may_unwind(&self) -> bool {
+        self.may_unwind
+    }
+}
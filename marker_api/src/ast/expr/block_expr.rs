@@ -1,11 +1,11 @@
 use crate::{
     ast::{pat::PatKind, stmt::StmtKind, ty::TyKind},
-    common::{BodyId, Safety, SpanId, Syncness},
+    common::{BodyId, CoroutineKind, Movability, Safety, SpanId, Syncness},
     ffi::{FfiOption, FfiSlice},
     span::Ident,
 };
 
-use super::{CommonExprData, ExprKind};
+use super::{CommonExprData, ExprKind, ExprPrecedence};
 
 /// A block expression is one of the most fundamental expressions in Rust. It
 /// is used by items and expressions to group statements together and express
@@ -37,7 +37,10 @@ use super::{CommonExprData, ExprKind};
 /// clear by linking directly to the [`BlockExpr`] struct or calling it a *block*.
 ///
 /// This expression also represents async blocks, the internal desugar used by
-/// rustc is resugared for this.
+/// rustc is resugared for this. The [`ExprId`](crate::common::ExprId) of an async
+/// block belongs to the desugared coroutine. The [semantic type](super::ExprData::ty)
+/// is therefore the anonymous future type, a [`sem::CoroutineTy`](crate::sem::CoroutineTy),
+/// and not the type of the value returned by the block.
 #[repr(C)]
 #[derive(Debug)]
 pub struct BlockExpr<'ast> {
@@ -134,11 +137,16 @@ impl<'ast> BlockExpr<'ast> {
 ///     print("Marker")
 /// };
 /// ```
+///
+/// This expression also represents async closures. Rustc desugars the body of
+/// an async closure into a coroutine, this desugar is resugared, meaning that
+/// the body of the closure directly contains the written expression.
 #[repr(C)]
 #[derive(Debug)]
 pub struct ClosureExpr<'ast> {
     data: CommonExprData<'ast>,
     capture_kind: CaptureKind,
+    syncness: Syncness,
     params: FfiSlice<'ast, ClosureParam<'ast>>,
     return_ty: FfiOption<TyKind<'ast>>,
     body_id: BodyId,
@@ -149,6 +157,20 @@ impl<'ast> ClosureExpr<'ast> {
         self.capture_kind
     }
 
+    /// Returns [`Syncness::Async`] for async closures, like:
+    ///
+    /// ```
+    /// # #![feature(async_closure)]
+    /// let _ = async move |x: u32| x + 1;
+    /// //      ^^^^^ The `async` keyword
+    /// ```
+    ///
+    /// The value, returned by [`ClosureExpr::return_ty`] is the output type of
+    /// the created future.
+    pub fn syncness(&self) -> Syncness {
+        self.syncness
+    }
+
     pub fn params(&self) -> &'ast [ClosureParam<'ast>] {
         self.params.get()
     }
@@ -169,12 +191,112 @@ impl<'ast> ClosureExpr<'ast> {
     pub fn new(
         data: CommonExprData<'ast>,
         capture_kind: CaptureKind,
+        syncness: Syncness,
+        params: &'ast [ClosureParam<'ast>],
+        return_ty: Option<TyKind<'ast>>,
+        body_id: BodyId,
+    ) -> Self {
+        Self {
+            data,
+            capture_kind,
+            syncness,
+            params: params.into(),
+            return_ty: return_ty.into(),
+            body_id,
+        }
+    }
+}
+
+/// A coroutine expression. This includes explicit coroutines, which are
+/// written like closures containing `yield` expressions, and `gen` blocks.
+///
+/// ```
+/// # #![feature(coroutines)]
+/// //          vvvvvv The optional `static` keyword, defining the movability
+/// let _ = static move || {
+/// //             ^^^^ The capture kind
+///     yield 1;
+///     yield 2;
+/// };
+/// ```
+///
+/// Async blocks and closures are represented by [`BlockExpr`] and [`ClosureExpr`],
+/// as their coroutine is a desugar, which is resugared for the API.
+#[repr(C)]
+#[derive(Debug)]
+pub struct CoroutineExpr<'ast> {
+    data: CommonExprData<'ast>,
+    kind: CoroutineKind,
+    movability: Movability,
+    capture_kind: CaptureKind,
+    params: FfiSlice<'ast, ClosureParam<'ast>>,
+    return_ty: FfiOption<TyKind<'ast>>,
+    body_id: BodyId,
+}
+
+impl<'ast> CoroutineExpr<'ast> {
+    /// The [`CoroutineKind`], which distinguishes explicit coroutines from
+    /// `gen` blocks.
+    pub fn kind(&self) -> CoroutineKind {
+        self.kind
+    }
+
+    /// The [`Movability`] of the coroutine. Coroutines declared with the
+    /// `static` keyword are [`Movability::Static`] and can hold references
+    /// to their own data across `yield` points.
+    pub fn movability(&self) -> Movability {
+        self.movability
+    }
+
+    /// The [`CaptureKind`] of the coroutine, which is [`CaptureKind::Move`]
+    /// if the `move` keyword is used.
+    pub fn capture_kind(&self) -> CaptureKind {
+        self.capture_kind
+    }
+
+    /// The parameters of the coroutine. Explicit coroutines can declare a
+    /// single resume argument, `gen` blocks will always return an empty slice.
+    pub fn params(&self) -> &'ast [ClosureParam<'ast>] {
+        self.params.get()
+    }
+
+    /// The explicit return type of the coroutine, like the `u32` in
+    /// `|| -> u32 { yield 1; 2 }`. This is [`None`], if the return type is
+    /// inferred and for `gen` blocks.
+    pub fn return_ty(&self) -> Option<TyKind<'ast>> {
+        self.return_ty.copy()
+    }
+
+    /// The [`BodyId`] of the coroutine body. The body contains the captured
+    /// expression, including the `yield` expressions.
+    pub fn body_id(&self) -> BodyId {
+        self.body_id
+    }
+}
+
+super::impl_expr_data!(
+    CoroutineExpr<'ast>,
+    Coroutine,
+    fn precedence(&self) -> ExprPrecedence {
+        ExprPrecedence::Closure
+    }
+);
+
+#[cfg(feature = "driver-api")]
+impl<'ast> CoroutineExpr<'ast> {
+    pub fn new(
+        data: CommonExprData<'ast>,
+        kind: CoroutineKind,
+        movability: Movability,
+        capture_kind: CaptureKind,
         params: &'ast [ClosureParam<'ast>],
         return_ty: Option<TyKind<'ast>>,
         body_id: BodyId,
     ) -> Self {
         Self {
             data,
+            kind,
+            movability,
             capture_kind,
             params: params.into(),
             return_ty: return_ty.into(),
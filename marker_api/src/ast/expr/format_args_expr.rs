@@ -0,0 +1,456 @@
+use std::marker::PhantomData;
+
+use crate::{
+    common::{SpanId, SymbolId},
+    context::with_cx,
+    ffi::{FfiOption, FfiSlice},
+    span::{Ident, Span},
+};
+
+use super::{CommonExprData, ExprKind, ExprPrecedence};
+
+/// An expression created by the `format_args!` macro. The macro is used by
+/// [`format!`], [`println!`], [`write!`], [`panic!`], [`assert!`] and many other
+/// macros, to format text.
+///
+/// ```
+/// # let name = "Marker";
+/// # let value = 1.0;
+/// //           vvvvvvvvvvvvvvvvvvvvvvv The format string
+/// let _ = format!("Hello {name}: {:>8.2}", value);
+/// //               ^^^^^^                 A literal piece
+/// //                     ^^^^^^           A placeholder, with the captured argument `name`
+/// //                           ^^         A literal piece
+/// //                             ^^^^^^^  A placeholder, with a format spec
+/// //                                      ^^^^^ An explicit argument
+/// ```
+///
+/// Nested `format_args!` calls are not flattened. Rustc flattens them and inlines
+/// literal arguments by default, these calls are then represented by their lowered
+/// expression instead. See `cargo marker check --unflatten-format-args`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct FormatArgsExpr<'ast> {
+    data: CommonExprData<'ast>,
+    format_str_span: SpanId,
+    pieces: FfiSlice<'ast, FormatPieceKind<'ast>>,
+    args: FfiSlice<'ast, FormatArg<'ast>>,
+}
+
+impl<'ast> FormatArgsExpr<'ast> {
+    /// The [`Span`] of the format string. The format string might be created by
+    /// another macro, like `concat!`. In that case, the span will point to that
+    /// macro call.
+    pub fn format_str_span(&self) -> &Span<'ast> {
+        with_cx(self, |cx| cx.span(self.format_str_span))
+    }
+
+    /// The literal pieces and placeholders of the format string, in the order
+    /// in which they appear.
+    pub fn pieces(&self) -> &[FormatPieceKind<'ast>] {
+        self.pieces.get()
+    }
+
+    /// An iterator over all placeholders of the format string.
+    pub fn placeholders(&self) -> impl Iterator<Item = &'ast FormatPlaceholder<'ast>> + '_ {
+        self.pieces.get().iter().filter_map(|piece| match piece {
+            FormatPieceKind::Placeholder(placeholder) => Some(*placeholder),
+            FormatPieceKind::Lit(_) => None,
+        })
+    }
+
+    /// The arguments of the format string. Explicit arguments are followed by
+    /// arguments captured from the format string, like the `name` in `"{name}"`.
+    /// Every argument is only listed once, even if it's referenced by multiple
+    /// placeholders.
+    pub fn args(&self) -> &[FormatArg<'ast>] {
+        self.args.get()
+    }
+}
+
+super::impl_expr_data!(
+    FormatArgsExpr<'ast>,
+    FormatArgs,
+    fn precedence(&self) -> ExprPrecedence {
+        ExprPrecedence::Macro
+    }
+);
+
+#[cfg(feature = "driver-api")]
+impl<'ast> FormatArgsExpr<'ast> {
+    pub fn new(
+        data: CommonExprData<'ast>,
+        format_str_span: SpanId,
+        pieces: &'ast [FormatPieceKind<'ast>],
+        args: &'ast [FormatArg<'ast>],
+    ) -> Self {
+        Self {
+            data,
+            format_str_span,
+            pieces: pieces.into(),
+            args: args.into(),
+        }
+    }
+}
+
+/// A piece of a format string, which is either a literal or a placeholder.
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone)]
+pub enum FormatPieceKind<'ast> {
+    /// A literal piece of text, like the `Hello ` in `"Hello {name}"`.
+    Lit(&'ast FormatLitPiece<'ast>),
+    /// A placeholder, like the `{name}` in `"Hello {name}"`, which is replaced
+    /// by a formatted argument.
+    Placeholder(&'ast FormatPlaceholder<'ast>),
+}
+
+impl<'ast> FormatPieceKind<'ast> {
+    /// The [`Span`] of this piece, if it can be mapped to the source code. This
+    /// requires the format string to be a literal, written by the user.
+    pub fn span(&self) -> Option<&Span<'ast>> {
+        match self {
+            FormatPieceKind::Lit(piece) => piece.span(),
+            FormatPieceKind::Placeholder(piece) => piece.span(),
+        }
+    }
+}
+
+/// A literal piece of a format string, like the `Hello ` in `"Hello {name}"`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct FormatLitPiece<'ast> {
+    _lifetime: PhantomData<&'ast ()>,
+    value: SymbolId,
+    span: FfiOption<SpanId>,
+}
+
+impl<'ast> FormatLitPiece<'ast> {
+    /// The text of this piece. Escape sequences, like `\n` and `{{`, have already
+    /// been replaced by the characters they represent.
+    pub fn value(&self) -> &str {
+        with_cx(self, |cx| cx.symbol_str(self.value))
+    }
+
+    /// The [`Span`] of this piece, if it can be mapped to the source code.
+    pub fn span(&self) -> Option<&Span<'ast>> {
+        self.span.get().map(|span| with_cx(self, |cx| cx.span(*span)))
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> FormatLitPiece<'ast> {
+    pub fn new(value: SymbolId, span: Option<SpanId>) -> Self {
+        Self {
+            _lifetime: PhantomData,
+            value,
+            span: span.into(),
+        }
+    }
+}
+
+/// A placeholder of a format string, like `{}`, `{name:?}` or `{0:>width$}`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct FormatPlaceholder<'ast> {
+    span: FfiOption<SpanId>,
+    arg: FormatArgPos<'ast>,
+    format_trait: FormatTrait,
+    spec: FormatSpec<'ast>,
+}
+
+impl<'ast> FormatPlaceholder<'ast> {
+    /// The [`Span`] of the entire placeholder, including the curly braces, if it
+    /// can be mapped to the source code.
+    pub fn span(&self) -> Option<&Span<'ast>> {
+        self.span.get().map(|span| with_cx(self, |cx| cx.span(*span)))
+    }
+
+    /// The argument, which is formatted by this placeholder.
+    pub fn arg(&self) -> &FormatArgPos<'ast> {
+        &self.arg
+    }
+
+    /// The trait used for formatting, like [`Display`](std::fmt::Display) for `{}`.
+    pub fn format_trait(&self) -> FormatTrait {
+        self.format_trait
+    }
+
+    /// The format spec of this placeholder, like the `>8.2` in `{:>8.2}`.
+    pub fn spec(&self) -> &FormatSpec<'ast> {
+        &self.spec
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> FormatPlaceholder<'ast> {
+    pub fn new(
+        span: Option<SpanId>,
+        arg: FormatArgPos<'ast>,
+        format_trait: FormatTrait,
+        spec: FormatSpec<'ast>,
+    ) -> Self {
+        Self {
+            span: span.into(),
+            arg,
+            format_trait,
+            spec,
+        }
+    }
+}
+
+/// A reference from a format string to one of the [`FormatArgsExpr::args`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct FormatArgPos<'ast> {
+    _lifetime: PhantomData<&'ast ()>,
+    index: usize,
+    kind: FormatArgPosKind,
+    span: FfiOption<SpanId>,
+}
+
+impl<'ast> FormatArgPos<'ast> {
+    /// The index of the referenced argument in [`FormatArgsExpr::args`].
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn kind(&self) -> FormatArgPosKind {
+        self.kind
+    }
+
+    /// The [`Span`] of the explicit index or name, like the `0` in `{0}`.
+    /// Implicit references don't have a span.
+    pub fn span(&self) -> Option<&Span<'ast>> {
+        self.span.get().map(|span| with_cx(self, |cx| cx.span(*span)))
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> FormatArgPos<'ast> {
+    pub fn new(index: usize, kind: FormatArgPosKind, span: Option<SpanId>) -> Self {
+        Self {
+            _lifetime: PhantomData,
+            index,
+            kind,
+            span: span.into(),
+        }
+    }
+}
+
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FormatArgPosKind {
+    /// The argument is referenced implicitly, like in `{}` or `{:.*}`.
+    Implicit,
+    /// The argument is referenced by its index, like in `{0}` or `{:.1$}`.
+    Number,
+    /// The argument is referenced by its name, like in `{name}` or `{:width$}`.
+    Named,
+}
+
+/// The trait, which is used to format an argument.
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FormatTrait {
+    /// `{}`
+    Display,
+    /// `{:?}`
+    Debug,
+    /// `{:e}`
+    LowerExp,
+    /// `{:E}`
+    UpperExp,
+    /// `{:o}`
+    Octal,
+    /// `{:p}`
+    Pointer,
+    /// `{:b}`
+    Binary,
+    /// `{:x}`
+    LowerHex,
+    /// `{:X}`
+    UpperHex,
+}
+
+/// The format spec of a placeholder, like the `>8.2` in `{:>8.2}`.
+///
+/// See: <https://doc.rust-lang.org/std/fmt/index.html#formatting-parameters>
+#[repr(C)]
+#[derive(Debug)]
+#[cfg_attr(feature = "driver-api", derive(typed_builder::TypedBuilder))]
+pub struct FormatSpec<'ast> {
+    #[cfg_attr(feature = "driver-api", builder(setter(into), default))]
+    width: FfiOption<FormatCount<'ast>>,
+    #[cfg_attr(feature = "driver-api", builder(setter(into), default))]
+    precision: FfiOption<FormatCount<'ast>>,
+    #[cfg_attr(feature = "driver-api", builder(setter(into), default))]
+    align: FfiOption<FormatAlign>,
+    #[cfg_attr(feature = "driver-api", builder(setter(into), default))]
+    fill: FfiOption<char>,
+    #[cfg_attr(feature = "driver-api", builder(setter(into), default))]
+    sign: FfiOption<FormatSign>,
+    #[cfg_attr(feature = "driver-api", builder(default))]
+    alternate: bool,
+    #[cfg_attr(feature = "driver-api", builder(default))]
+    zero_pad: bool,
+    #[cfg_attr(feature = "driver-api", builder(setter(into), default))]
+    debug_hex: FfiOption<FormatDebugHex>,
+}
+
+impl<'ast> FormatSpec<'ast> {
+    /// The minimum width, like the `8` in `{:8}`.
+    pub fn width(&self) -> Option<&FormatCount<'ast>> {
+        self.width.get()
+    }
+
+    /// The precision, like the `2` in `{:.2}`.
+    pub fn precision(&self) -> Option<&FormatCount<'ast>> {
+        self.precision.get()
+    }
+
+    /// The alignment, like the `>` in `{:>8}`.
+    pub fn align(&self) -> Option<FormatAlign> {
+        self.align.copy()
+    }
+
+    /// The fill character, like the `-` in `{:->8}`.
+    pub fn fill(&self) -> Option<char> {
+        self.fill.copy()
+    }
+
+    /// The sign flag, like the `+` in `{:+}`.
+    pub fn sign(&self) -> Option<FormatSign> {
+        self.sign.copy()
+    }
+
+    /// Returns `true`, if the `#` flag is set, like in `{:#?}`.
+    pub fn is_alternate(&self) -> bool {
+        self.alternate
+    }
+
+    /// Returns `true`, if the `0` flag is set, like in `{:08}`.
+    pub fn is_zero_pad(&self) -> bool {
+        self.zero_pad
+    }
+
+    /// The hex flag for debug formatting, like the `x` in `{:x?}`.
+    pub fn debug_hex(&self) -> Option<FormatDebugHex> {
+        self.debug_hex.copy()
+    }
+
+    /// Returns `true`, if no formatting parameter has been set.
+    pub fn is_default(&self) -> bool {
+        self.width.get().is_none()
+            && self.precision.get().is_none()
+            && self.align.get().is_none()
+            && self.fill.get().is_none()
+            && self.sign.get().is_none()
+            && !self.alternate
+            && !self.zero_pad
+            && self.debug_hex.get().is_none()
+    }
+}
+
+/// A count used for the width and precision of a [`FormatSpec`].
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy)]
+pub enum FormatCount<'ast> {
+    /// A literal count, like the `8` in `{:8}`.
+    Lit(usize),
+    /// A count provided by an argument, like the `width$` in `{:width$}` or the
+    /// `*` in `{:.*}`.
+    Arg(FormatArgPos<'ast>),
+}
+
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FormatAlign {
+    /// `{:<}`
+    Left,
+    /// `{:>}`
+    Right,
+    /// `{:^}`
+    Center,
+}
+
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FormatSign {
+    /// `{:+}`
+    Plus,
+    /// `{:-}`
+    Minus,
+}
+
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FormatDebugHex {
+    /// `{:x?}`
+    Lower,
+    /// `{:X?}`
+    Upper,
+}
+
+/// An argument of a [`FormatArgsExpr`].
+///
+/// ```
+/// # let (a, b, c) = (1, 2, 3);
+/// //                             v Positional argument
+/// let _ = format!("{} {b} {c}", a, b = b);
+/// //                               ^^^^^ Named argument
+/// //                      ^ Captured argument
+/// ```
+#[repr(C)]
+#[derive(Debug)]
+pub struct FormatArg<'ast> {
+    kind: FormatArgKind,
+    ident: FfiOption<Ident<'ast>>,
+    expr: ExprKind<'ast>,
+}
+
+impl<'ast> FormatArg<'ast> {
+    pub fn kind(&self) -> FormatArgKind {
+        self.kind
+    }
+
+    /// The name of named and captured arguments.
+    pub fn ident(&self) -> Option<&Ident<'ast>> {
+        self.ident.get()
+    }
+
+    /// The argument expression. For captured arguments, this is a path
+    /// expression with the span of the name inside the format string.
+    pub fn expr(&self) -> ExprKind<'ast> {
+        self.expr
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> FormatArg<'ast> {
+    pub fn new(kind: FormatArgKind, ident: Option<Ident<'ast>>, expr: ExprKind<'ast>) -> Self {
+        Self {
+            kind,
+            ident: ident.into(),
+            expr,
+        }
+    }
+}
+
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FormatArgKind {
+    /// A positional argument, like the `a` in `format!("{}", a)`.
+    Positional,
+    /// A named argument, like the `b = b` in `format!("{b}", b = b)`.
+    Named,
+    /// An argument captured from the format string, like the `c` in `format!("{c}")`.
+    Captured,
+}
@@ -0,0 +1,58 @@
+use crate::{ast::TyKind, ffi::FfiSlice, span::Ident};
+
+use super::{CommonExprData, ExprPrecedence};
+
+/// An expression created by the [`offset_of!`](std::mem::offset_of) macro,
+/// which evaluates to the offset of a field in bytes.
+///
+/// ```
+/// # #![feature(offset_of)]
+/// struct Container {
+///     first: u8,
+///     second: u32,
+/// }
+///
+/// let _ = std::mem::offset_of!(Container, second);
+/// //                           ^^^^^^^^^  ^^^^^^ The fields
+/// //                           |
+/// //                           The container type
+/// ```
+#[repr(C)]
+#[derive(Debug)]
+pub struct OffsetOfExpr<'ast> {
+    data: CommonExprData<'ast>,
+    container: TyKind<'ast>,
+    fields: FfiSlice<'ast, Ident<'ast>>,
+}
+
+impl<'ast> OffsetOfExpr<'ast> {
+    /// The type, which contains the field.
+    pub fn container(&self) -> TyKind<'ast> {
+        self.container
+    }
+
+    /// The path of the field. This contains several identifiers, if the
+    /// offset of a nested field is requested, like in `offset_of!(Ty, a.b)`.
+    pub fn fields(&self) -> &[Ident<'ast>] {
+        self.fields.get()
+    }
+}
+
+super::impl_expr_data!(
+    OffsetOfExpr<'ast>,
+    OffsetOf,
+    fn precedence(&self) -> ExprPrecedence {
+        ExprPrecedence::Macro
+    }
+);
+
+#[cfg(feature = "driver-api")]
+impl<'ast> OffsetOfExpr<'ast> {
+    pub fn new(data: CommonExprData<'ast>, container: TyKind<'ast>, fields: &'ast [Ident<'ast>]) -> Self {
+        Self {
+            data,
+            container,
+            fields: fields.into(),
+        }
+    }
+}
@@ -1,5 +1,8 @@
+use crate::ffi::FfiStr;
+
 use super::{CommonExprData, ExprPrecedence};
 
+/// A placeholder for expressions, which are not represented by the API yet.
 #[repr(C)]
 #[derive(Debug)]
 pub struct UnstableExpr<'ast> {
@@ -7,6 +10,8 @@ pub struct UnstableExpr<'ast> {
     /// For this expression, we need to specifically store the precedence, as
     /// this could represent different expressions with different precedence.
     precedence: ExprPrecedence,
+    kind: UnstableExprKind,
+    reason: FfiStr<'ast>,
 }
 
 super::impl_expr_data!(
@@ -17,9 +22,55 @@ super::impl_expr_data!(
     }
 );
 
+impl<'ast> UnstableExpr<'ast> {
+    /// The kind of expression, which is represented by this placeholder.
+    pub fn kind(&self) -> UnstableExprKind {
+        self.kind
+    }
+
+    /// A short description of the expression, which is represented by this
+    /// placeholder, like "inline assembly". The text is intended for debugging
+    /// and might change between versions.
+    pub fn reason(&self) -> &str {
+        self.reason.get()
+    }
+}
+
 #[cfg(feature = "driver-api")]
 impl<'ast> UnstableExpr<'ast> {
-    pub fn new(data: CommonExprData<'ast>, precedence: ExprPrecedence) -> Self {
-        Self { data, precedence }
+    pub fn new(
+        data: CommonExprData<'ast>,
+        precedence: ExprPrecedence,
+        kind: UnstableExprKind,
+        reason: &'ast str,
+    ) -> Self {
+        Self {
+            data,
+            precedence,
+            kind,
+            reason: reason.into(),
+        }
     }
 }
+
+/// The kinds of expressions, which are represented by an [`UnstableExpr`].
+/// These expressions use unstable language features, their representation is
+/// therefore likely to change.
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnstableExprKind {
+    /// A `yield` expression, like `yield 1`.
+    Yield,
+    /// A `become` expression for guaranteed tail calls, like `become f()`.
+    Become,
+    /// A type ascription expression, like `type_ascribe!(x, u32)`.
+    TypeAscription,
+    /// An inline `const` block, like `const { 1 + 1 }`.
+    InlineConst,
+    /// A C string literal, like `c"text"`.
+    CStrLit,
+    /// Any other expression, which is not represented by the API yet. Checkout
+    /// [`UnstableExpr::reason`] for a description.
+    Other,
+}
@@ -187,6 +187,32 @@ pub enum GenericArgKind<'ast> {
     /// //            ^
     /// ```
     Const(&'ast ConstArg<'ast>),
+    /// An inferred generic argument, like this:
+    ///
+    /// ```
+    /// let _bar = Vec::<_>::new();
+    /// //               ^
+    /// # let _: Vec<u8> = _bar;
+    /// ```
+    Infer(&'ast InferArg<'ast>),
+    /// A constant binding as a generic argument, like this:
+    ///
+    /// ```ignore
+    /// # #![feature(associated_const_equality)]
+    /// # trait Matrix { const N: usize; }
+    /// fn foo(_: impl Matrix<N = 3>) {}
+    /// //                    ^^^^^
+    /// ```
+    ConstBinding(&'ast ConstBindingArg<'ast>),
+    /// A constraint on an associated type as a generic argument, like this:
+    ///
+    /// ```ignore
+    /// # #![feature(associated_type_bounds)]
+    /// # use std::fmt::Debug;
+    /// fn foo(_: impl Iterator<Item: Debug>) {}
+    /// //                      ^^^^^^^^^^^
+    /// ```
+    Constraint(&'ast ConstraintArg<'ast>),
 }
 
 /// This represents the generic parameters of a generic item. The bounds applied
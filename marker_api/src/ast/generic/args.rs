@@ -1,7 +1,10 @@
+use std::marker::PhantomData;
+
 use crate::{
     ast::{expr::ConstExpr, ty::TyKind, TraitRef},
     common::{SpanId, SymbolId},
     context::with_cx,
+    ffi::FfiSlice,
     span::Span,
 };
 
@@ -130,6 +133,102 @@ impl<'ast> BindingArg<'ast> {
     }
 }
 
+/// A generic binding, which sets an associated constant to a specific value.
+/// For example, `N = 3` would be the constant binding here:
+///
+/// ```ignore
+/// # #![feature(associated_const_equality)]
+/// # trait Matrix { const N: usize; }
+/// fn foo(_: impl Matrix<N = 3>) {}
+/// //                    ^^^^^
+/// ```
+///
+/// Associated constant equality is currently an unstable feature, see
+/// [rust#92827](https://github.com/rust-lang/rust/issues/92827).
+#[repr(C)]
+#[derive(Debug)]
+pub struct ConstBindingArg<'ast> {
+    span: SpanId,
+    ident: SymbolId,
+    expr: ConstExpr<'ast>,
+}
+
+impl<'ast> ConstBindingArg<'ast> {
+    /// The name of the associated constant used in the binding. This would be
+    /// `N` in the example above.
+    pub fn ident(&self) -> &str {
+        with_cx(self, |cx| cx.symbol_str(self.ident))
+    }
+
+    /// The [`ConstExpr`] that the constant is set to. This would be `3` in the
+    /// example above.
+    pub fn expr(&self) -> &ConstExpr<'ast> {
+        &self.expr
+    }
+
+    /// The [`Span`] of the binding.
+    pub fn span(&self) -> &Span<'ast> {
+        with_cx(self, |cx| cx.span(self.span))
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> ConstBindingArg<'ast> {
+    pub fn new(span: SpanId, ident: SymbolId, expr: ConstExpr<'ast>) -> Self {
+        Self { span, ident, expr }
+    }
+}
+
+/// A generic constraint, which bounds an associated type of a trait. For example,
+/// `Item: Debug` would be the constraint here:
+///
+/// ```ignore
+/// # #![feature(associated_type_bounds)]
+/// # use std::fmt::Debug;
+/// fn foo(_: impl Iterator<Item: Debug>) {}
+/// //                      ^^^^^^^^^^^
+/// ```
+///
+/// Associated type bounds are currently an unstable feature, see
+/// [rust#52662](https://github.com/rust-lang/rust/issues/52662).
+#[repr(C)]
+#[derive(Debug)]
+pub struct ConstraintArg<'ast> {
+    span: SpanId,
+    ident: SymbolId,
+    bounds: FfiSlice<'ast, TyParamBound<'ast>>,
+}
+
+impl<'ast> ConstraintArg<'ast> {
+    /// The name of the associated type, which is constrained. This would be
+    /// `Item` in the example above.
+    pub fn ident(&self) -> &str {
+        with_cx(self, |cx| cx.symbol_str(self.ident))
+    }
+
+    /// The bounds applied to the associated type. This would be `Debug` in the
+    /// example above.
+    pub fn bounds(&self) -> &'ast [TyParamBound<'ast>] {
+        self.bounds.get()
+    }
+
+    /// The [`Span`] of the constraint.
+    pub fn span(&self) -> &Span<'ast> {
+        with_cx(self, |cx| cx.span(self.span))
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> ConstraintArg<'ast> {
+    pub fn new(span: SpanId, ident: SymbolId, bounds: &'ast [TyParamBound<'ast>]) -> Self {
+        Self {
+            span,
+            ident,
+            bounds: bounds.into(),
+        }
+    }
+}
+
 /// A constant expression as an argument for a constant generic.
 ///
 /// ```
@@ -174,6 +273,37 @@ impl<'ast> ConstArg<'ast> {
     }
 }
 
+/// An inferred generic argument, like this:
+///
+/// ```
+/// let _bar = Vec::<_>::new();
+/// //               ^
+/// # let _: Vec<u8> = _bar;
+/// ```
+#[repr(C)]
+#[derive(Debug)]
+pub struct InferArg<'ast> {
+    _data: PhantomData<&'ast ()>,
+    span: SpanId,
+}
+
+impl<'ast> InferArg<'ast> {
+    /// The [`Span`] of the inferred argument.
+    pub fn span(&self) -> &Span<'ast> {
+        with_cx(self, |cx| cx.span(self.span))
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> InferArg<'ast> {
+    pub fn new(span: SpanId) -> Self {
+        Self {
+            _data: PhantomData,
+            span,
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Debug)]
 #[non_exhaustive]
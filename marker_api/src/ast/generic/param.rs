@@ -11,7 +11,7 @@ use crate::{
     context::with_cx,
     ffi::{FfiOption, FfiSlice},
     private::Sealed,
-    span::Span,
+    span::{HasSpan, Span},
 };
 
 use super::TyParamBound;
@@ -254,9 +254,33 @@ pub enum WhereClauseKind<'ast> {
     Ty(&'ast TyClause<'ast>),
 }
 
+impl<'ast> WhereClauseKind<'ast> {
+    /// Returns the [`Span`] of the clause. Bounds declared directly on generic
+    /// parameters, like the `T: Copy` in `fn foo<T: Copy>()`, are also represented
+    /// as clauses. Their span will cover the parameter name and the bounds.
+    pub fn span(&self) -> &Span<'ast> {
+        match self {
+            WhereClauseKind::Lifetime(clause) => clause.span(),
+            WhereClauseKind::Ty(clause) => clause.span(),
+        }
+    }
+}
+
+crate::span::impl_spanned_for!(WhereClauseKind<'ast>);
+
+/// A clause bounding a lifetime, like this:
+///
+/// ```
+/// fn foo<'a, 'b>()
+/// where
+///     'a: 'b,
+/// //  ^^^^^^
+/// {}
+/// ```
 #[repr(C)]
 #[derive(Debug)]
 pub struct LifetimeClause<'ast> {
+    span: SpanId,
     lifetime: Lifetime<'ast>,
     bounds: FfiSlice<'ast, Lifetime<'ast>>,
 }
@@ -271,19 +295,33 @@ impl<'ast> LifetimeClause<'ast> {
     }
 }
 
+crate::span::impl_has_span_via_field!(LifetimeClause<'ast>);
+
 #[cfg(feature = "driver-api")]
 impl<'ast> LifetimeClause<'ast> {
-    pub fn new(lifetime: Lifetime<'ast>, bounds: &'ast [Lifetime<'ast>]) -> Self {
+    pub fn new(span: SpanId, lifetime: Lifetime<'ast>, bounds: &'ast [Lifetime<'ast>]) -> Self {
         Self {
+            span,
             lifetime,
             bounds: bounds.into(),
         }
     }
 }
 
+/// A clause bounding a type, like this:
+///
+/// ```
+/// # use std::fmt::Debug;
+/// fn foo<T>()
+/// where
+///     T: Debug + 'static,
+/// //  ^^^^^^^^^^^^^^^^^^
+/// {}
+/// ```
 #[repr(C)]
 #[derive(Debug)]
 pub struct TyClause<'ast> {
+    span: SpanId,
     params: FfiOption<GenericParams<'ast>>,
     ty: TyKind<'ast>,
     bounds: FfiSlice<'ast, TyParamBound<'ast>>,
@@ -306,10 +344,18 @@ impl<'ast> TyClause<'ast> {
     }
 }
 
+crate::span::impl_has_span_via_field!(TyClause<'ast>);
+
 #[cfg(feature = "driver-api")]
 impl<'ast> TyClause<'ast> {
-    pub fn new(params: Option<GenericParams<'ast>>, ty: TyKind<'ast>, bounds: &'ast [TyParamBound<'ast>]) -> Self {
+    pub fn new(
+        span: SpanId,
+        params: Option<GenericParams<'ast>>,
+        ty: TyKind<'ast>,
+        bounds: &'ast [TyParamBound<'ast>],
+    ) -> Self {
         Self {
+            span,
             params: params.into(),
             ty,
             bounds: bounds.into(),
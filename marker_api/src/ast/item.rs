@@ -35,6 +35,8 @@ mod impl_item;
 pub use impl_item::*;
 mod extern_block_item;
 pub use extern_block_item::*;
+mod extern_ty_item;
+pub use extern_ty_item::*;
 mod unstable_item;
 pub use unstable_item::*;
 
@@ -84,6 +86,7 @@ pub enum ItemKind<'ast> {
     Trait(&'ast TraitItem<'ast>),
     Impl(&'ast ImplItem<'ast>),
     ExternBlock(&'ast ExternBlockItem<'ast>),
+    ExternTy(&'ast ExternTyItem<'ast>),
     Unstable(&'ast UnstableItem<'ast>),
 }
 
@@ -134,6 +137,7 @@ impl<'ast> From<AssocItemKind<'ast>> for ItemKind<'ast> {
 pub enum ExternItemKind<'ast> {
     Static(&'ast StaticItem<'ast>, CtorBlocker),
     Fn(&'ast FnItem<'ast>, CtorBlocker),
+    Ty(&'ast ExternTyItem<'ast>, CtorBlocker),
 }
 
 impl<'ast> ExternItemKind<'ast> {
@@ -153,6 +157,7 @@ impl<'ast> From<ExternItemKind<'ast>> for ItemKind<'ast> {
         match value {
             ExternItemKind::Static(item, ..) => ItemKind::Static(item),
             ExternItemKind::Fn(item, ..) => ItemKind::Fn(item),
+            ExternItemKind::Ty(item, ..) => ItemKind::ExternTy(item),
         }
     }
 }
@@ -163,7 +168,7 @@ macro_rules! impl_item_type_fn {
     (ItemKind: $method:ident () -> $return_ty:ty) => {
         impl_item_type_fn!((ItemKind) $method() -> $return_ty,
             Mod, ExternCrate, Use, Static, Const, Fn, TyAlias, Struct, Enum,
-            Union, Trait, Impl, ExternBlock, Unstable
+            Union, Trait, Impl, ExternBlock, ExternTy, Unstable
         );
     };
     (AssocItemKind: $method:ident () -> $return_ty:ty) => {
@@ -173,7 +178,7 @@ macro_rules! impl_item_type_fn {
     };
     (ExternItemKind: $method:ident () -> $return_ty:ty) => {
         impl_item_type_fn!((ExternItemKind) $method() -> $return_ty,
-            Static, Fn
+            Static, Fn, Ty
         );
     };
     (($self:ident) $method:ident () -> $return_ty:ty $(, $item:ident)+) => {
@@ -340,6 +345,6 @@ mod test {
         assert_size_of::<TraitItem<'_>>(&expect!["136"]);
         assert_size_of::<ImplItem<'_>>(&expect!["168"]);
         assert_size_of::<ExternBlockItem<'_>>(&expect!["88"]);
-        assert_size_of::<UnstableItem<'_>>(&expect!["72"]);
+        assert_size_of::<UnstableItem<'_>>(&expect!["88"]);
     }
 }
@@ -0,0 +1,31 @@
+use super::CommonItemData;
+
+/// A foreign type declared inside an extern block, like:
+///
+/// ```ignore
+/// # #![feature(extern_types)]
+/// extern "C" {
+///     type Handle;
+/// //  ^^^^^^^^^^^^
+/// }
+/// ```
+///
+/// The size and alignment of foreign types are unknown. They can therefore only
+/// be used behind pointers. Foreign types are currently an unstable feature, see
+/// [rust#43467](https://github.com/rust-lang/rust/issues/43467).
+///
+/// * See <https://doc.rust-lang.org/nightly/unstable-book/language-features/extern-types.html>
+#[repr(C)]
+#[derive(Debug)]
+pub struct ExternTyItem<'ast> {
+    data: CommonItemData<'ast>,
+}
+
+super::impl_item_data!(ExternTyItem, ExternTy);
+
+#[cfg(feature = "driver-api")]
+impl<'ast> ExternTyItem<'ast> {
+    pub fn new(data: CommonItemData<'ast>) -> Self {
+        Self { data }
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{common::SymbolId, context::with_cx, ffi::FfiOption};
+use crate::{
+    common::SymbolId,
+    context::with_cx,
+    ffi::{FfiOption, FfiStr},
+};
 
 use super::CommonItemData;
 
@@ -8,6 +12,7 @@ use super::CommonItemData;
 pub struct UnstableItem<'ast> {
     data: CommonItemData<'ast>,
     feature: FfiOption<SymbolId>,
+    reason: FfiStr<'ast>,
 }
 
 super::impl_item_data!(UnstableItem, Unstable);
@@ -18,14 +23,22 @@ impl<'ast> UnstableItem<'ast> {
             .get()
             .map(|feature| with_cx(self, |cx| cx.symbol_str(*feature)))
     }
+
+    /// A short description of the item, which is represented by this
+    /// placeholder, like "trait alias". The text is intended for debugging
+    /// and might change between versions.
+    pub fn reason(&self) -> &str {
+        self.reason.get()
+    }
 }
 
 #[cfg(feature = "driver-api")]
 impl<'ast> UnstableItem<'ast> {
-    pub fn new(data: CommonItemData<'ast>, feature: Option<SymbolId>) -> Self {
+    pub fn new(data: CommonItemData<'ast>, feature: Option<SymbolId>, reason: &'ast str) -> Self {
         Self {
             data,
             feature: feature.into(),
+            reason: reason.into(),
         }
     }
 }
@@ -1,16 +1,32 @@
+use crate::ffi::FfiStr;
+
 use super::CommonPatData;
 
+/// A placeholder for patterns, which are not represented by the API yet.
 #[repr(C)]
 #[derive(Debug)]
 pub struct UnstablePat<'ast> {
     data: CommonPatData<'ast>,
+    reason: FfiStr<'ast>,
 }
 
 super::impl_pat_data!(UnstablePat<'ast>, Unstable);
 
+impl<'ast> UnstablePat<'ast> {
+    /// A short description of the pattern, which is represented by this
+    /// placeholder, like "box pattern". The text is intended for debugging
+    /// and might change between versions.
+    pub fn reason(&self) -> &str {
+        self.reason.get()
+    }
+}
+
 #[cfg(feature = "driver-api")]
 impl<'ast> UnstablePat<'ast> {
-    pub fn new(data: CommonPatData<'ast>) -> Self {
-        Self { data }
+    pub fn new(data: CommonPatData<'ast>, reason: &'ast str) -> Self {
+        Self {
+            data,
+            reason: reason.into(),
+        }
     }
 }
@@ -4,6 +4,8 @@
 mod id;
 pub use id::*;
 
+use crate::ffi::{FfiSlice, FfiStr};
+
 #[non_exhaustive]
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum Edition {
@@ -92,6 +94,50 @@ impl Syncness {
     }
 }
 
+/// The kind of a coroutine. Coroutines are resumable functions, which are either
+/// written explicitly or created from `async` and `gen` desugarings.
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CoroutineKind {
+    /// An explicit coroutine, like `|| { yield 1; }`
+    Coroutine,
+    /// A coroutine created by an `async` block, closure or function. These
+    /// implement the [`Future`](std::future::Future) trait.
+    Async,
+    /// A coroutine created by a `gen` block. These implement the [`Iterator`] trait.
+    Gen,
+    /// A coroutine created by an `async gen` block.
+    AsyncGen,
+}
+
+impl CoroutineKind {
+    #[must_use]
+    pub fn is_async(&self) -> bool {
+        matches!(self, Self::Async | Self::AsyncGen)
+    }
+}
+
+/// Indicates if a coroutine can be moved, after it has been resumed.
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Movability {
+    /// The coroutine can be moved, as it holds no references into itself
+    /// across suspension points.
+    Movable,
+    /// The coroutine is declared with the `static` keyword and can hold
+    /// references into itself across suspension points. This makes it immovable.
+    Static,
+}
+
+impl Movability {
+    #[must_use]
+    pub fn is_static(&self) -> bool {
+        matches!(self, Self::Static)
+    }
+}
+
 #[repr(C)]
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -208,11 +254,14 @@ pub enum Level {
     Forbid,
 }
 
-/// FIXME(xFrednet): These settings should be working now, but are still limited
-/// due to the limited [`Span`](crate::span::Span) implementation. Ideally, I would
-/// also like more options, like a `Local` variant that only lints in local marcos.
-/// For libraries it might also be cool to have a `Crate` variant, that only lints
-/// in user code and code from macros from the specified crate.
+/// This value defines, if a lint should be emitted in code, that was created by
+/// a macro expansion. Code written by the user, like the arguments of a macro
+/// call, is not affected by this setting.
+///
+/// A span can originate from several nested expansions. The `Local` and `Crates`
+/// variants only report a lint, if every macro in the expansion chain is accepted.
+/// The macros of a span can be inspected via the [`MacroInfo`](crate::span::MacroInfo)
+/// of [`ExpnInfo`](crate::span::ExpnInfo).
 ///
 /// See: rust-marker/marker#149
 #[repr(C)]
@@ -221,6 +270,36 @@ pub enum Level {
 pub enum MacroReport {
     /// No reporting in local or external macros.
     No,
+    /// Only report in macros, which are defined in the linted workspace. This
+    /// includes macros from the currently linted crate and other crates of the
+    /// same workspace. Macros from external dependencies and the standard library
+    /// are ignored.
+    Local,
+    /// Report in macros from the linted workspace, like [`Local`](Self::Local),
+    /// and in macros from the crates with the given names. Crate names use
+    /// underscores instead of dashes, like `serde_json`.
+    ///
+    /// Instances should be created with [`MacroReport::crates`], which can
+    /// be used in constant expressions, like [`declare_lint!`](crate::declare_lint):
+    ///
+    /// ```
+    /// # use marker_api::common::MacroReport;
+    /// # use marker_api::ffi::FfiStr;
+    /// // Report in local macros and macros from `alloc`, like `vec![]`
+    /// const REPORT: MacroReport = MacroReport::crates(&[FfiStr::new("alloc")]);
+    /// ```
+    Crates(FfiSlice<'static, FfiStr<'static>>),
     /// Report in local and external macros.
     All,
 }
+
+impl MacroReport {
+    /// Creates a [`MacroReport::Crates`] value for the given crate names.
+    ///
+    /// The names are passed as [`FfiStr`]s, since the value is read by the
+    /// driver, which might be compiled by a different version of rustc.
+    /// A `const fn` can't convert a `&[&str]` slice, without allocating.
+    pub const fn crates(crates: &'static [FfiStr<'static>]) -> Self {
+        Self::Crates(FfiSlice::new(crates))
+    }
+}
@@ -8,11 +8,11 @@
 use std::{cell::RefCell, mem::transmute};
 
 use crate::{
-    common::{ExpnId, ExprId, ItemId, Level, MacroReport, SpanId, SymbolId, TyDefId},
+    common::{ExpnId, ExprId, ItemId, Level, MacroId, MacroReport, NodeId, SpanId, SymbolId, TyDefId},
     diagnostic::{Diagnostic, DiagnosticBuilder, EmissionNode},
     ffi,
-    sem::TyKind,
-    span::{ExpnInfo, FileInfo, FilePos, Span, SpanPos, SpanSource},
+    sem::{PredicateKind, TyKind},
+    span::{Comment, ExpnInfo, FileInfo, FilePos, MacroInfo, SourceFile, Span, SpanPos, SpanSource, Token},
     Lint,
 };
 
@@ -215,7 +215,7 @@ impl<'ast> MarkerContext<'ast> {
     ) -> DiagnosticBuilder<'ast> {
         let id = node.node_id();
         let span = node.span();
-        if matches!(lint.report_in_macro, MacroReport::No) && span.is_from_expansion() {
+        if span.is_from_expansion() && !is_reported_in_expansion(lint.report_in_macro, span) {
             return DiagnosticBuilder::dummy();
         }
         if self.ast().lint_level_at(lint, &node) == Level::Allow {
@@ -253,6 +253,193 @@ impl<'ast> MarkerContext<'ast> {
     pub fn resolve_ty_ids(&self, path: &str) -> &[TyDefId] {
         (self.callbacks.resolve_ty_ids)(self.callbacks.data, path.into()).get()
     }
+
+    /// Returns the [`ItemId`]s of all `impl` blocks, which implement the trait
+    /// identified by the given [`TyDefId`].
+    ///
+    /// This includes impl blocks from the current crate and all dependencies,
+    /// that are known to the driver. The returned slice will be empty, if the
+    /// given id doesn't belong to a trait.
+    ///
+    /// The returned ids can be used to request the impl blocks via
+    /// [`AstMap::item`]. Note that only impl blocks from the currently linted
+    /// crate are available as AST nodes. Checkout the documentation of [`AstMap`]
+    /// for more information.
+    ///
+    /// ```ignore
+    /// // Check if the current crate implements a specific trait
+    /// let ids = cx.resolve_ty_ids("std::cmp::PartialEq");
+    /// let has_impls = ids.iter().any(|id| !cx.impls_of_trait(*id).is_empty());
+    /// ```
+    pub fn impls_of_trait(&self, trait_id: TyDefId) -> &[ItemId] {
+        (self.callbacks.impls_of_trait)(self.callbacks.data, trait_id).get()
+    }
+
+    /// Returns the [`ItemId`]s of all inherent `impl` blocks of the type
+    /// identified by the given [`TyDefId`]. Inherent impl blocks are impl blocks
+    /// without a trait, like this:
+    ///
+    /// ```
+    /// # struct Mouse;
+    /// impl Mouse {
+    ///     // ...
+    /// }
+    /// ```
+    ///
+    /// The returned slice will be empty, if the type has no inherent impl blocks
+    /// or if the id doesn't belong to a type, which can have inherent impls.
+    pub fn inherent_impls_of(&self, ty_id: TyDefId) -> &[ItemId] {
+        (self.callbacks.inherent_impls_of)(self.callbacks.data, ty_id).get()
+    }
+
+    /// Returns the [`ItemId`] of the `impl` block, that implements the trait
+    /// identified by `trait_id` for the given semantic type.
+    ///
+    /// The impl block is selected, by checking that its self type matches the given
+    /// type and that its `where` clauses may hold. Impl blocks for the concrete type
+    /// are preferred over blanket impls, like `impl<T: Display> ToString for T`.
+    /// Generic parameters in the given type are treated as types without any
+    /// bounds.
+    ///
+    /// ```ignore
+    /// // Check if the type of an expression implements `Default`
+    /// let ty = expr.ty();
+    /// let is_default = cx
+    ///     .resolve_ty_ids("std::default::Default")
+    ///     .iter()
+    ///     .any(|id| cx.trait_impl_for(ty, *id).is_some());
+    /// ```
+    pub fn trait_impl_for(&self, ty: TyKind<'ast>, trait_id: TyDefId) -> Option<ItemId> {
+        (self.callbacks.trait_impl_for)(self.callbacks.data, ty, trait_id).copy()
+    }
+
+    /// Returns the semantic predicates of the item identified by the given
+    /// [`ItemId`]. This includes bounds declared directly on generic parameters,
+    /// clauses from `where` statements and implicit bounds, like `Sized`, added
+    /// by the compiler:
+    ///
+    /// ```
+    /// # use std::fmt::Debug;
+    /// fn foo<'a, T: Debug + 'a>(_: &'a T)
+    /// where
+    ///     T: IntoIterator<Item = u8>,
+    /// {}
+    /// // Predicates:
+    /// // * `T: Sized`
+    /// // * `T: Debug`
+    /// // * `T: 'a`
+    /// // * `T: IntoIterator`
+    /// // * `<T as IntoIterator>::Item == u8`
+    /// ```
+    ///
+    /// Predicates inherited from a parent item, like the `impl` block of a method,
+    /// are not included. The returned slice will be empty, if the item doesn't
+    /// have predicates or if the id doesn't belong to an item, which can have
+    /// predicates.
+    pub fn predicates_of(&self, item: ItemId) -> &[PredicateKind<'ast>] {
+        (self.callbacks.predicates_of)(self.callbacks.data, item).get()
+    }
+
+    /// Returns the [`MacroInfo`] of the macro identified by the given [`MacroId`].
+    /// This includes the name, path, kind and defining crate of the macro.
+    ///
+    /// [`MacroId`]s can be retrieved from the [`ExpnInfo`] of a [`Span`], which
+    /// originates from a macro expansion:
+    ///
+    /// ```ignore
+    /// // Check if an expression was created by `vec![]`
+    /// if let SpanSource::Macro(expn) = expr.span().source() {
+    ///     let info = cx.macro_info(expn.macro_id());
+    ///     if info.name() == "vec" && info.crate_name() == "alloc" {
+    ///         // ...
+    ///     }
+    /// }
+    /// ```
+    pub fn macro_info(&self, id: MacroId) -> &'ast MacroInfo<'ast> {
+        (self.callbacks.macro_info)(self.callbacks.data, id)
+    }
+
+    /// Returns all comments, which are located inside the given [`Span`]. This
+    /// includes doc comments. The comments are sorted by their position in the
+    /// source file.
+    ///
+    /// ```ignore
+    /// fn example() {
+    ///     // This comment is inside the span of `example`
+    /// }
+    /// ```
+    ///
+    /// Comments are only available for spans, which point into a source file.
+    /// An empty slice is returned for spans from macro expansions.
+    pub fn comments_in(&self, span: &Span<'ast>) -> &'ast [Comment<'ast>] {
+        (self.callbacks.comments_in)(self.callbacks.data, span).get()
+    }
+
+    /// Returns the comments directly preceding the given [`Span`]. Comments
+    /// are only included, if they are separated by whitespace from the span
+    /// and from each other. Trailing comments of previous lines are not
+    /// included. The comments are sorted by their position in the source file.
+    ///
+    /// ```ignore
+    /// let a = 1; // This trailing comment belongs to `a`
+    /// // SAFETY: This comment is returned for the span of the following statement
+    /// /* And so is this one */
+    /// let b = unsafe { .. };
+    /// ```
+    ///
+    /// Note that the comment in this example precedes the `let` statement, and
+    /// not the `unsafe` block. Comments are only available for spans, which point
+    /// into a source file. An empty slice is returned for spans from macro expansions.
+    pub fn comments_before(&self, span: &Span<'ast>) -> &'ast [Comment<'ast>] {
+        (self.callbacks.comments_before)(self.callbacks.data, span).get()
+    }
+
+    /// Returns the doc comments of the given node. This includes outer doc
+    /// comments, like `/// Docs`, and inner doc comments, like `//! Docs`.
+    /// Documentation provided via `#[doc = "..."]` attributes is not included.
+    ///
+    /// The returned slice is empty, if the node doesn't have doc comments or
+    /// can't be documented.
+    pub fn doc_comments(&self, node: impl Into<NodeId>) -> &'ast [Comment<'ast>] {
+        (self.callbacks.doc_comments)(self.callbacks.data, node.into()).get()
+    }
+
+    /// Returns all source files of the linted crate, which define a module.
+    /// The first file is always the crate root, like `src/lib.rs` or `src/main.rs`.
+    /// Each file is also passed to [`LintPass::check_file`](crate::LintPass::check_file).
+    ///
+    /// Files, which are only included via macros like `include!()` or generated
+    /// by build scripts outside of the crate, are not listed.
+    pub fn source_files(&self) -> &'ast [SourceFile<'ast>] {
+        (self.callbacks.source_files)(self.callbacks.data).get()
+    }
+}
+
+/// Checks if a lint with the given [`MacroReport`] value should be emitted at a
+/// [`Span`], which originates from a macro expansion.
+fn is_reported_in_expansion(report: MacroReport, span: &Span<'_>) -> bool {
+    let accepts = |info: &MacroInfo<'_>| match report {
+        MacroReport::No => false,
+        MacroReport::Local => info.is_from_workspace(),
+        MacroReport::Crates(crates) => {
+            info.is_from_workspace() || crates.get().iter().any(|krate| krate.get() == info.crate_name())
+        },
+        MacroReport::All => true,
+    };
+
+    match span.source() {
+        SpanSource::Macro(expn) => {
+            let mut current = Some(expn);
+            while let Some(expn) = current {
+                if !accepts(expn.macro_info()) {
+                    return false;
+                }
+                current = expn.parent();
+            }
+            true
+        },
+        _ => matches!(report, MacroReport::All),
+    }
 }
 
 impl<'ast> MarkerContext<'ast> {
@@ -275,6 +462,21 @@ impl<'ast> MarkerContext<'ast> {
     pub(crate) fn span_source(&self, span: &Span<'_>) -> SpanSource<'ast> {
         (self.callbacks.span_source)(self.callbacks.data, span)
     }
+    pub(crate) fn span_tokens(&self, span: &Span<'ast>) -> &'ast [Token<'ast>] {
+        (self.callbacks.span_tokens)(self.callbacks.data, span).get()
+    }
+    pub(crate) fn span_to(&self, span: &Span<'ast>, end: &Span<'ast>) -> Span<'ast> {
+        (self.callbacks.span_to)(self.callbacks.data, span, end)
+    }
+    pub(crate) fn span_between(&self, span: &Span<'ast>, end: &Span<'ast>) -> Span<'ast> {
+        (self.callbacks.span_between)(self.callbacks.data, span, end)
+    }
+    pub(crate) fn span_source_callsite(&self, span: &Span<'ast>) -> Span<'ast> {
+        (self.callbacks.span_source_callsite)(self.callbacks.data, span)
+    }
+    pub(crate) fn span_expand_to_lines(&self, span: &Span<'ast>) -> Span<'ast> {
+        (self.callbacks.span_expand_to_lines)(self.callbacks.data, span)
+    }
     pub(crate) fn span_pos_to_file_loc(&self, file: &FileInfo<'ast>, pos: SpanPos) -> Option<FilePos<'ast>> {
         (self.callbacks.span_pos_to_file_loc)(self.callbacks.data, file, pos).into()
     }
@@ -319,12 +521,26 @@ struct MarkerContextCallbacks<'ast> {
 
     // Public utility
     pub resolve_ty_ids: extern "C" fn(&'ast MarkerContextData, path: ffi::FfiStr<'_>) -> ffi::FfiSlice<'ast, TyDefId>,
+    pub impls_of_trait: extern "C" fn(&'ast MarkerContextData, TyDefId) -> ffi::FfiSlice<'ast, ItemId>,
+    pub inherent_impls_of: extern "C" fn(&'ast MarkerContextData, TyDefId) -> ffi::FfiSlice<'ast, ItemId>,
+    pub trait_impl_for: extern "C" fn(&'ast MarkerContextData, TyKind<'ast>, TyDefId) -> ffi::FfiOption<ItemId>,
+    pub predicates_of: extern "C" fn(&'ast MarkerContextData, ItemId) -> ffi::FfiSlice<'ast, PredicateKind<'ast>>,
+    pub macro_info: extern "C" fn(&'ast MarkerContextData, MacroId) -> &'ast MacroInfo<'ast>,
+    pub comments_in: extern "C" fn(&'ast MarkerContextData, &Span<'ast>) -> ffi::FfiSlice<'ast, Comment<'ast>>,
+    pub comments_before: extern "C" fn(&'ast MarkerContextData, &Span<'ast>) -> ffi::FfiSlice<'ast, Comment<'ast>>,
+    pub doc_comments: extern "C" fn(&'ast MarkerContextData, NodeId) -> ffi::FfiSlice<'ast, Comment<'ast>>,
+    pub source_files: extern "C" fn(&'ast MarkerContextData) -> ffi::FfiSlice<'ast, SourceFile<'ast>>,
 
     // Internal utility
     pub expr_ty: extern "C" fn(&'ast MarkerContextData, ExprId) -> TyKind<'ast>,
     pub span: extern "C" fn(&'ast MarkerContextData, SpanId) -> &'ast Span<'ast>,
     pub span_snippet: extern "C" fn(&'ast MarkerContextData, &Span<'ast>) -> ffi::FfiOption<ffi::FfiStr<'ast>>,
     pub span_source: extern "C" fn(&'ast MarkerContextData, &Span<'_>) -> SpanSource<'ast>,
+    pub span_tokens: extern "C" fn(&'ast MarkerContextData, &Span<'ast>) -> ffi::FfiSlice<'ast, Token<'ast>>,
+    pub span_to: extern "C" fn(&'ast MarkerContextData, &Span<'ast>, &Span<'ast>) -> Span<'ast>,
+    pub span_between: extern "C" fn(&'ast MarkerContextData, &Span<'ast>, &Span<'ast>) -> Span<'ast>,
+    pub span_source_callsite: extern "C" fn(&'ast MarkerContextData, &Span<'ast>) -> Span<'ast>,
+    pub span_expand_to_lines: extern "C" fn(&'ast MarkerContextData, &Span<'ast>) -> Span<'ast>,
     pub span_pos_to_file_loc:
         extern "C" fn(&'ast MarkerContextData, &FileInfo<'ast>, SpanPos) -> ffi::FfiOption<FilePos<'ast>>,
     pub span_expn_info: extern "C" fn(&'ast MarkerContextData, ExpnId) -> ffi::FfiOption<&'ast ExpnInfo<'ast>>,
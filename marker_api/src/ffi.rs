@@ -21,6 +21,10 @@ pub struct FfiStr<'a> {
     len: usize,
 }
 
+// The string data is immutable, like the data of a `&str`
+unsafe impl<'a> Send for FfiStr<'a> {}
+unsafe impl<'a> Sync for FfiStr<'a> {}
+
 impl<'a> PartialEq for FfiStr<'a> {
     fn eq(&self, other: &Self) -> bool {
         self.get().eq(other.get())
@@ -29,11 +33,7 @@ impl<'a> PartialEq for FfiStr<'a> {
 
 impl<'a> From<&'a str> for FfiStr<'a> {
     fn from(source: &'a str) -> Self {
-        Self {
-            _lifetime: PhantomData,
-            data: source.as_ptr(),
-            len: source.len(),
-        }
+        Self::new(source)
     }
 }
 
@@ -44,6 +44,16 @@ impl<'a> From<&'a String> for FfiStr<'a> {
 }
 
 impl<'a> FfiStr<'a> {
+    /// Creates a new string. In contrast to the [`From`] implementation, this
+    /// can be used in constant expressions.
+    pub const fn new(source: &'a str) -> Self {
+        Self {
+            _lifetime: PhantomData,
+            data: source.as_ptr(),
+            len: source.len(),
+        }
+    }
+
     pub fn get(&self) -> &'a str {
         unsafe {
             let data = slice::from_raw_parts(self.data, self.len);
@@ -138,6 +148,10 @@ pub struct FfiSlice<'a, T> {
     len: usize,
 }
 
+// SAFETY: The slice behaves like a `&'a [T]`, which is `Send` and `Sync` if `T` is `Sync`
+unsafe impl<'a, T: Sync> Send for FfiSlice<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for FfiSlice<'a, T> {}
+
 impl<'a, T: Eq> Eq for FfiSlice<'a, T> {}
 
 impl<'a, T: PartialEq> PartialEq for FfiSlice<'a, T> {
@@ -153,6 +167,16 @@ impl<'a, T: std::hash::Hash> std::hash::Hash for FfiSlice<'a, T> {
 }
 
 impl<'a, T> FfiSlice<'a, T> {
+    /// Creates a new slice. In contrast to the [`From`] implementation, this
+    /// can be used in constant expressions, like the definition of statics.
+    pub const fn new(src_data: &'a [T]) -> Self {
+        Self {
+            _lifetime: PhantomData,
+            data: src_data.as_ptr(),
+            len: src_data.len(),
+        }
+    }
+
     pub fn get(&self) -> &'a [T] {
         self.into()
     }
@@ -168,11 +192,7 @@ impl<'a, T> FfiSlice<'a, T> {
 
 impl<'a, T> From<&'a [T]> for FfiSlice<'a, T> {
     fn from(src_data: &'a [T]) -> Self {
-        Self {
-            _lifetime: PhantomData,
-            data: src_data.as_ptr(),
-            len: src_data.len(),
-        }
+        Self::new(src_data)
     }
 }
 
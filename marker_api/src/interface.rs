@@ -1,7 +1,11 @@
 //! A module responsible for generating and exposing an interface from lint crates.
 //! [`export_lint_pass`](crate::export_lint_pass) is the main macro, from this module.
 
-use crate::{context::MarkerContext, ffi::FfiSlice, lint::Lint};
+use crate::{
+    context::MarkerContext,
+    ffi::FfiSlice,
+    lint::{Lint, LintGroup},
+};
 
 /// **!Unstable!**
 /// This struct is used to connect lint crates to drivers.
@@ -13,6 +17,7 @@ pub struct LintCrateBindings {
     // lint pass functions
     pub info: for<'ast> extern "C" fn() -> LintPassInfo,
     pub check_crate: for<'ast> extern "C" fn(&'ast MarkerContext<'ast>, &'ast crate::ast::Crate<'ast>),
+    pub check_file: for<'ast> extern "C" fn(&'ast MarkerContext<'ast>, &'ast crate::span::SourceFile<'ast>),
     pub check_item: for<'ast> extern "C" fn(&'ast MarkerContext<'ast>, crate::ast::ItemKind<'ast>),
     pub check_field: for<'ast> extern "C" fn(&'ast MarkerContext<'ast>, &'ast crate::ast::ItemField<'ast>),
     pub check_variant: for<'ast> extern "C" fn(&'ast MarkerContext<'ast>, &'ast crate::ast::EnumVariant<'ast>),
@@ -85,6 +90,12 @@ macro_rules! export_lint_pass {
                 ) {
                     super::__MARKER_STATE.with(|state| state.borrow_mut().check_crate(cx, krate));
                 }
+                extern "C" fn check_file<'ast>(
+                    cx: &'ast $crate::MarkerContext<'ast>,
+                    file: &'ast $crate::span::SourceFile<'ast>,
+                ) {
+                    super::__MARKER_STATE.with(|state| state.borrow_mut().check_file(cx, file));
+                }
                 extern "C" fn check_item<'ast>(
                     cx: &'ast $crate::MarkerContext<'ast>,
                     item: $crate::ast::ItemKind<'ast>,
@@ -126,6 +137,7 @@ macro_rules! export_lint_pass {
                     set_ast_context,
                     info,
                     check_crate,
+                    check_file,
                     check_item,
                     check_field,
                     check_variant,
@@ -141,6 +153,7 @@ macro_rules! export_lint_pass {
 #[derive(Debug)]
 pub struct LintPassInfoBuilder {
     lints: &'static [&'static Lint],
+    groups: &'static [&'static LintGroup],
 }
 
 impl LintPassInfoBuilder {
@@ -154,13 +167,26 @@ impl LintPassInfoBuilder {
             // is called. Ideally, it would be cool to just store the `Box` directly but
             // that is sadly not possible due to ABI constraints
             lints: Box::leak(lints),
+            groups: &[],
         }
     }
 
+    /// This method sets the lint groups, which are declared by this crate.
+    ///
+    /// Lint groups allow users to set the level of several lints at once. The
+    /// lints of every group have to be included in the `lints` passed to
+    /// [`LintPassInfoBuilder::new`].
+    #[must_use]
+    pub fn groups(mut self, groups: Box<[&'static LintGroup]>) -> Self {
+        self.groups = Box::leak(groups);
+        self
+    }
+
     /// This method builds the [`LintPassInfo`], ready for consumption.
     pub fn build(self) -> LintPassInfo {
         LintPassInfo {
             lints: self.lints.into(),
+            groups: self.groups.into(),
         }
     }
 }
@@ -176,6 +202,7 @@ impl LintPassInfoBuilder {
 #[non_exhaustive]
 pub struct LintPassInfo {
     lints: FfiSlice<'static, &'static Lint>,
+    groups: FfiSlice<'static, &'static LintGroup>,
 }
 
 #[cfg(feature = "driver-api")]
@@ -183,4 +210,8 @@ impl LintPassInfo {
     pub fn lints(&self) -> &[&'static Lint] {
         self.lints.get()
     }
+
+    pub fn groups(&self) -> &[&'static LintGroup] {
+        self.groups.get()
+    }
 }
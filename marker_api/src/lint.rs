@@ -1,4 +1,5 @@
 use crate::common::{Level, MacroReport};
+use crate::ffi::FfiSlice;
 
 /// This struct defines a lint.
 ///
@@ -61,7 +62,7 @@ pub struct Lint {
     /// See [`MacroReport`] for the possible levels.
     pub report_in_macro: MacroReport,
 
-    /// Fully qualiefied name of the static variable that defines the lint.
+    /// Fully qualified name of the static variable that defines the lint.
     /// It includes the module and the name of the variable.
     pub fqn: &'static str,
 
@@ -144,3 +145,79 @@ macro_rules! declare_lint {
         };
     };
 }
+
+/// This struct defines a group of lints.
+///
+/// Lint groups allow users to set the level of several lints at once, like
+/// `#![warn(marker::my_lints::pedantic)]`. They should be declared using the
+/// [`declare_lint_group`](crate::declare_lint_group) macro and can be registered
+/// with the [`LintPassInfoBuilder`](crate::LintPassInfoBuilder).
+///
+/// The fields of this struct are public, to allow the instantiation in constant
+/// context. Marker reserves the right to add new fields, as long the group can still
+/// be constructed using the [`declare_lint_group`](crate::declare_lint_group) macro.
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct LintGroup {
+    /// The string identifier of this group.
+    ///
+    /// The identifier follows the same structure as the [`Lint::name`]. It's
+    /// converted to lowercase when the group is registered.
+    pub name: &'static str,
+
+    /// Description of the group and the lints it contains.
+    pub explanation: &'static str,
+
+    /// The lints, which belong to this group. All of them have to be registered
+    /// by the lint crate as well.
+    pub lints: FfiSlice<'static, &'static Lint>,
+
+    /// Fully qualified name of the static variable that defines the group.
+    /// It includes the module and the name of the variable.
+    pub fqn: &'static str,
+
+    /// This struct should always be instantiated using the
+    /// [`declare_lint_group`](crate::declare_lint_group) macro. This value is simply here, to
+    /// force any construction to acknowledge the instability of manual construction.
+    pub _unstable_i_accept_the_risk_of_instability: (),
+}
+
+/// This macro creates a new lint group. The group is named like a lint, using
+/// the name of the lint crate as an infix. The doc comment will be available in
+/// the crate documentation and any documentation generated by Marker.
+///
+/// ```
+/// marker_api::declare_lint!{
+///     /// # What it does
+///     /// Here you can describe what your lint does.
+///     ITEM_WITH_TEST_NAME,
+///     Warn,
+/// }
+///
+/// marker_api::declare_lint_group!{
+///     /// Lints, which are very picky about the code.
+///     PEDANTIC,
+///     [ITEM_WITH_TEST_NAME],
+/// }
+/// ```
+///
+/// The lints of the group have to be registered with the
+/// [`LintPassInfoBuilder`](crate::LintPassInfoBuilder) as well.
+#[macro_export]
+macro_rules! declare_lint_group {
+    (
+        $(#[doc = $doc:literal])+
+        $NAME: ident,
+        [$($LINT: path),* $(,)?] $(,)?
+    ) => {
+        $(#[doc = $doc])+
+        pub static $NAME: &$crate::LintGroup = &$crate::LintGroup {
+            // See `declare_lint!` for details about the `CARGO_CRATE_NAME` variable
+            name: concat!("marker::", std::env!("CARGO_CRATE_NAME"), "::", stringify!($NAME)),
+            explanation: concat!($($doc, '\n',)*),
+            lints: $crate::ffi::FfiSlice::new(&[$($LINT),*]),
+            fqn: concat!(module_path!(), "::", stringify!($NAME)),
+            _unstable_i_accept_the_risk_of_instability: (),
+        };
+    };
+}
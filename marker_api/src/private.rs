@@ -18,11 +18,17 @@ impl Sealed for ast::FnTyParameter<'_> {}
 impl Sealed for ast::FnParam<'_> {}
 impl Sealed for ast::ItemField<'_> {}
 impl Sealed for ast::ItemKind<'_> {}
+impl Sealed for ast::LifetimeClause<'_> {}
 impl Sealed for ast::LifetimeParam<'_> {}
 impl Sealed for ast::LitExprKind<'_> {}
 impl Sealed for ast::StmtKind<'_> {}
 impl Sealed for ast::StructFieldPat<'_> {}
+impl Sealed for ast::TyClause<'_> {}
 impl Sealed for ast::TyKind<'_> {}
 impl Sealed for ast::TyParam<'_> {}
+impl Sealed for ast::WhereClauseKind<'_> {}
 impl Sealed for Span<'_> {}
 impl Sealed for Ident<'_> {}
+impl Sealed for crate::span::Comment<'_> {}
+impl Sealed for crate::span::Token<'_> {}
+impl Sealed for crate::span::SourceFile<'_> {}
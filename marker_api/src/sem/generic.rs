@@ -1,9 +1,13 @@
 mod args;
 mod param;
+mod predicate;
 pub use args::*;
 pub use param::*;
+pub use predicate::*;
 
-use crate::{ffi::FfiSlice, sem::ty::TyKind};
+use std::marker::PhantomData;
+
+use crate::{common::GenericId, ffi::FfiSlice, sem::ty::TyKind};
 
 /// The semantic representation of generic arguments for an item or path.
 ///
@@ -80,4 +84,64 @@ pub enum GenericArgKind<'ast> {
     /// //            ^
     /// ```
     Const(&'ast ConstArg<'ast>),
+    /// A constant binding as a generic argument, like this:
+    ///
+    /// ```ignore
+    /// # #![feature(associated_const_equality)]
+    /// # trait Matrix { const N: usize; }
+    /// fn foo(_: &dyn Matrix<N = 3>) {}
+    /// //                    ^^^^^
+    /// ```
+    ConstBinding(&'ast ConstBindingArg<'ast>),
+}
+
+/// The semantic representation of a lifetime, like `'a` or `'static`.
+///
+/// Semantic lifetimes are currently only used by [`PredicateKind`]s. Most other
+/// semantic representations, like [`GenericArgs`], don't include lifetimes.
+#[repr(C)]
+#[derive(Debug)]
+pub struct Lifetime<'ast> {
+    _data: PhantomData<&'ast ()>,
+    kind: LifetimeKind,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+#[allow(clippy::exhaustive_enums)]
+#[cfg_attr(feature = "driver-api", visibility::make(pub))]
+pub(crate) enum LifetimeKind {
+    /// The magic `'static` lifetime
+    Static,
+    /// A lifetime declared by a generic parameter like `'a`
+    Param(GenericId),
+    /// Any other lifetime, like higher ranked lifetimes from a `for<'a>` binder.
+    /// These are not represented by the API yet.
+    Other,
+}
+
+impl<'ast> Lifetime<'ast> {
+    /// Returns `true`, if this is the `'static` lifetime.
+    pub fn is_static(&self) -> bool {
+        matches!(self.kind, LifetimeKind::Static)
+    }
+
+    /// Returns the [`GenericId`] of the generic parameter that declared this
+    /// lifetime, if it was declared by one.
+    pub fn param_id(&self) -> Option<GenericId> {
+        match self.kind {
+            LifetimeKind::Param(id) => Some(id),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> Lifetime<'ast> {
+    pub fn new(kind: LifetimeKind) -> Self {
+        Self {
+            _data: PhantomData,
+            kind,
+        }
+    }
 }
@@ -43,6 +43,41 @@ impl<'ast> BindingArg<'ast> {
     }
 }
 
+/// A semantic generic binding, which sets an associated constant to a specific
+/// value. For example, `N = 3` would be the constant binding here:
+///
+/// ```ignore
+/// # #![feature(associated_const_equality)]
+/// # trait Matrix { const N: usize; }
+/// fn foo(_: &dyn Matrix<N = 3>) {}
+/// //                    ^^^^^
+/// ```
+#[repr(C)]
+#[derive(Debug)]
+pub struct ConstBindingArg<'ast> {
+    binding_target: ItemId,
+    value: ConstValue<'ast>,
+}
+
+impl<'ast> ConstBindingArg<'ast> {
+    /// This returns the `ItemId` of the binding target.
+    pub fn binding_target(&self) -> ItemId {
+        self.binding_target
+    }
+
+    /// The value that the binding is set to.
+    pub fn value(&self) -> &ConstValue<'ast> {
+        &self.value
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> ConstBindingArg<'ast> {
+    pub fn new(binding_target: ItemId, value: ConstValue<'ast>) -> Self {
+        Self { binding_target, value }
+    }
+}
+
 #[repr(C)]
 #[derive(Debug)]
 pub struct ConstArg<'ast> {
@@ -0,0 +1,239 @@
+use crate::{
+    common::ItemId,
+    sem::{ty::TyKind, ConstValue, Lifetime, TraitBound},
+};
+
+/// A semantic predicate of an item. Predicates are the semantic representation
+/// of bounds, declared on generic parameters and in `where` clauses. They also
+/// include bounds, which are added implicitly, like the `Sized` bound on generic
+/// types.
+///
+/// ```
+/// # use std::fmt::Debug;
+/// fn foo<'a, 'b: 'a, T: Debug + 'a, I>(_t: &'a T, _i: I)
+/// //         ^^^^^^  ^^^^^^^^^^^^^ These are predicates
+/// where
+///     I: Iterator<Item = u32>,
+/// //  ^^^^^^^^^^^^^^^^^^^^^^^ These are predicates as well
+/// {}
+/// ```
+///
+/// The predicates of an item can be requested with
+/// [`MarkerContext::predicates_of`](crate::MarkerContext::predicates_of).
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum PredicateKind<'ast> {
+    /// A trait predicate, like `T: Debug` or `Self: Sized`
+    Trait(&'ast TraitPredicate<'ast>),
+    /// A lifetime predicate, which requires that a lifetime outlives another one,
+    /// like `'b: 'a`
+    LifetimeOutlives(&'ast LifetimeOutlivesPredicate<'ast>),
+    /// A type predicate, which requires that a type outlives a lifetime, like
+    /// `T: 'a`
+    TyOutlives(&'ast TyOutlivesPredicate<'ast>),
+    /// A projection predicate, which requires an associated type of a trait
+    /// to be equal to a specific type, like the `Item = u32` in
+    /// `I: Iterator<Item = u32>`.
+    Projection(&'ast ProjectionPredicate<'ast>),
+    /// A projection predicate, which requires an associated constant of a trait
+    /// to be equal to a specific value, like the `N = 3` in `T: Matrix<N = 3>`.
+    /// This requires the unstable `associated_const_equality` feature.
+    ConstProjection(&'ast ConstProjectionPredicate<'ast>),
+}
+
+/// A semantic trait predicate, like `T: Debug` or `Self: Sized`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct TraitPredicate<'ast> {
+    ty: TyKind<'ast>,
+    bound: TraitBound<'ast>,
+}
+
+impl<'ast> TraitPredicate<'ast> {
+    /// The type that is bound by this predicate. This would be `T` for `T: Debug`.
+    pub fn ty(&self) -> TyKind<'ast> {
+        self.ty
+    }
+
+    /// The trait bound, applied to the type. This would be `Debug` for `T: Debug`.
+    pub fn bound(&self) -> &TraitBound<'ast> {
+        &self.bound
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> TraitPredicate<'ast> {
+    pub fn new(ty: TyKind<'ast>, bound: TraitBound<'ast>) -> Self {
+        Self { ty, bound }
+    }
+}
+
+/// A semantic predicate, that requires one lifetime to outlive another one,
+/// like `'b: 'a`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct LifetimeOutlivesPredicate<'ast> {
+    lifetime: Lifetime<'ast>,
+    bound: Lifetime<'ast>,
+}
+
+impl<'ast> LifetimeOutlivesPredicate<'ast> {
+    /// The lifetime that has to outlive the bound. This would be `'b` for `'b: 'a`.
+    pub fn lifetime(&self) -> &Lifetime<'ast> {
+        &self.lifetime
+    }
+
+    /// The lifetime that has to be outlived. This would be `'a` for `'b: 'a`.
+    pub fn bound(&self) -> &Lifetime<'ast> {
+        &self.bound
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> LifetimeOutlivesPredicate<'ast> {
+    pub fn new(lifetime: Lifetime<'ast>, bound: Lifetime<'ast>) -> Self {
+        Self { lifetime, bound }
+    }
+}
+
+/// A semantic predicate, that requires a type to outlive a lifetime, like `T: 'a`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct TyOutlivesPredicate<'ast> {
+    ty: TyKind<'ast>,
+    bound: Lifetime<'ast>,
+}
+
+impl<'ast> TyOutlivesPredicate<'ast> {
+    /// The type that has to outlive the bound. This would be `T` for `T: 'a`.
+    pub fn ty(&self) -> TyKind<'ast> {
+        self.ty
+    }
+
+    /// The lifetime that has to be outlived. This would be `'a` for `T: 'a`.
+    pub fn bound(&self) -> &Lifetime<'ast> {
+        &self.bound
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> TyOutlivesPredicate<'ast> {
+    pub fn new(ty: TyKind<'ast>, bound: Lifetime<'ast>) -> Self {
+        Self { ty, bound }
+    }
+}
+
+/// A semantic projection predicate, which requires an associated type to be
+/// equal to a specific type. For example, `I: Iterator<Item = u32>` results in
+/// a [`TraitPredicate`] for `I: Iterator` and a projection predicate for
+/// `<I as Iterator>::Item == u32`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct ProjectionPredicate<'ast> {
+    self_ty: TyKind<'ast>,
+    trait_bound: TraitBound<'ast>,
+    assoc_item: ItemId,
+    ty: TyKind<'ast>,
+}
+
+impl<'ast> ProjectionPredicate<'ast> {
+    /// The type that the projection is applied to. This would be `I` for
+    /// `<I as Iterator>::Item == u32`.
+    pub fn self_ty(&self) -> TyKind<'ast> {
+        self.self_ty
+    }
+
+    /// The trait, that defines the associated item. This would be `Iterator`
+    /// for `<I as Iterator>::Item == u32`.
+    pub fn trait_bound(&self) -> &TraitBound<'ast> {
+        &self.trait_bound
+    }
+
+    /// The [`ItemId`] of the associated item. This would be the id of `Item`
+    /// for `<I as Iterator>::Item == u32`.
+    pub fn assoc_item(&self) -> ItemId {
+        self.assoc_item
+    }
+
+    /// The type that the associated item is equal to. This would be `u32` for
+    /// `<I as Iterator>::Item == u32`.
+    pub fn ty(&self) -> TyKind<'ast> {
+        self.ty
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> ProjectionPredicate<'ast> {
+    pub fn new(self_ty: TyKind<'ast>, trait_bound: TraitBound<'ast>, assoc_item: ItemId, ty: TyKind<'ast>) -> Self {
+        Self {
+            self_ty,
+            trait_bound,
+            assoc_item,
+            ty,
+        }
+    }
+}
+
+/// A semantic projection predicate, which requires an associated constant to be
+/// equal to a specific value. For example, `T: Matrix<N = 3>` results in a
+/// [`TraitPredicate`] for `T: Matrix` and a const projection predicate for
+/// `<T as Matrix>::N == 3`.
+///
+/// ```ignore
+/// # #![feature(associated_const_equality)]
+/// # trait Matrix { const N: usize; }
+/// fn foo<T: Matrix<N = 3>>() {}
+/// //               ^^^^^
+/// ```
+#[repr(C)]
+#[derive(Debug)]
+pub struct ConstProjectionPredicate<'ast> {
+    self_ty: TyKind<'ast>,
+    trait_bound: TraitBound<'ast>,
+    assoc_item: ItemId,
+    value: ConstValue<'ast>,
+}
+
+impl<'ast> ConstProjectionPredicate<'ast> {
+    /// The type that the projection is applied to. This would be `T` for
+    /// `<T as Matrix>::N == 3`.
+    pub fn self_ty(&self) -> TyKind<'ast> {
+        self.self_ty
+    }
+
+    /// The trait, that defines the associated constant. This would be `Matrix`
+    /// for `<T as Matrix>::N == 3`.
+    pub fn trait_bound(&self) -> &TraitBound<'ast> {
+        &self.trait_bound
+    }
+
+    /// The [`ItemId`] of the associated constant. This would be the id of `N`
+    /// for `<T as Matrix>::N == 3`.
+    pub fn assoc_item(&self) -> ItemId {
+        self.assoc_item
+    }
+
+    /// The value that the associated constant is equal to. This would be `3`
+    /// for `<T as Matrix>::N == 3`.
+    pub fn value(&self) -> &ConstValue<'ast> {
+        &self.value
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> ConstProjectionPredicate<'ast> {
+    pub fn new(
+        self_ty: TyKind<'ast>,
+        trait_bound: TraitBound<'ast>,
+        assoc_item: ItemId,
+        value: ConstValue<'ast>,
+    ) -> Self {
+        Self {
+            self_ty,
+            trait_bound,
+            assoc_item,
+            value,
+        }
+    }
+}
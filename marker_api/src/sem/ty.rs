@@ -51,6 +51,9 @@ pub enum TyKind<'ast> {
     /// The semantic representation of a
     /// [closure type](https://doc.rust-lang.org/reference/types/closure.html).
     Closure(&'ast ClosureTy<'ast>),
+    /// The semantic representation of a coroutine type. This includes the
+    /// futures generated by `async` blocks, closures and functions.
+    Coroutine(&'ast CoroutineTy<'ast>),
     // ================================
     // Pointer types
     // ================================
@@ -76,6 +79,8 @@ pub enum TyKind<'ast> {
     /// semantic types. This kind is mainly used for type aliases, where the concrete
     /// type is not yet known, for example in traits.
     Alias(&'ast AliasTy<'ast>),
+    /// A foreign type, declared inside an `extern` block
+    Foreign(&'ast ForeignTy<'ast>),
     // ================================
     // Other types
     // ================================
@@ -115,6 +120,33 @@ impl<'ast> TyKind<'ast> {
     }
 }
 
+#[cfg(feature = "driver-api")]
+impl<'ast> TyKind<'ast> {
+    pub fn data(&self) -> &CommonTyData<'ast> {
+        match self {
+            TyKind::Bool(ty) => ty.data(),
+            TyKind::Num(ty) => ty.data(),
+            TyKind::Text(ty) => ty.data(),
+            TyKind::Never(ty) => ty.data(),
+            TyKind::Tuple(ty) => ty.data(),
+            TyKind::Array(ty) => ty.data(),
+            TyKind::Slice(ty) => ty.data(),
+            TyKind::Fn(ty) => ty.data(),
+            TyKind::Closure(ty) => ty.data(),
+            TyKind::Coroutine(ty) => ty.data(),
+            TyKind::Ref(ty) => ty.data(),
+            TyKind::RawPtr(ty) => ty.data(),
+            TyKind::FnPtr(ty) => ty.data(),
+            TyKind::TraitObj(ty) => ty.data(),
+            TyKind::Adt(ty) => ty.data(),
+            TyKind::Generic(ty) => ty.data(),
+            TyKind::Alias(ty) => ty.data(),
+            TyKind::Foreign(ty) => ty.data(),
+            TyKind::Unstable(ty) => ty.data(),
+        }
+    }
+}
+
 #[repr(C)]
 #[cfg_attr(feature = "driver-api", visibility::make(pub))]
 #[cfg_attr(feature = "driver-api", derive(typed_builder::TypedBuilder))]
@@ -139,7 +171,7 @@ impl<'ast> CommonTyData<'ast> {
 
 macro_rules! impl_ty_data {
     ($self_ty:ty, $enum_name:ident) => {
-        #[cfg(feature = "driver_api")]
+        #[cfg(feature = "driver-api")]
         impl<'ast> $self_ty {
             pub fn data(&self) -> &$crate::sem::ty::CommonTyData<'ast> {
                 &self.data
@@ -1,5 +1,5 @@
 use crate::{
-    common::{ItemId, TyDefId},
+    common::{CoroutineKind, ItemId, Movability, TyDefId},
     sem::generic::GenericArgs,
 };
 
@@ -60,3 +60,43 @@ impl<'ast> ClosureTy<'ast> {
 }
 
 super::impl_ty_data!(ClosureTy<'ast>, Closure);
+
+/// The semantic representation of a coroutine type.
+///
+/// Coroutines create anonymous types, similar to closures. This type is used
+/// for explicit coroutines and for the futures created by async blocks, closures
+/// and functions. The [`CoroutineTy::kind`] can be used to distinguish them.
+#[repr(C)]
+#[derive(Debug)]
+#[cfg_attr(feature = "driver-api", derive(typed_builder::TypedBuilder))]
+pub struct CoroutineTy<'ast> {
+    data: CommonTyData<'ast>,
+    def_id: TyDefId,
+    kind: CoroutineKind,
+    movability: Movability,
+    generics: GenericArgs<'ast>,
+}
+
+impl<'ast> CoroutineTy<'ast> {
+    /// This returns the [`TyDefId`] of the type that was generated for this coroutine.
+    pub fn def_id(&self) -> TyDefId {
+        self.def_id
+    }
+
+    /// The kind of this coroutine. Futures created by `async` blocks, closures
+    /// and functions will have the [`CoroutineKind::Async`] kind.
+    pub fn kind(&self) -> CoroutineKind {
+        self.kind
+    }
+
+    pub fn movability(&self) -> Movability {
+        self.movability
+    }
+
+    /// This returns the [`GenericArgs`] used by coroutine.
+    pub fn generics(&self) -> &GenericArgs<'ast> {
+        &self.generics
+    }
+}
+
+super::impl_ty_data!(CoroutineTy<'ast>, Coroutine);
@@ -1,3 +1,5 @@
+use crate::ffi::FfiStr;
+
 use super::CommonTyData;
 
 /// The placeholder type, signalling that the semantic type is still unstable
@@ -7,6 +9,17 @@ use super::CommonTyData;
 #[cfg_attr(feature = "driver-api", derive(typed_builder::TypedBuilder))]
 pub struct UnstableTy<'ast> {
     data: CommonTyData<'ast>,
+    #[cfg_attr(feature = "driver-api", builder(setter(into)))]
+    reason: FfiStr<'ast>,
+}
+
+impl<'ast> UnstableTy<'ast> {
+    /// A short description of the type, which is represented by this
+    /// placeholder, like "coroutine". The text is intended for debugging
+    /// and might change between versions.
+    pub fn reason(&self) -> &str {
+        self.reason.get()
+    }
 }
 
 super::impl_ty_data!(UnstableTy<'ast>, Unstable);
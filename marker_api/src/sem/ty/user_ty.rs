@@ -82,3 +82,35 @@ impl<'ast> AliasTy<'ast> {
 }
 
 super::impl_ty_data!(AliasTy<'ast>, Alias);
+
+/// The semantic representation of a foreign type, declared inside an `extern`
+/// block, like this:
+///
+/// ```ignore
+/// # #![feature(extern_types)]
+/// extern "C" {
+///     type Handle;
+/// }
+///
+/// fn close(_handle: &Handle) {}
+/// //                 ^^^^^^ This will have the foreign type `Handle`
+/// ```
+///
+/// Foreign types are currently an unstable feature, see
+/// [rust#43467](https://github.com/rust-lang/rust/issues/43467).
+#[repr(C)]
+#[derive(Debug)]
+#[cfg_attr(feature = "driver-api", derive(typed_builder::TypedBuilder))]
+pub struct ForeignTy<'ast> {
+    data: CommonTyData<'ast>,
+    def_id: TyDefId,
+}
+
+impl<'ast> ForeignTy<'ast> {
+    /// This returns the [`TyDefId`] of the foreign type.
+    pub fn def_id(&self) -> TyDefId {
+        self.def_id
+    }
+}
+
+super::impl_ty_data!(ForeignTy<'ast>, Foreign);
@@ -1,7 +1,7 @@
-use std::marker::PhantomData;
+use std::{marker::PhantomData, ops::RangeInclusive};
 
 use crate::{
-    common::{ExpnId, MacroId, SpanId, SpanSrcId, SymbolId},
+    common::{CrateId, ExpnId, HasNodeId, ItemId, MacroId, NodeId, SpanId, SpanSrcId, SymbolId},
     context::with_cx,
     diagnostic::Applicability,
     ffi,
@@ -143,6 +143,7 @@ pub struct ExpnInfo<'ast> {
     parent: ExpnId,
     call_site: SpanId,
     macro_id: MacroId,
+    kind: MacroKind,
 }
 
 impl<'ast> ExpnInfo<'ast> {
@@ -161,21 +162,129 @@ impl<'ast> ExpnInfo<'ast> {
     pub fn macro_id(&self) -> MacroId {
         self.macro_id
     }
+
+    /// The kind of macro, that created this expansion.
+    pub fn kind(&self) -> MacroKind {
+        self.kind
+    }
+
+    /// Returns the [`MacroInfo`] of the macro, that created this expansion.
+    /// This is a shorthand for [`MarkerContext::macro_info`](crate::MarkerContext::macro_info).
+    pub fn macro_info(&self) -> &MacroInfo<'ast> {
+        with_cx(self, |cx| cx.macro_info(self.macro_id))
+    }
 }
 
 #[cfg(feature = "driver-api")]
 impl<'ast> ExpnInfo<'ast> {
     #[must_use]
-    pub fn new(parent: ExpnId, call_site: SpanId, macro_id: MacroId) -> Self {
+    pub fn new(parent: ExpnId, call_site: SpanId, macro_id: MacroId, kind: MacroKind) -> Self {
         Self {
             _lifetime: PhantomData,
             parent,
             call_site,
             macro_id,
+            kind,
         }
     }
 }
 
+/// The different kinds of macros, that can create an expansion.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum MacroKind {
+    /// A function-like macro, invoked with an exclamation mark, like `vec![]`.
+    /// This includes `macro_rules!` macros and function-like proc macros.
+    Bang,
+    /// An attribute macro, like `#[test]` or `#[tokio::main]`.
+    Attr,
+    /// A derive macro, like the `Debug` in `#[derive(Debug)]`.
+    Derive,
+}
+
+/// Information about a macro, identified by a [`MacroId`]. This information can
+/// be requested via [`MarkerContext::macro_info`](crate::MarkerContext::macro_info)
+/// or [`ExpnInfo::macro_info`].
+///
+/// ```
+/// # macro_rules! local {
+/// #     () => {};
+/// # }
+/// let _ = vec![1, 2, 3];
+/// // Name: `vec`, Kind: `Bang`, Crate: `alloc`
+///
+/// #[derive(Debug)]
+/// struct Item;
+/// // Name: `Debug`, Kind: `Derive`, Crate: `core`, Builtin: `true`
+///
+/// local!();
+/// // Name: `local`, Kind: `Bang`, Local: `true`
+/// ```
+#[repr(C)]
+#[derive(Debug)]
+#[cfg_attr(feature = "driver-api", derive(typed_builder::TypedBuilder))]
+pub struct MacroInfo<'ast> {
+    #[cfg_attr(feature = "driver-api", builder(setter(skip), default))]
+    _lifetime: PhantomData<&'ast ()>,
+    name: SymbolId,
+    #[cfg_attr(feature = "driver-api", builder(setter(into)))]
+    path: ffi::FfiStr<'ast>,
+    kind: MacroKind,
+    is_builtin: bool,
+    crate_id: CrateId,
+    crate_name: SymbolId,
+    is_local: bool,
+    is_from_workspace: bool,
+}
+
+impl<'ast> MacroInfo<'ast> {
+    /// The name of the macro, like `vec` for `vec![]` or `Debug` for `#[derive(Debug)]`.
+    pub fn name(&self) -> &str {
+        with_cx(self, |cx| cx.symbol_str(self.name))
+    }
+
+    /// The path of the macro definition, like `std::vec`. The path might
+    /// differ from the path, that was used to invoke the macro.
+    pub fn path(&self) -> &str {
+        self.path.get()
+    }
+
+    /// The [`MacroKind`] of this macro, like [`MacroKind::Derive`] for
+    /// `#[derive(Debug)]`.
+    pub fn kind(&self) -> MacroKind {
+        self.kind
+    }
+
+    /// Returns `true`, if this macro is implemented by the compiler itself, like
+    /// `format_args!` or `#[derive(Clone)]`.
+    pub fn is_builtin(&self) -> bool {
+        self.is_builtin
+    }
+
+    /// The [`CrateId`] of the crate, that defined this macro.
+    pub fn crate_id(&self) -> CrateId {
+        self.crate_id
+    }
+
+    /// The name of the crate, that defined this macro.
+    pub fn crate_name(&self) -> &str {
+        with_cx(self, |cx| cx.symbol_str(self.crate_name))
+    }
+
+    /// Returns `true`, if this macro was defined in the currently linted crate.
+    pub fn is_local(&self) -> bool {
+        self.is_local
+    }
+
+    /// Returns `true`, if this macro was defined in the linted workspace. This is
+    /// always the case for [local](Self::is_local) macros, but also includes macros
+    /// from other crates of the same workspace.
+    pub fn is_from_workspace(&self) -> bool {
+        self.is_from_workspace
+    }
+}
+
 /// A region of code, used for snipping, lint emission, and the retrieval of
 /// context information.
 ///
@@ -389,6 +498,123 @@ impl<'ast> Span<'ast> {
     pub fn source(&self) -> SpanSource<'ast> {
         with_cx(self, |cx| cx.span_source(self))
     }
+
+    /// Returns an empty [`Span`] at the start of this span.
+    #[must_use]
+    pub fn shrink_to_lo(&self) -> Span<'ast> {
+        self.with_end(self.start)
+    }
+
+    /// Returns an empty [`Span`] at the end of this span.
+    #[must_use]
+    pub fn shrink_to_hi(&self) -> Span<'ast> {
+        let mut new_span = self.clone();
+        new_span.start = self.end;
+        new_span
+    }
+
+    /// Returns `true`, if both spans come from the same source, meaning the same
+    /// file or the same macro expansion. Only positions of spans with the same
+    /// source can be compared and combined directly.
+    pub fn ctxt_eq(&self, other: &Span<'_>) -> bool {
+        self.source_id == other.source_id
+    }
+
+    /// Returns a [`Span`], which starts at the start of this span and ends at
+    /// the end of the `end` span.
+    ///
+    /// ```ignore
+    /// let _ = a + b;
+    /// //      ^   ^ b_span
+    /// //      a_span
+    ///
+    /// a_span.to(&b_span); // Span of `a + b`
+    /// ```
+    ///
+    /// If the spans come from different sources, the driver tries to find a
+    /// common call site for both spans, like the macro invocation, which
+    /// contains them.
+    #[must_use]
+    pub fn to(&self, end: &Span<'ast>) -> Span<'ast> {
+        with_cx(self, |cx| cx.span_to(self, end))
+    }
+
+    /// Returns the [`Span`] between the end of this span and the start of the
+    /// `end` span.
+    ///
+    /// ```ignore
+    /// let _ = a  +  b;
+    /// //      ^^^^^^^ a_span.to(&b_span)
+    /// //       ^^^^^^ a_span.between(&b_span) -> "  +  "
+    /// ```
+    #[must_use]
+    pub fn between(&self, end: &Span<'ast>) -> Span<'ast> {
+        with_cx(self, |cx| cx.span_between(self, end))
+    }
+
+    /// Walks up the macro expansions of this [`Span`] and returns the outermost
+    /// call site, which was written in a source file. The span is returned as
+    /// is, if it doesn't come from a macro expansion.
+    ///
+    /// ```ignore
+    /// let _ = vec![1, 2];
+    /// //      ^^^^^^^^^^ The source call site of all spans from the expansion
+    /// ```
+    #[must_use]
+    pub fn source_callsite(&self) -> Span<'ast> {
+        with_cx(self, |cx| cx.span_source_callsite(self))
+    }
+
+    /// Returns the 1-indexed lines, that this [`Span`] covers. This returns [`None`],
+    /// if the span doesn't point into a source file, like for spans from macro
+    /// expansions. Consider using [`source_callsite`](Self::source_callsite) first,
+    /// to get the span of the macro invocation.
+    pub fn line_range(&self) -> Option<RangeInclusive<usize>> {
+        let SpanSource::File(file) = self.source() else {
+            return None;
+        };
+        let start = file.try_to_file_pos(self.start)?.line();
+        let end = file.try_to_file_pos(self.end)?.line();
+        Some(start..=end)
+    }
+
+    /// Expands this [`Span`] to cover the whole lines, including the indentation
+    /// at the start and trailing code or comments at the end. The line break at
+    /// the end is not included.
+    ///
+    /// ```ignore
+    /// fn main() {
+    ///     let _ = 12;
+    /// //          ^^ lit_span
+    /// //^^^^^^^^^^^^^^ lit_span.expand_to_lines()
+    /// }
+    /// ```
+    #[must_use]
+    pub fn expand_to_lines(&self) -> Span<'ast> {
+        with_cx(self, |cx| cx.span_expand_to_lines(self))
+    }
+
+    /// Returns the lexical tokens inside this [`Span`], including whitespace
+    /// and comments. Only tokens which are completely inside the span are
+    /// returned, they are sorted by their position in the source file.
+    ///
+    /// ```ignore
+    /// let tuple = (1, 2,);
+    /// //          ^^^^^^^
+    /// //          expr_span
+    ///
+    /// // Checking for a trailing comma
+    /// let tokens: Vec<_> = expr_span.tokens().iter().filter(|token| !token.is_trivia()).collect();
+    /// if let [.., second_last, _] = tokens.as_slice() {
+    ///     assert_eq!(second_last.kind(), TokenKind::Comma);
+    /// }
+    /// ```
+    ///
+    /// Tokens are only available for spans, which point into a source file.
+    /// An empty slice is returned for spans from macro expansions.
+    pub fn tokens(&self) -> &'ast [Token<'ast>] {
+        with_cx(self, |cx| cx.span_tokens(self))
+    }
 }
 
 impl<'ast> HasSpan<'ast> for Span<'ast> {
@@ -469,6 +695,111 @@ impl<'ast> FileInfo<'ast> {
     }
 }
 
+/// A source file of the linted crate. The files can be requested via
+/// [`MarkerContext::source_files`](crate::MarkerContext::source_files) and are
+/// passed to [`LintPass::check_file`](crate::LintPass::check_file).
+///
+/// Every module, which is declared with a `mod <name>;` item, has its own file.
+/// The [`NodeId`] of a source file is the one of the module, that it defines.
+/// This allows the emission of lints on source files, while still respecting
+/// the lint levels defined on the module.
+///
+/// ```ignore
+/// if file.line_count() > 1000 {
+///     cx.emit_lint(FILE_TOO_LONG, file, "this file is too long")
+///         .span(file.line_span(1001).unwrap());
+/// }
+/// ```
+#[repr(C)]
+#[derive(Debug)]
+#[cfg_attr(feature = "driver-api", derive(typed_builder::TypedBuilder))]
+pub struct SourceFile<'ast> {
+    #[cfg_attr(feature = "driver-api", builder(setter(into)))]
+    path: ffi::FfiStr<'ast>,
+    #[cfg_attr(feature = "driver-api", builder(setter(into)))]
+    module_path: ffi::FfiStr<'ast>,
+    module_id: ItemId,
+    #[cfg_attr(feature = "driver-api", builder(setter(into)))]
+    text: ffi::FfiStr<'ast>,
+    /// The start positions of all lines in this file.
+    #[cfg_attr(feature = "driver-api", builder(setter(into)))]
+    line_starts: ffi::FfiSlice<'ast, SpanPos>,
+    span: SpanId,
+}
+
+impl<'ast> SourceFile<'ast> {
+    /// The path of this file, like `src/lib.rs`. This is the same path as
+    /// the one provided by [`FileInfo::file`].
+    pub fn path(&self) -> &'ast str {
+        self.path.get()
+    }
+
+    /// The path of the module defined by this file, like `crate_name::module`.
+    pub fn module_path(&self) -> &'ast str {
+        self.module_path.get()
+    }
+
+    /// The [`ItemId`] of the module defined by this file.
+    pub fn module_id(&self) -> ItemId {
+        self.module_id
+    }
+
+    /// The full text of this file.
+    pub fn text(&self) -> &'ast str {
+        self.text.get()
+    }
+
+    /// The number of lines in this file.
+    pub fn line_count(&self) -> usize {
+        self.line_starts().len()
+    }
+
+    /// The start positions of all lines in this file.
+    pub fn line_starts(&self) -> &'ast [SpanPos] {
+        self.line_starts.get()
+    }
+
+    /// Returns the text of the given 1-indexed line, without the line break.
+    /// [`None`] is returned, if the file doesn't have this line.
+    pub fn line(&self, line: usize) -> Option<&'ast str> {
+        let range = self.line_range(line)?;
+        let text = &self.text()[range];
+        let text = text.strip_suffix('\n').unwrap_or(text);
+        Some(text.strip_suffix('\r').unwrap_or(text))
+    }
+
+    /// Returns the [`Span`] of the given 1-indexed line, without the line break.
+    /// [`None`] is returned, if the file doesn't have this line.
+    pub fn line_span(&self, line: usize) -> Option<Span<'ast>> {
+        let start = self.line_starts().get(line.checked_sub(1)?)?.0;
+        let len = u32::try_from(self.line(line)?.len()).expect("lines have to fit into `u32`");
+        Some(self.span().with_start(SpanPos(start)).with_end(SpanPos(start + len)))
+    }
+
+    /// Returns the byte range of the given 1-indexed line in [`Self::text`]
+    fn line_range(&self, line: usize) -> Option<std::ops::Range<usize>> {
+        let file_start = self.span().start.0;
+        let starts = self.line_starts();
+        let start = starts.get(line.checked_sub(1)?)?.0 - file_start;
+        let end = starts
+            .get(line)
+            .map_or(self.text().len(), |end| (end.0 - file_start) as usize);
+        Some(start as usize..end)
+    }
+}
+
+impl<'ast> HasSpan<'ast> for SourceFile<'ast> {
+    fn span(&self) -> &Span<'ast> {
+        with_cx(self, |cx| cx.span(self.span))
+    }
+}
+
+impl<'ast> HasNodeId for SourceFile<'ast> {
+    fn node_id(&self) -> NodeId {
+        self.module_id.into()
+    }
+}
+
 /// A location inside a file.
 ///
 /// [`SpanPos`] instances belonging to files can be mapped to [`FilePos`] with
@@ -509,6 +840,229 @@ impl<'ast> FilePos<'ast> {
     }
 }
 
+/// A comment in the source code. Comments are not part of the AST, they can
+/// be requested via [`MarkerContext::comments_in`](crate::MarkerContext::comments_in),
+/// [`MarkerContext::comments_before`](crate::MarkerContext::comments_before) and
+/// [`MarkerContext::doc_comments`](crate::MarkerContext::doc_comments).
+///
+/// ```
+/// // A line comment, with the text " A line comment"
+/// /* A block comment */
+/// /// An outer doc comment
+/// fn item() {
+///     //! An inner doc comment
+/// }
+/// ```
+#[repr(C)]
+#[derive(Debug)]
+pub struct Comment<'ast> {
+    kind: CommentKind,
+    doc_style: ffi::FfiOption<DocStyle>,
+    text: ffi::FfiStr<'ast>,
+    span: SpanId,
+}
+
+impl<'ast> Comment<'ast> {
+    /// Returns the [`CommentKind`], which indicates if this is a line or
+    /// block comment.
+    pub fn kind(&self) -> CommentKind {
+        self.kind
+    }
+
+    /// Returns `true`, if this is a doc comment, like `/// Docs` or `//! Docs`.
+    pub fn is_doc(&self) -> bool {
+        self.doc_style.is_some()
+    }
+
+    /// Returns the [`DocStyle`], if this is a doc comment.
+    pub fn doc_style(&self) -> Option<DocStyle> {
+        self.doc_style.copy()
+    }
+
+    /// The text of this comment, without the comment delimiters. The text of
+    /// `// SAFETY: ...` is `" SAFETY: ..."`, the text of `/// Docs` is `" Docs"`.
+    pub fn text(&self) -> &str {
+        self.text.get()
+    }
+}
+
+impl<'ast> HasSpan<'ast> for Comment<'ast> {
+    fn span(&self) -> &Span<'ast> {
+        with_cx(self, |cx| cx.span(self.span))
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> Comment<'ast> {
+    pub fn new(kind: CommentKind, doc_style: Option<DocStyle>, text: &'ast str, span: SpanId) -> Self {
+        Self {
+            kind,
+            doc_style: doc_style.into(),
+            text: text.into(),
+            span,
+        }
+    }
+}
+
+/// The syntax of a [`Comment`]. Doc comments are distinguished by their
+/// [`DocStyle`] instead.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CommentKind {
+    /// A line comment, like `// Comment` or `/// Docs`.
+    Line,
+    /// A block comment, like `/* Comment */` or `/** Docs */`.
+    Block,
+}
+
+/// The style of a doc [`Comment`], which determines the documented item.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DocStyle {
+    /// An outer doc comment, like `/// Docs`, which documents the following item.
+    Outer,
+    /// An inner doc comment, like `//! Docs`, which documents the surrounding item.
+    Inner,
+}
+
+/// A lexical token of the source code, as returned by [`Span::tokens`].
+///
+/// Tokens are produced by the lexer and only describe the raw source text.
+/// Multi-character operators, like `==` or `::`, are therefore represented
+/// by several single-character tokens. The token stream also includes trivia,
+/// like whitespace and comments, which are lost in the AST.
+#[repr(C)]
+#[derive(Debug)]
+pub struct Token<'ast> {
+    kind: TokenKind,
+    text: ffi::FfiStr<'ast>,
+    span: SpanId,
+}
+
+impl<'ast> Token<'ast> {
+    /// Returns the [`TokenKind`] of this token, like [`TokenKind::Ident`]
+    /// for `foo` or [`TokenKind::Semi`] for `;`.
+    pub fn kind(&self) -> TokenKind {
+        self.kind
+    }
+
+    /// The source text of this token.
+    pub fn text(&self) -> &'ast str {
+        self.text.get()
+    }
+
+    /// Returns `true`, if this token is whitespace or a comment.
+    pub fn is_trivia(&self) -> bool {
+        matches!(
+            self.kind,
+            TokenKind::Whitespace | TokenKind::LineComment | TokenKind::BlockComment
+        )
+    }
+}
+
+impl<'ast> HasSpan<'ast> for Token<'ast> {
+    fn span(&self) -> &Span<'ast> {
+        with_cx(self, |cx| cx.span(self.span))
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> Token<'ast> {
+    pub fn new(kind: TokenKind, text: &'ast str, span: SpanId) -> Self {
+        Self {
+            kind,
+            text: text.into(),
+            span,
+        }
+    }
+}
+
+/// The kind of a [`Token`]. Keywords are represented as [`TokenKind::Ident`]
+/// and multi-character operators, like `::` or `+=`, are split into one token
+/// per character. The [text](Token::text) of the token can be used to
+/// distinguish them.
+///
+/// More kinds might be added in the future, for example, when the lexer
+/// learns new syntax.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TokenKind {
+    /// Any sequence of whitespace characters, including newlines.
+    Whitespace,
+    /// A line comment, like `// Comment` or `/// Docs`.
+    LineComment,
+    /// A block comment, like `/* Comment */` or `/** Docs */`.
+    BlockComment,
+    /// An identifier or keyword, like `foo`, `self` or `fn`.
+    Ident,
+    /// A raw identifier, like `r#try`.
+    RawIdent,
+    /// A lifetime or label, like `'a` or `'outer`.
+    Lifetime,
+    /// A literal including its suffix, like `1_u32`, `'c'` or `"string"`.
+    Literal,
+    /// `;`
+    Semi,
+    /// `,`
+    Comma,
+    /// `.`
+    Dot,
+    /// `(`
+    OpenParen,
+    /// `)`
+    CloseParen,
+    /// `{`
+    OpenBrace,
+    /// `}`
+    CloseBrace,
+    /// `[`
+    OpenBracket,
+    /// `]`
+    CloseBracket,
+    /// `@`
+    At,
+    /// `#`
+    Pound,
+    /// `~`
+    Tilde,
+    /// `?`
+    Question,
+    /// `:`
+    Colon,
+    /// `$`
+    Dollar,
+    /// `=`
+    Eq,
+    /// `!`
+    Bang,
+    /// `<`
+    Lt,
+    /// `>`
+    Gt,
+    /// `-`
+    Minus,
+    /// `&`
+    And,
+    /// `|`
+    Or,
+    /// `+`
+    Plus,
+    /// `*`
+    Star,
+    /// `/`
+    Slash,
+    /// `^`
+    Caret,
+    /// `%`
+    Percent,
+    /// A token, which is not valid Rust, like an unknown character or an
+    /// unknown literal prefix.
+    Unknown,
+}
+
 /// The [`Span`] belongs to something, which was generated by the Compiler. This
 /// could be the imports from the prelude or the testing harness.
 #[repr(C)]
@@ -2,21 +2,24 @@ use std::cell::{OnceCell, RefCell};
 
 use marker_adapter::context::{AstMapWrapper, MarkerContextDriver, MarkerContextWrapper};
 use marker_api::{
-    common::{SpanId, SymbolId},
+    common::{MacroId, NodeId, SpanId, SymbolId},
     diagnostic::Diagnostic,
     prelude::*,
+    span::{Comment, MacroInfo, SourceFile, Token},
 };
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use rustc_hir as hir;
 use rustc_lint::LintStore;
 use rustc_middle::ty::TyCtxt;
 
 use crate::conversion::{marker::MarkerConverter, rustc::RustcConverter};
 
-use self::storage::Storage;
+use self::{comments::FileComments, storage::Storage, tokens::FileTokens};
 
+mod comments;
 mod map;
 pub mod storage;
+mod tokens;
 
 /// This is the central context for the rustc driver and the struct providing the
 /// callback implementation for [`MarkerContext`](`marker_api::context::MarkerContext`).
@@ -40,6 +43,15 @@ pub struct RustcContext<'ast, 'tcx> {
     /// which makes it safe to access afterwards.
     ast_cx: OnceCell<&'ast MarkerContext<'ast>>,
     resolved_ty_ids: RefCell<FxHashMap<&'ast str, &'ast [TyDefId]>>,
+    trait_impls: RefCell<FxHashMap<TyDefId, &'ast [ItemId]>>,
+    inherent_impls: RefCell<FxHashMap<TyDefId, &'ast [ItemId]>>,
+    predicates: RefCell<FxHashMap<ItemId, &'ast [marker_api::sem::PredicateKind<'ast>]>>,
+    macro_infos: RefCell<FxHashMap<MacroId, &'ast MacroInfo<'ast>>>,
+    /// The comments of source files, identified by the start position of the file.
+    file_comments: RefCell<FxHashMap<rustc_span::BytePos, FileComments<'ast>>>,
+    /// The tokens of source files, identified by the start position of the file.
+    file_tokens: RefCell<FxHashMap<rustc_span::BytePos, FileTokens<'ast>>>,
+    source_files: OnceCell<&'ast [SourceFile<'ast>]>,
 }
 
 impl<'ast, 'tcx> RustcContext<'ast, 'tcx> {
@@ -53,6 +65,13 @@ impl<'ast, 'tcx> RustcContext<'ast, 'tcx> {
             rustc_converter: RustcConverter::new(rustc_cx),
             ast_cx: OnceCell::new(),
             resolved_ty_ids: RefCell::default(),
+            trait_impls: RefCell::default(),
+            inherent_impls: RefCell::default(),
+            predicates: RefCell::default(),
+            macro_infos: RefCell::default(),
+            file_comments: RefCell::default(),
+            file_tokens: RefCell::default(),
+            source_files: OnceCell::new(),
         });
 
         // Create and link `MarkerContext`
@@ -189,6 +208,151 @@ impl<'ast, 'tcx: 'ast> MarkerContextDriver<'ast> for RustcContext<'ast, 'tcx> {
         ids
     }
 
+    fn impls_of_trait(&'ast self, trait_id: TyDefId) -> &'ast [ItemId] {
+        if let Some(ids) = self.trait_impls.borrow().get(&trait_id) {
+            return ids;
+        }
+
+        let tcx = self.rustc_cx;
+        let def_id = self.rustc_converter.to_def_id(trait_id);
+        let ids: &[ItemId] = if matches!(tcx.def_kind(def_id), hir::def::DefKind::Trait) {
+            let ids: Vec<_> = tcx
+                .all_impls(def_id)
+                .map(|impl_id| self.marker_converter.to_item_id(impl_id))
+                .collect();
+            self.storage.alloc_slice(ids)
+        } else {
+            &[]
+        };
+
+        self.trait_impls.borrow_mut().insert(trait_id, ids);
+        ids
+    }
+
+    fn inherent_impls_of(&'ast self, ty_id: TyDefId) -> &'ast [ItemId] {
+        if let Some(ids) = self.inherent_impls.borrow().get(&ty_id) {
+            return ids;
+        }
+
+        let tcx = self.rustc_cx;
+        let def_id = self.rustc_converter.to_def_id(ty_id);
+        let ids: &[ItemId] = if matches!(
+            tcx.def_kind(def_id),
+            hir::def::DefKind::Struct
+                | hir::def::DefKind::Union
+                | hir::def::DefKind::Enum
+                | hir::def::DefKind::Trait
+                | hir::def::DefKind::ForeignTy
+        ) {
+            self.storage.alloc_slice(
+                tcx.inherent_impls(def_id)
+                    .iter()
+                    .map(|impl_id| self.marker_converter.to_item_id(*impl_id)),
+            )
+        } else {
+            &[]
+        };
+
+        self.inherent_impls.borrow_mut().insert(ty_id, ids);
+        ids
+    }
+
+    fn trait_impl_for(&'ast self, ty: marker_api::sem::TyKind<'ast>, trait_id: TyDefId) -> Option<ItemId> {
+        let tcx = self.rustc_cx;
+        let trait_def_id = self.rustc_converter.to_def_id(trait_id);
+        if !matches!(tcx.def_kind(trait_def_id), hir::def::DefKind::Trait) {
+            return None;
+        }
+
+        let rustc_ty = self.rustc_converter.to_driver_ty_id(ty.data().driver_id());
+        let rustc_ty = tcx.erase_regions(rustc_ty);
+        // Impls for concrete types are checked first, to prefer them over blanket impls.
+        let trait_impls = tcx.trait_impls_of(trait_def_id);
+        tcx.non_blanket_impls_for_ty(trait_def_id, rustc_ty)
+            .chain(trait_impls.blanket_impls().iter().copied())
+            .find(|impl_id| impl_may_apply(tcx, *impl_id, rustc_ty))
+            .map(|impl_id| self.marker_converter.to_item_id(impl_id))
+    }
+
+    fn predicates_of(&'ast self, item: ItemId) -> &'ast [marker_api::sem::PredicateKind<'ast>] {
+        if let Some(predicates) = self.predicates.borrow().get(&item) {
+            return predicates;
+        }
+
+        let def_id = self.rustc_converter.to_def_id(item);
+        let predicates = if matches!(
+            self.rustc_cx.def_kind(def_id),
+            hir::def::DefKind::Struct
+                | hir::def::DefKind::Union
+                | hir::def::DefKind::Enum
+                | hir::def::DefKind::Trait
+                | hir::def::DefKind::TraitAlias
+                | hir::def::DefKind::TyAlias { .. }
+                | hir::def::DefKind::Fn
+                | hir::def::DefKind::AssocFn
+                | hir::def::DefKind::AssocTy
+                | hir::def::DefKind::AssocConst
+                | hir::def::DefKind::Impl { .. }
+        ) {
+            self.marker_converter.to_sem_predicates(def_id)
+        } else {
+            &[]
+        };
+
+        self.predicates.borrow_mut().insert(item, predicates);
+        predicates
+    }
+
+    fn macro_info(&'ast self, id: MacroId) -> &'ast MacroInfo<'ast> {
+        if let Some(info) = self.macro_infos.borrow().get(&id) {
+            return info;
+        }
+
+        let def_id = self.rustc_converter.to_def_id(id);
+        let info = self.storage.alloc(self.marker_converter.to_macro_info(def_id));
+        self.macro_infos.borrow_mut().insert(id, info);
+        info
+    }
+
+    fn comments_in(&'ast self, span: &Span<'ast>) -> &'ast [Comment<'ast>] {
+        self.comments_in_span(span)
+    }
+
+    fn comments_before(&'ast self, span: &Span<'ast>) -> &'ast [Comment<'ast>] {
+        self.comments_before_span(span)
+    }
+
+    fn doc_comments(&'ast self, node: NodeId) -> &'ast [Comment<'ast>] {
+        self.doc_comments_of(node)
+    }
+
+    fn source_files(&'ast self) -> &'ast [SourceFile<'ast>] {
+        self.source_files.get_or_init(|| {
+            // Modules are sorted by their depth, to ensure that every file is
+            // mapped to the outermost module it defines. Inline modules are
+            // part of the file of their parent.
+            let mut modules = vec![];
+            self.rustc_cx.hir().for_each_module(|module| modules.push(module));
+            modules.sort_by_key(|module| self.rustc_cx.def_path(module.to_def_id()).data.len());
+
+            let source_map = self.rustc_cx.sess.source_map();
+            let mut seen_files = FxHashSet::default();
+            let mut files = vec![];
+            for module in modules {
+                let span = self.rustc_cx.hir().get_module(module).0.spans.inner_span;
+                if span.from_expansion() || span.is_dummy() {
+                    continue;
+                }
+                let file = source_map.lookup_source_file(span.lo());
+                if !seen_files.insert(file.start_pos) {
+                    continue;
+                }
+                files.extend(self.marker_converter.to_source_file(&file, module.to_local_def_id()));
+            }
+            self.storage.alloc_slice(files)
+        })
+    }
+
     fn expr_ty(&'ast self, expr: ExprId) -> marker_api::sem::TyKind<'ast> {
         let hir_id = self.rustc_converter.to_hir_id(expr);
         self.marker_converter.expr_ty(hir_id)
@@ -210,6 +374,33 @@ impl<'ast, 'tcx: 'ast> MarkerContextDriver<'ast> for RustcContext<'ast, 'tcx> {
         self.marker_converter.to_span_source(rust_span)
     }
 
+    fn span_tokens(&'ast self, span: &Span<'ast>) -> &'ast [Token<'ast>] {
+        self.tokens_in_span(span)
+    }
+
+    fn span_to(&'ast self, span: &Span<'ast>, end: &Span<'ast>) -> Span<'ast> {
+        let span = self.rustc_converter.to_span(span);
+        let end = self.rustc_converter.to_span(end);
+        self.marker_converter.to_span(span.to(end))
+    }
+
+    fn span_between(&'ast self, span: &Span<'ast>, end: &Span<'ast>) -> Span<'ast> {
+        let span = self.rustc_converter.to_span(span);
+        let end = self.rustc_converter.to_span(end);
+        self.marker_converter.to_span(span.between(end))
+    }
+
+    fn span_source_callsite(&'ast self, span: &Span<'ast>) -> Span<'ast> {
+        let span = self.rustc_converter.to_span(span);
+        self.marker_converter.to_span(span.source_callsite())
+    }
+
+    fn span_expand_to_lines(&'ast self, span: &Span<'ast>) -> Span<'ast> {
+        let span = self.rustc_converter.to_span(span);
+        let lines = self.rustc_cx.sess.source_map().span_extend_to_line(span);
+        self.marker_converter.to_span(lines)
+    }
+
     fn span_pos_to_file_loc(
         &'ast self,
         file: &marker_api::span::FileInfo<'ast>,
@@ -246,6 +437,39 @@ impl<'ast, 'tcx: 'ast> MarkerContextDriver<'ast> for RustcContext<'ast, 'tcx> {
     }
 }
 
+/// Checks if the given impl can apply to the given self type. This unifies the
+/// self type of the impl with the given type and checks that all predicates of the
+/// impl may hold. Generic parameters in `self_ty` are checked without any bounds.
+fn impl_may_apply<'tcx>(tcx: TyCtxt<'tcx>, impl_id: hir::def_id::DefId, self_ty: rustc_middle::ty::Ty<'tcx>) -> bool {
+    use rustc_infer::infer::TyCtxtInferExt;
+    use rustc_infer::traits::{Obligation, ObligationCause};
+    use rustc_trait_selection::traits::ObligationCtxt;
+
+    let Some(impl_trait_ref) = tcx.impl_trait_ref(impl_id) else {
+        return false;
+    };
+
+    let infcx = tcx.infer_ctxt().build();
+    let obligation_cx = ObligationCtxt::new(&infcx);
+    let cause = ObligationCause::dummy();
+    let param_env = rustc_middle::ty::ParamEnv::empty();
+
+    let args = infcx.fresh_args_for_item(rustc_span::DUMMY_SP, impl_id);
+    let impl_self_ty = impl_trait_ref.instantiate(tcx, args).self_ty();
+    if obligation_cx.eq(&cause, param_env, impl_self_ty, self_ty).is_err() {
+        return false;
+    }
+
+    let predicates = tcx.predicates_of(impl_id).instantiate(tcx, args);
+    obligation_cx.register_obligations(
+        predicates
+            .predicates
+            .into_iter()
+            .map(|clause| Obligation::new(tcx, cause.clone(), param_env, clause)),
+    );
+    obligation_cx.select_where_possible().is_empty()
+}
+
 fn select_children_with_name(
     tcx: TyCtxt<'_>,
     search: &[hir::def::Res<hir::def_id::DefId>],
@@ -0,0 +1,102 @@
+use std::ops::Range;
+
+use marker_api::{common::NodeId, prelude::*, span::Comment};
+use rustc_span::BytePos;
+
+use super::{tokens::lex_file, RustcContext};
+
+/// The comments of a single source file, sorted by their position.
+#[derive(Clone, Copy)]
+pub struct FileComments<'ast> {
+    comments: &'ast [Comment<'ast>],
+    /// The positions of the comments in `comments`.
+    ranges: &'ast [Range<BytePos>],
+}
+
+impl<'ast, 'tcx: 'ast> RustcContext<'ast, 'tcx> {
+    pub(super) fn comments_in_span(&'ast self, api_span: &Span<'ast>) -> &'ast [Comment<'ast>] {
+        let span = self.rustc_converter.to_span(api_span);
+        let Some(file_comments) = self.file_comments(span) else {
+            return &[];
+        };
+
+        let ranges = file_comments.ranges;
+        let start = ranges.partition_point(|range| range.start < span.lo());
+        let end = ranges.partition_point(|range| range.end <= span.hi());
+        if start >= end {
+            return &[];
+        }
+        &file_comments.comments[start..end]
+    }
+
+    pub(super) fn comments_before_span(&'ast self, api_span: &Span<'ast>) -> &'ast [Comment<'ast>] {
+        let span = self.rustc_converter.to_span(api_span);
+        let Some(file_comments) = self.file_comments(span) else {
+            return &[];
+        };
+        let file = self.rustc_cx.sess.source_map().lookup_source_file(span.lo());
+        let Some(src) = &file.src else {
+            return &[];
+        };
+
+        let ranges = file_comments.ranges;
+        let end = ranges.partition_point(|range| range.end <= span.lo());
+        let mut start = end;
+        let mut boundary = span.lo();
+        while start > 0 {
+            let prev = &ranges[start - 1];
+            let prev_start = (prev.start - file.start_pos).0 as usize;
+            let gap = &src[(prev.end - file.start_pos).0 as usize..(boundary - file.start_pos).0 as usize];
+            if !gap.trim().is_empty() {
+                break;
+            }
+            // Trailing comments on previous lines belong to the code in front of them
+            let line_start = src[..prev_start].rfind('\n').map_or(0, |index| index + 1);
+            if gap.contains('\n') && !src[line_start..prev_start].trim().is_empty() {
+                break;
+            }
+            start -= 1;
+            boundary = prev.start;
+        }
+        &file_comments.comments[start..end]
+    }
+
+    pub(super) fn doc_comments_of(&'ast self, node: NodeId) -> &'ast [Comment<'ast>] {
+        let Some(hir_id) = self.rustc_converter.try_to_hir_id_from_emission_node(node) else {
+            return &[];
+        };
+        let comments: Vec<_> = self
+            .rustc_cx
+            .hir()
+            .attrs(hir_id)
+            .iter()
+            .filter_map(|attr| self.marker_converter.to_doc_comment(attr))
+            .collect();
+        self.storage.alloc_slice(comments)
+    }
+
+    /// Returns the comments of the source file, that contains the given span.
+    /// Comments are lexed once per file and cached afterwards.
+    fn file_comments(&'ast self, span: rustc_span::Span) -> Option<FileComments<'ast>> {
+        let file = self.source_file_of(span)?;
+        if let Some(comments) = self.file_comments.borrow().get(&file.start_pos) {
+            return Some(*comments);
+        }
+
+        let mut comments = vec![];
+        let mut ranges = vec![];
+        lex_file(&file, |kind, text, span| {
+            if let Some(comment) = self.marker_converter.to_comment(kind, text, span) {
+                comments.push(comment);
+                ranges.push(span.lo()..span.hi());
+            }
+        });
+
+        let comments = FileComments {
+            comments: self.storage.alloc_slice(comments),
+            ranges: self.storage.alloc_slice(ranges),
+        };
+        self.file_comments.borrow_mut().insert(file.start_pos, comments);
+        Some(comments)
+    }
+}
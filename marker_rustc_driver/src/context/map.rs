@@ -9,6 +9,8 @@ use super::RustcContext;
 
 impl<'ast, 'tcx: 'ast> AstMapDriver<'ast> for RustcContext<'ast, 'tcx> {
     fn item(&'ast self, id: ItemId) -> Option<ItemKind<'ast>> {
+        // Only items of the local crate are available as AST nodes
+        self.rustc_converter.to_def_id(id).as_local()?;
         let rustc_id = self.rustc_converter.to_item_id(id);
         self.marker_converter.item(rustc_id)
     }
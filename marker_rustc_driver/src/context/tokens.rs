@@ -0,0 +1,86 @@
+use std::ops::Range;
+
+use marker_api::{prelude::*, span::Token};
+use rustc_span::{BytePos, Pos, SourceFile};
+
+use super::RustcContext;
+
+/// The tokens of a single source file, sorted by their position.
+#[derive(Clone, Copy)]
+pub struct FileTokens<'ast> {
+    tokens: &'ast [Token<'ast>],
+    /// The positions of the tokens in `tokens`.
+    ranges: &'ast [Range<BytePos>],
+}
+
+impl<'ast, 'tcx: 'ast> RustcContext<'ast, 'tcx> {
+    pub(super) fn tokens_in_span(&'ast self, api_span: &Span<'ast>) -> &'ast [Token<'ast>] {
+        let span = self.rustc_converter.to_span(api_span);
+        let Some(file) = self.source_file_of(span) else {
+            return &[];
+        };
+
+        if let Some(file_tokens) = self.file_tokens.borrow().get(&file.start_pos) {
+            return file_tokens.tokens_in(span);
+        }
+
+        let mut tokens = vec![];
+        let mut ranges = vec![];
+        lex_file(&file, |kind, text, span| {
+            tokens.push(self.marker_converter.to_token(kind, text, span));
+            ranges.push(span.lo()..span.hi());
+        });
+
+        let file_tokens = FileTokens {
+            tokens: self.storage.alloc_slice(tokens),
+            ranges: self.storage.alloc_slice(ranges),
+        };
+        self.file_tokens.borrow_mut().insert(file.start_pos, file_tokens);
+        file_tokens.tokens_in(span)
+    }
+
+    /// Returns the source file, which contains the given span, if the span
+    /// points into a source file and doesn't come from a macro expansion.
+    pub(super) fn source_file_of(
+        &self,
+        span: rustc_span::Span,
+    ) -> Option<rustc_data_structures::sync::Lrc<SourceFile>> {
+        if span.from_expansion() || span.is_dummy() {
+            return None;
+        }
+        let file = self.rustc_cx.sess.source_map().lookup_source_file(span.lo());
+        if span.hi() > file.end_position() {
+            return None;
+        }
+        Some(file)
+    }
+}
+
+impl<'ast> FileTokens<'ast> {
+    fn tokens_in(&self, span: rustc_span::Span) -> &'ast [Token<'ast>] {
+        let start = self.ranges.partition_point(|range| range.start < span.lo());
+        let end = self.ranges.partition_point(|range| range.end <= span.hi());
+        if start >= end {
+            return &[];
+        }
+        &self.tokens[start..end]
+    }
+}
+
+/// Lexes the given source file and calls `f` for every token, with the
+/// token kind, the source text of the token and its span.
+pub(super) fn lex_file(file: &SourceFile, mut f: impl FnMut(rustc_lexer::TokenKind, &str, rustc_span::Span)) {
+    let Some(src) = &file.src else {
+        return;
+    };
+
+    let mut offset = rustc_lexer::strip_shebang(src).unwrap_or(0);
+    for token in rustc_lexer::tokenize(&src[offset..]) {
+        let text = &src[offset..offset + token.len as usize];
+        let lo = file.start_pos + BytePos::from_usize(offset);
+        let hi = lo + BytePos(token.len);
+        offset += token.len as usize;
+
+        f(token.kind, text, rustc_span::Span::with_root_ctxt(lo, hi));
+    }
+}
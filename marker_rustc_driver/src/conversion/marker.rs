@@ -10,15 +10,15 @@ mod span;
 
 use std::cell::{OnceCell, RefCell};
 
-use crate::context::storage::Storage;
+use crate::{context::storage::Storage, MARKER_REPORT_UNSUPPORTED_ENV, MARKER_WORKSPACE_CRATES_ENV};
 use marker_api::{
     ast::{Body, CommonItemData, Crate, EnumVariant, ItemField, ModItem, Visibility as AstVisibility},
     common::{Level, SymbolId},
     prelude::*,
-    sem::{Visibility as SemVisibility, VisibilityKind},
-    span::{ExpnInfo, FilePos, Span, SpanSource},
+    sem::{PredicateKind, Visibility as SemVisibility, VisibilityKind},
+    span::{Comment, ExpnInfo, FilePos, MacroInfo, SourceFile, Span, SpanSource, Token},
 };
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use rustc_hir as hir;
 
 /// An interface to convert rustc's IR to marker types.
@@ -62,7 +62,7 @@ impl<'ast, 'tcx> MarkerConverter<'ast, 'tcx> {
     pub fn expr_ty(&self, id: hir::HirId) -> marker_api::sem::TyKind<'ast> {
         self.with_body(id, |inner| {
             let ty = inner.rustc_ty_check().node_type(id);
-            inner.to_sem_ty(ty)
+            inner.with_sem_span(inner.rustc_cx.hir().span(id), || inner.to_sem_ty(ty))
         })
     }
 
@@ -138,9 +138,30 @@ impl<'ast, 'tcx> MarkerConverter<'ast, 'tcx> {
     }
 
     forward_to_inner!(pub fn to_ty_def_id(&self, id: hir::def_id::DefId) -> TyDefId);
+    forward_to_inner!(pub fn to_item_id(&self, id: hir::def_id::DefId) -> ItemId);
+    forward_to_inner!(pub fn to_sem_predicates(&self, def_id: hir::def_id::DefId) -> &'ast [PredicateKind<'ast>]);
     forward_to_inner!(pub fn to_span(&self, rustc_span: rustc_span::Span) -> Span<'ast>);
     forward_to_inner!(pub fn to_span_source(&self, rust_span: rustc_span::Span) -> SpanSource<'ast>);
     forward_to_inner!(pub fn try_to_expn_info(&self, expn_id: rustc_span::ExpnId) -> Option<&'ast ExpnInfo<'ast>>);
+    forward_to_inner!(pub fn to_macro_info(&self, def_id: hir::def_id::DefId) -> MacroInfo<'ast>);
+    forward_to_inner!(pub fn to_comment(
+        &self,
+        kind: rustc_lexer::TokenKind,
+        text: &str,
+        span: rustc_span::Span,
+    ) -> Option<Comment<'ast>>);
+    forward_to_inner!(pub fn to_token(
+        &self,
+        kind: rustc_lexer::TokenKind,
+        text: &str,
+        span: rustc_span::Span,
+    ) -> Token<'ast>);
+    forward_to_inner!(pub fn to_source_file(
+        &self,
+        file: &rustc_span::SourceFile,
+        module: hir::def_id::LocalDefId,
+    ) -> Option<SourceFile<'ast>>);
+    forward_to_inner!(pub fn to_doc_comment(&self, attr: &rustc_ast::Attribute) -> Option<Comment<'ast>>);
     forward_to_inner!(pub fn try_to_span_pos(
         &self,
         scx: rustc_span::SyntaxContext,
@@ -199,6 +220,16 @@ struct MarkerConverterInner<'ast, 'tcx> {
     ///
     /// The map is filled in [`Self::fill_create_lang_item_map`].
     lang_item_map: RefCell<FxHashMap<hir::LangItem, SymbolId>>,
+    /// The reasons of all unsupported nodes, which have been reported so far.
+    /// This is used to log every kind of unsupported node only once. See
+    /// [`Self::report_unsupported`] for more context.
+    reported_unsupported: RefCell<FxHashSet<&'static str>>,
+    /// Indicates, that every unsupported node should be reported with its span.
+    /// This is enabled by the [`MARKER_REPORT_UNSUPPORTED_ENV`] environment value.
+    report_all_unsupported: bool,
+    /// The crate names of all workspace members, taken from the
+    /// [`MARKER_WORKSPACE_CRATES_ENV`] environment value.
+    workspace_crates: FxHashSet<String>,
 
     // Context information
     /// This holds the [`hir::BodyId`] of the body that is currently being
@@ -212,6 +243,14 @@ struct MarkerConverterInner<'ast, 'tcx> {
     /// Requested on demand from rustc using a [`hir::BodyId`] see
     /// [`MarkerConverterInner::rustc_body`] for more information
     rustc_ty_check: RefCell<Option<&'tcx rustc_middle::ty::TypeckResults<'tcx>>>,
+    /// The item, which defines the generic parameters for semantic conversions
+    /// outside of bodies, like the predicates of an item. If this is [`None`],
+    /// the owner of [`MarkerConverterInner::rustc_body`] will be used.
+    rustc_generics_owner: RefCell<Option<hir::def_id::DefId>>,
+    /// The span of the node, whose semantic types are currently converted.
+    /// Semantic types don't have a span of their own, this is used to report
+    /// unsupported types at the location, where they occur.
+    rustc_sem_span: RefCell<Option<rustc_span::Span>>,
 }
 
 // General util functions
@@ -230,8 +269,15 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
             builtin_span_source: storage.alloc(marker_api::span::BuiltinInfo::default()),
             num_symbols: RefCell::default(),
             lang_item_map: RefCell::default(),
+            reported_unsupported: RefCell::default(),
+            report_all_unsupported: std::env::var_os(MARKER_REPORT_UNSUPPORTED_ENV).is_some(),
+            workspace_crates: std::env::var(MARKER_WORKSPACE_CRATES_ENV)
+                .map(|crates| crates.split(',').map(ToString::to_string).collect())
+                .unwrap_or_default(),
             rustc_body: RefCell::default(),
             rustc_ty_check: RefCell::default(),
+            rustc_generics_owner: RefCell::default(),
+            rustc_sem_span: RefCell::default(),
         };
 
         s.fill_create_lang_item_map();
@@ -304,6 +350,79 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
         // Return result
         res
     }
+
+    pub fn with_generics_owner<U, F>(&self, owner: hir::def_id::DefId, f: F) -> U
+    where
+        F: FnOnce() -> U,
+    {
+        let prev_owner = self.rustc_generics_owner.replace(Some(owner));
+        let res = f();
+        self.rustc_generics_owner.replace(prev_owner);
+        res
+    }
+
+    /// Sets the span, which is used to report unsupported semantic types,
+    /// during the conversion in `f`. See [`Self::report_unsupported_sem`].
+    pub fn with_sem_span<U, F>(&self, span: rustc_span::Span, f: F) -> U
+    where
+        F: FnOnce() -> U,
+    {
+        let prev_span = self.rustc_sem_span.replace(Some(span));
+        let res = f();
+        self.rustc_sem_span.replace(prev_span);
+        res
+    }
+
+    /// This reports, that a construct in the linted crate can't be represented
+    /// by Marker's API. The conversion then uses a placeholder, like an
+    /// `Unstable*` node, or skips the construct entirely.
+    ///
+    /// Every `reason` is only logged once per crate. If the
+    /// [`MARKER_REPORT_UNSUPPORTED_ENV`] environment value is set, every
+    /// occurrence is reported with its span instead.
+    pub fn report_unsupported(&self, reason: &'static str, span: rustc_span::Span) {
+        self.report_unsupported_at(reason, Some(span));
+    }
+
+    /// This is a variant of [`Self::report_unsupported`] for semantic types.
+    /// They don't have a span, the unsupported type is therefore reported at
+    /// the node, whose type is currently converted. See [`Self::with_sem_span`].
+    pub fn report_unsupported_sem(&self, reason: &'static str) {
+        self.report_unsupported_at(reason, *self.rustc_sem_span.borrow());
+    }
+
+    fn report_unsupported_at(&self, reason: &'static str, span: Option<rustc_span::Span>) {
+        let is_new = self.reported_unsupported.borrow_mut().insert(reason);
+        let dcx = self.rustc_cx.sess.dcx();
+        let msg = format!("marker: {reason} is not supported yet and was replaced by a placeholder");
+        if self.report_all_unsupported {
+            match span {
+                Some(span) => dcx.span_note(span, msg),
+                None => dcx.note(msg),
+            }
+        } else if is_new {
+            dcx.struct_note(msg)
+                .note("use `cargo marker --report-unsupported` to list all occurrences")
+                .emit();
+        }
+    }
+
+    /// Returns the [`DefId`](hir::def_id::DefId) of the item, that defines the
+    /// generic parameters for the current conversion.
+    pub fn generics_owner(&self) -> hir::def_id::DefId {
+        if let Some(owner) = *self.rustc_generics_owner.borrow() {
+            return owner;
+        }
+
+        let body_id = self
+            .rustc_body
+            .borrow()
+            .expect("generic parameters can only be resolved inside bodies or with a generics owner");
+        // This is a local id, this makes sense, since rustc only accesses
+        // expressions and therefore semantic types of the current crate.
+        // This should be fine...
+        self.rustc_cx.hir().body_owner_def_id(body_id).to_def_id()
+    }
 }
 
 impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
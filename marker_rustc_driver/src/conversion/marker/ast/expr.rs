@@ -1,11 +1,15 @@
 use marker_api::{
     ast::{
-        ArrayExpr, AsExpr, AssignExpr, AwaitExpr, BinaryOpExpr, BinaryOpKind, BlockExpr, BoolLitExpr, BreakExpr,
-        CallExpr, CaptureKind, CharLitExpr, ClosureExpr, ClosureParam, CommonExprData, ConstExpr, ContinueExpr,
-        CtorExpr, CtorField, ExprKind, ExprPrecedence, FieldExpr, FloatLitExpr, FloatSuffix, ForExpr, IfExpr,
-        IndexExpr, IntLitExpr, IntSuffix, LetExpr, LoopExpr, MatchArm, MatchExpr, MethodExpr, PathExpr, RangeExpr,
-        RefExpr, ReturnExpr, StrLitData, StrLitExpr, TryExpr, TupleExpr, UnaryOpExpr, UnaryOpKind, UnstableExpr,
-        WhileExpr,
+        ArrayExpr, AsExpr, AsmClobberAbi, AsmConstOperand, AsmDirection, AsmLitPiece, AsmOperandKind, AsmOptions,
+        AsmPieceKind, AsmPlaceholder, AsmReg, AsmRegOperand, AsmSymStaticOperand, AssignExpr, AwaitExpr, BinaryOpExpr,
+        BinaryOpKind, BlockExpr, BoolLitExpr, BreakExpr, CallExpr, CaptureKind, CharLitExpr, ClosureExpr, ClosureParam,
+        CommonExprData, ConstExpr, ContinueExpr, CoroutineExpr, CtorExpr, CtorField, ExprKind, ExprPrecedence,
+        FieldExpr, FloatLitExpr, FloatSuffix, ForExpr, FormatAlign, FormatArg, FormatArgKind, FormatArgPos,
+        FormatArgPosKind, FormatArgsExpr, FormatCount, FormatDebugHex, FormatLitPiece, FormatPieceKind,
+        FormatPlaceholder, FormatSign, FormatSpec, FormatTrait, IfExpr, IndexExpr, InlineAsmExpr, IntLitExpr,
+        IntSuffix, LetExpr, LoopExpr, MatchArm, MatchExpr, MethodExpr, OffsetOfExpr, PathExpr, RangeExpr, RefExpr,
+        ReturnExpr, StrLitData, StrLitExpr, TryExpr, TupleExpr, TyKind, UnaryOpExpr, UnaryOpKind, UnstableExpr,
+        UnstableExprKind, WhileExpr,
     },
     common::{Safety, Syncness},
     span::Ident,
@@ -15,6 +19,7 @@ use rustc_hir as hir;
 use std::str::FromStr;
 
 use crate::conversion::marker::MarkerConverterInner;
+use crate::lint_pass::FormatArgsInfo;
 
 impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
     #[must_use]
@@ -44,9 +49,14 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
             return *expr;
         }
 
+        if let Some(format_args) = self.try_to_format_args_expr(expr) {
+            self.exprs.borrow_mut().insert(id, format_args);
+            return format_args;
+        }
+
         let data = CommonExprData::new(id, self.to_span_id(expr.span));
         let expr = match &expr.kind {
-            hir::ExprKind::Lit(spanned_lit) => self.to_expr_from_lit_kind(data, &spanned_lit.node),
+            hir::ExprKind::Lit(spanned_lit) => self.to_expr_from_lit_kind(data, &spanned_lit.node, spanned_lit.span),
             hir::ExprKind::Binary(op, left, right) => ExprKind::BinaryOp(self.alloc(BinaryOpExpr::new(
                 data,
                 self.to_expr(left),
@@ -155,6 +165,12 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                     Some(self.to_const_expr(*anon_const)),
                 )))
             },
+            hir::ExprKind::Repeat(_, hir::ArrayLen::Infer(..)) => self.to_unstable_expr(
+                data,
+                expr,
+                UnstableExprKind::Other,
+                "array repeat expression with an inferred length",
+            ),
             hir::ExprKind::Struct(path, fields, base) => match path {
                 hir::QPath::LangItem(hir::LangItem::RangeFull, _) => {
                     ExprKind::Range(self.alloc(RangeExpr::new(data, None, None, false)))
@@ -220,6 +236,9 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
             hir::ExprKind::Match(_scrutinee, [_awaitee_arm], hir::MatchSource::AwaitDesugar) => {
                 ExprKind::Await(self.alloc(self.to_await_expr_from_desugar(expr)))
             },
+            hir::ExprKind::Match(..) => {
+                self.to_unstable_expr(data, expr, UnstableExprKind::Other, "unexpected `match` desugar")
+            },
             hir::ExprKind::Assign(assignee, value, _span) => ExprKind::Assign(self.alloc(AssignExpr::new(
                 data,
                 self.to_place_pat_from_expr(assignee),
@@ -255,7 +274,7 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                 hir::LoopSource::While => ExprKind::While(self.alloc(self.to_while_loop_from_desugar(expr))),
                 hir::LoopSource::ForLoop => unreachable!("is desugared at a higher node level"),
             },
-            hir::ExprKind::Closure(closure) => self.to_expr_from_closure(data, expr, closure),
+            hir::ExprKind::Closure(closure) => self.to_expr_from_closure(data, closure),
             hir::ExprKind::Cast(expr, ty) => {
                 ExprKind::As(self.alloc(AsExpr::new(data, self.to_expr(expr), self.to_syn_ty(ty))))
             },
@@ -264,13 +283,25 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
             // convert the inner expression directly
             hir::ExprKind::DropTemps(inner) => return self.to_expr(inner),
             hir::ExprKind::Err(..) => unreachable!("would have triggered a rustc error"),
-            _ => {
-                eprintln!("skipping not implemented expr at: {:?}", expr.span);
-                ExprKind::Unstable(
-                    self.alloc({
-                        UnstableExpr::new(data, ExprPrecedence::Unstable(i32::from(expr.precedence().order())))
-                    }),
-                )
+            hir::ExprKind::InlineAsm(asm) => {
+                ExprKind::InlineAsm(self.alloc(self.to_inline_asm_expr(data, asm, expr.span)))
+            },
+            hir::ExprKind::OffsetOf(container, fields) => ExprKind::OffsetOf(self.alloc(OffsetOfExpr::new(
+                data,
+                self.to_syn_ty(container),
+                self.alloc_slice(fields.iter().map(|field| self.to_ident(*field))),
+            ))),
+            hir::ExprKind::ConstBlock(_) => {
+                self.to_unstable_expr(data, expr, UnstableExprKind::InlineConst, "inline `const` block")
+            },
+            hir::ExprKind::Type(..) => {
+                self.to_unstable_expr(data, expr, UnstableExprKind::TypeAscription, "type ascription")
+            },
+            hir::ExprKind::Become(_) => {
+                self.to_unstable_expr(data, expr, UnstableExprKind::Become, "`become` expression")
+            },
+            hir::ExprKind::Yield(..) => {
+                self.to_unstable_expr(data, expr, UnstableExprKind::Yield, "`yield` expression")
             },
         };
 
@@ -306,8 +337,123 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
         )
     }
 
+    /// Creates an [`UnstableExpr`] placeholder for expressions, which are not
+    /// represented by Marker's API yet.
     #[must_use]
-    fn to_expr_from_lit_kind(&self, data: CommonExprData<'ast>, lit_kind: &rustc_ast::LitKind) -> ExprKind<'ast> {
+    fn to_unstable_expr(
+        &self,
+        data: CommonExprData<'ast>,
+        expr: &hir::Expr<'tcx>,
+        kind: UnstableExprKind,
+        reason: &'static str,
+    ) -> ExprKind<'ast> {
+        self.report_unsupported(reason, expr.span);
+        ExprKind::Unstable(self.alloc(UnstableExpr::new(
+            data,
+            ExprPrecedence::Unstable(i32::from(expr.precedence().order())),
+            kind,
+            reason,
+        )))
+    }
+
+    fn to_inline_asm_expr(
+        &self,
+        data: CommonExprData<'ast>,
+        asm: &hir::InlineAsm<'tcx>,
+        span: rustc_span::Span,
+    ) -> InlineAsmExpr<'ast> {
+        let template = self.alloc_slice(asm.template.iter().map(|piece| match piece {
+            rustc_ast::InlineAsmTemplatePiece::String(value) => {
+                AsmPieceKind::Lit(self.alloc(AsmLitPiece::new(self.storage.alloc_str(value))))
+            },
+            rustc_ast::InlineAsmTemplatePiece::Placeholder {
+                operand_idx,
+                modifier,
+                span,
+            } => AsmPieceKind::Placeholder(self.alloc(AsmPlaceholder::new(
+                *operand_idx,
+                *modifier,
+                self.to_span_id(*span),
+            ))),
+        }));
+        let operands = self.alloc_slice(
+            asm.operands
+                .iter()
+                .map(|(operand, span)| self.to_asm_operand(operand, *span)),
+        );
+        let options = asm.options;
+        let options = AsmOptions::builder()
+            .pure(options.contains(rustc_ast::InlineAsmOptions::PURE))
+            .nomem(options.contains(rustc_ast::InlineAsmOptions::NOMEM))
+            .readonly(options.contains(rustc_ast::InlineAsmOptions::READONLY))
+            .preserves_flags(options.contains(rustc_ast::InlineAsmOptions::PRESERVES_FLAGS))
+            .noreturn(options.contains(rustc_ast::InlineAsmOptions::NORETURN))
+            .nostack(options.contains(rustc_ast::InlineAsmOptions::NOSTACK))
+            .att_syntax(options.contains(rustc_ast::InlineAsmOptions::ATT_SYNTAX))
+            .raw(options.contains(rustc_ast::InlineAsmOptions::RAW))
+            .may_unwind(options.contains(rustc_ast::InlineAsmOptions::MAY_UNWIND))
+            .build();
+        let clobber_abis = self.alloc_slice(
+            crate::lint_pass::asm_clobber_abis_at(span)
+                .into_iter()
+                .map(|(name, abi_span)| AsmClobberAbi::new(self.to_symbol_id(name), self.to_span_id(abi_span))),
+        );
+        InlineAsmExpr::new(data, template, operands, clobber_abis, options)
+    }
+
+    fn to_asm_operand(&self, operand: &hir::InlineAsmOperand<'tcx>, span: rustc_span::Span) -> AsmOperandKind<'ast> {
+        let span = self.to_span_id(span);
+        let (direction, reg, late, in_expr, out_expr) = match operand {
+            hir::InlineAsmOperand::In { reg, expr } => (AsmDirection::In, reg, false, Some(*expr), None),
+            hir::InlineAsmOperand::Out { reg, late, expr } => (AsmDirection::Out, reg, *late, None, *expr),
+            hir::InlineAsmOperand::InOut { reg, late, expr } => {
+                (AsmDirection::InOut, reg, *late, Some(*expr), Some(*expr))
+            },
+            hir::InlineAsmOperand::SplitInOut {
+                reg,
+                late,
+                in_expr,
+                out_expr,
+            } => (AsmDirection::SplitInOut, reg, *late, Some(*in_expr), *out_expr),
+            hir::InlineAsmOperand::Const { anon_const } => {
+                return AsmOperandKind::Const(self.alloc(AsmConstOperand::new(span, self.to_const_expr(*anon_const))));
+            },
+            hir::InlineAsmOperand::SymFn { anon_const } => {
+                return AsmOperandKind::SymFn(self.alloc(AsmConstOperand::new(span, self.to_const_expr(*anon_const))));
+            },
+            hir::InlineAsmOperand::SymStatic { def_id, .. } => {
+                return AsmOperandKind::SymStatic(self.alloc(AsmSymStaticOperand::new(span, self.to_item_id(*def_id))));
+            },
+        };
+        let reg = match reg {
+            rustc_target::asm::InlineAsmRegOrRegClass::Reg(reg) => {
+                AsmReg::new(self.storage.alloc_str(reg.name()), true)
+            },
+            rustc_target::asm::InlineAsmRegOrRegClass::RegClass(class) => {
+                AsmReg::new(self.storage.alloc_str(class.name().as_str()), false)
+            },
+        };
+        AsmOperandKind::Reg(
+            self.alloc(
+                AsmRegOperand::builder()
+                    .span(span)
+                    .direction(direction)
+                    .reg(reg)
+                    .late(late)
+                    .in_expr(in_expr.map(|expr| self.to_expr(expr)))
+                    .out_expr(out_expr.map(|expr| self.to_expr(expr)))
+                    .build(),
+            ),
+        )
+    }
+
+    #[must_use]
+    fn to_expr_from_lit_kind(
+        &self,
+        data: CommonExprData<'ast>,
+        lit_kind: &rustc_ast::LitKind,
+        span: rustc_span::Span,
+    ) -> ExprKind<'ast> {
         match &lit_kind {
             rustc_ast::LitKind::Str(sym, kind) => ExprKind::StrLit(self.alloc({
                 StrLitExpr::new(
@@ -325,7 +471,14 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
             })),
             // Still unstable see: https://github.com/rust-lang/rust/issues/105723
             rustc_ast::LitKind::CStr(_, _) => {
-                ExprKind::Unstable(self.alloc(UnstableExpr::new(data, ExprPrecedence::Lit)))
+                let reason = "C string literal";
+                self.report_unsupported(reason, span);
+                ExprKind::Unstable(self.alloc(UnstableExpr::new(
+                    data,
+                    ExprPrecedence::Lit,
+                    UnstableExprKind::CStrLit,
+                    reason,
+                )))
             },
             rustc_ast::LitKind::Byte(value) => {
                 ExprKind::IntLit(self.alloc(IntLitExpr::new(data, u128::from(*value), None)))
@@ -416,12 +569,7 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
         )
     }
 
-    fn to_expr_from_closure(
-        &self,
-        data: CommonExprData<'ast>,
-        _expr: &hir::Expr<'tcx>,
-        closure: &hir::Closure<'tcx>,
-    ) -> ExprKind<'ast> {
+    fn to_expr_from_closure(&self, data: CommonExprData<'ast>, closure: &hir::Closure<'tcx>) -> ExprKind<'ast> {
         let body_id = closure.body;
         let body = self.rustc_cx.hir().body(body_id);
         match closure.kind {
@@ -444,9 +592,11 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
             )) => {
                 let block_expr = body.value;
                 if let hir::ExprKind::Block(block, None) = block_expr.kind {
+                    // The expression data of the closure is used, to have the
+                    // future as the semantic type of this expression.
                     let api_block_expr = self.with_body(body_id, || {
                         self.to_block_expr(
-                            CommonExprData::new(self.to_expr_id(block_expr.hir_id), self.to_span_id(block_expr.span)),
+                            data,
                             block,
                             None,
                             Syncness::Async,
@@ -457,16 +607,65 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                 }
                 unreachable!("`async` block desugar always has the same structure")
             },
-            hir::ClosureKind::Coroutine(
-                hir::CoroutineKind::Desugared(hir::CoroutineDesugaring::Gen | hir::CoroutineDesugaring::AsyncGen, _)
-                | hir::CoroutineKind::Desugared(hir::CoroutineDesugaring::Async, hir::CoroutineSource::Closure)
-                | hir::CoroutineKind::Coroutine(_),
-            ) => ExprKind::Unstable(self.alloc(UnstableExpr::new(data, ExprPrecedence::Closure))),
+            hir::ClosureKind::Coroutine(hir::CoroutineKind::Desugared(
+                hir::CoroutineDesugaring::Async,
+                hir::CoroutineSource::Closure,
+            )) => {
+                // The body of an async closure is desugared into a coroutine,
+                // which directly contains the written body expression. The outer
+                // closure is converted by `to_closure_expr`.
+                self.with_body(body_id, || self.to_expr(body.value))
+            },
+            hir::ClosureKind::Coroutine(kind) => {
+                let (params, return_ty) = match kind {
+                    hir::CoroutineKind::Coroutine(_) => self.to_closure_signature(closure),
+                    // The parameters and return type of `gen` blocks are generated
+                    // by rustc and therefore not exposed.
+                    hir::CoroutineKind::Desugared(..) => ([].as_slice(), None),
+                };
+                ExprKind::Coroutine(self.alloc(CoroutineExpr::new(
+                    data,
+                    self.to_coroutine_kind(kind),
+                    self.to_movability(kind.movability()),
+                    self.to_capture_kind(closure.capture_clause),
+                    params,
+                    return_ty,
+                    self.to_body_id(body_id),
+                )))
+            },
             hir::ClosureKind::Closure => ExprKind::Closure(self.alloc(self.to_closure_expr(data, closure))),
         }
     }
 
     fn to_closure_expr(&self, data: CommonExprData<'ast>, closure: &hir::Closure<'tcx>) -> ClosureExpr<'ast> {
+        let (params, mut return_ty) = self.to_closure_signature(closure);
+
+        // Async closures are desugared into a closure, returning an async coroutine.
+        // The written return type is stored in the declaration of the coroutine.
+        let body = self.rustc_cx.hir().body(closure.body);
+        let mut syncness = Syncness::Sync;
+        if let hir::ExprKind::Closure(inner) = body.value.kind {
+            if let hir::ClosureKind::Coroutine(hir::CoroutineKind::Desugared(
+                hir::CoroutineDesugaring::Async,
+                hir::CoroutineSource::Closure,
+            )) = inner.kind
+            {
+                syncness = Syncness::Async;
+                return_ty = self.to_closure_signature(inner).1;
+            }
+        }
+
+        ClosureExpr::new(
+            data,
+            self.to_capture_kind(closure.capture_clause),
+            syncness,
+            params,
+            return_ty,
+            self.to_body_id(closure.body),
+        )
+    }
+
+    fn to_closure_signature(&self, closure: &hir::Closure<'tcx>) -> (&'ast [ClosureParam<'ast>], Option<TyKind<'ast>>) {
         let fn_decl = closure.fn_decl;
 
         let body_id = closure.body;
@@ -490,13 +689,7 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
             None
         };
 
-        ClosureExpr::new(
-            data,
-            self.to_capture_kind(closure.capture_clause),
-            params,
-            return_ty,
-            self.to_body_id(closure.body),
-        )
+        (params, return_ty)
     }
 
     fn to_capture_kind(&self, capture: hir::CaptureBy) -> CaptureKind {
@@ -707,6 +900,226 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
         unreachable!("await desugar always has the same structure")
     }
 
+    /// The `format_args!` macro is lowered into a call of a constructor of
+    /// `fmt::Arguments`. The arguments are either stored in an array or in the
+    /// scrutinee of a `match` expression, if they're used multiple times or out
+    /// of order:
+    ///
+    /// ```ignore
+    /// format_args!("{} {}", a, b);
+    /// // Lowered to:
+    /// Arguments::new_v1(&["", " "], &[Argument::new_display(&a), Argument::new_display(&b)]);
+    ///
+    /// format_args!("{1} {0} {0}", a, b);
+    /// // Lowered to:
+    /// Arguments::new_v1(&[..], &match (&a, &b) {
+    ///     args => [Argument::new_display(args.1), Argument::new_display(args.0), ..],
+    /// });
+    /// ```
+    ///
+    /// The format string is taken from the AST, which has been collected by the
+    /// [`AstCollector`](crate::lint_pass::AstCollector). Rustc
+    /// flattens nested `format_args!` calls and inlines literal arguments during
+    /// the lowering, unless this is disabled with the [`MARKER_UNFLATTEN_FORMAT_ARGS_ENV`]
+    /// environment value. The arguments are matched with the AST by their spans.
+    /// If some of them were removed by the lowering, the call is converted as a
+    /// normal expression instead.
+    ///
+    /// [`MARKER_UNFLATTEN_FORMAT_ARGS_ENV`]: crate::MARKER_UNFLATTEN_FORMAT_ARGS_ENV
+    fn try_to_format_args_expr(&self, expr: &hir::Expr<'tcx>) -> Option<ExprKind<'ast>> {
+        let hir::ExprKind::Call(_, call_args) = expr.kind else {
+            return None;
+        };
+        let format_args = crate::lint_pass::format_args_at(expr.span)?;
+
+        let ast_args = &format_args.args;
+        let hir_args: Vec<&hir::Expr<'tcx>> = if ast_args.is_empty() {
+            vec![]
+        } else if let [
+            _pieces,
+            hir::Expr {
+                kind: hir::ExprKind::AddrOf(_, _, args),
+                ..
+            },
+            ..,
+        ] = call_args
+        {
+            match args.kind {
+                hir::ExprKind::Array(elements) => elements
+                    .iter()
+                    .map(|element| match element.kind {
+                        hir::ExprKind::Call(
+                            _,
+                            [
+                                hir::Expr {
+                                    kind: hir::ExprKind::AddrOf(_, _, arg),
+                                    ..
+                                },
+                            ],
+                        ) => Some(*arg),
+                        _ => None,
+                    })
+                    .collect::<Option<_>>()?,
+                hir::ExprKind::Match(
+                    hir::Expr {
+                        kind: hir::ExprKind::Tup(elements),
+                        ..
+                    },
+                    _,
+                    hir::MatchSource::FormatArgs,
+                ) => elements
+                    .iter()
+                    .map(|element| match element.kind {
+                        hir::ExprKind::AddrOf(_, _, arg) => Some(arg),
+                        _ => None,
+                    })
+                    .collect::<Option<_>>()?,
+                _ => return None,
+            }
+        } else {
+            return None;
+        };
+        if hir_args.len() != ast_args.len()
+            || !ast_args
+                .iter()
+                .zip(&hir_args)
+                .all(|((_, span), hir_arg)| span.source_equal(hir_arg.span))
+        {
+            return None;
+        }
+
+        let args = self.alloc_slice(ast_args.iter().zip(hir_args).map(|((kind, _), hir_arg)| {
+            let (kind, ident) = match *kind {
+                rustc_ast::FormatArgumentKind::Normal => (FormatArgKind::Positional, None),
+                rustc_ast::FormatArgumentKind::Named(ident) => (FormatArgKind::Named, Some(self.to_ident(ident))),
+                rustc_ast::FormatArgumentKind::Captured(ident) => (FormatArgKind::Captured, Some(self.to_ident(ident))),
+            };
+            FormatArg::new(kind, ident, self.to_expr(hir_arg))
+        }));
+
+        let data = CommonExprData::new(self.to_expr_id(expr.hir_id), self.to_span_id(expr.span));
+        Some(ExprKind::FormatArgs(self.alloc(FormatArgsExpr::new(
+            data,
+            self.to_span_id(format_args.span),
+            self.to_format_pieces(&format_args),
+            args,
+        ))))
+    }
+
+    fn to_format_pieces(&self, format_args: &FormatArgsInfo) -> &'ast [FormatPieceKind<'ast>] {
+        // The AST only stores spans for placeholders. The spans of literal pieces
+        // are the gaps between them, if the format string is a literal.
+        let content = self.to_format_str_content(format_args.span);
+        let mut start = content.map(rustc_span::Span::lo);
+
+        let template = &format_args.template;
+        self.alloc_slice(template.iter().enumerate().map(|(index, piece)| match piece {
+            rustc_ast::FormatArgsPiece::Literal(sym) => {
+                let end = match template.get(index + 1) {
+                    Some(rustc_ast::FormatArgsPiece::Placeholder(next)) => next.span.map(rustc_span::Span::lo),
+                    Some(rustc_ast::FormatArgsPiece::Literal(_)) => None,
+                    None => content.map(rustc_span::Span::hi),
+                };
+                let span = start
+                    .zip(end)
+                    .map(|(lo, hi)| self.to_span_id(format_args.span.with_lo(lo).with_hi(hi)));
+                start = None;
+                FormatPieceKind::Lit(self.alloc(FormatLitPiece::new(self.to_symbol_id(*sym), span)))
+            },
+            rustc_ast::FormatArgsPiece::Placeholder(placeholder) => {
+                start = placeholder.span.map(rustc_span::Span::hi);
+                FormatPieceKind::Placeholder(self.alloc(self.to_format_placeholder(placeholder)))
+            },
+        }))
+    }
+
+    /// Returns the span of the format string content, without quotes, if the
+    /// format string is a string literal.
+    fn to_format_str_content(&self, span: rustc_span::Span) -> Option<rustc_span::Span> {
+        let snippet = self.rustc_cx.sess.source_map().span_to_snippet(span).ok()?;
+        let (prefix, suffix) = if snippet.starts_with('"') {
+            (1, 1)
+        } else if let Some(raw) = snippet.strip_prefix('r') {
+            let hashes = raw.len() - raw.trim_start_matches('#').len();
+            (hashes + 2, hashes + 1)
+        } else {
+            return None;
+        };
+        if snippet.len() < prefix + suffix
+            || !snippet[..prefix].ends_with('"')
+            || !snippet[snippet.len() - suffix..].starts_with('"')
+        {
+            return None;
+        }
+
+        Some(
+            span.with_lo(span.lo() + rustc_span::BytePos(u32::try_from(prefix).ok()?))
+                .with_hi(span.hi() - rustc_span::BytePos(u32::try_from(suffix).ok()?)),
+        )
+    }
+
+    fn to_format_placeholder(&self, placeholder: &rustc_ast::FormatPlaceholder) -> FormatPlaceholder<'ast> {
+        let format_trait = match placeholder.format_trait {
+            rustc_ast::FormatTrait::Display => FormatTrait::Display,
+            rustc_ast::FormatTrait::Debug => FormatTrait::Debug,
+            rustc_ast::FormatTrait::LowerExp => FormatTrait::LowerExp,
+            rustc_ast::FormatTrait::UpperExp => FormatTrait::UpperExp,
+            rustc_ast::FormatTrait::Octal => FormatTrait::Octal,
+            rustc_ast::FormatTrait::Pointer => FormatTrait::Pointer,
+            rustc_ast::FormatTrait::Binary => FormatTrait::Binary,
+            rustc_ast::FormatTrait::LowerHex => FormatTrait::LowerHex,
+            rustc_ast::FormatTrait::UpperHex => FormatTrait::UpperHex,
+        };
+        let options = &placeholder.format_options;
+        let spec = FormatSpec::builder()
+            .width(options.width.as_ref().map(|count| self.to_format_count(count)))
+            .precision(options.precision.as_ref().map(|count| self.to_format_count(count)))
+            .align(options.alignment.map(|align| match align {
+                rustc_ast::FormatAlignment::Left => FormatAlign::Left,
+                rustc_ast::FormatAlignment::Right => FormatAlign::Right,
+                rustc_ast::FormatAlignment::Center => FormatAlign::Center,
+            }))
+            .fill(options.fill)
+            .sign(options.sign.map(|sign| match sign {
+                rustc_ast::FormatSign::Plus => FormatSign::Plus,
+                rustc_ast::FormatSign::Minus => FormatSign::Minus,
+            }))
+            .alternate(options.alternate)
+            .zero_pad(options.zero_pad)
+            .debug_hex(options.debug_hex.map(|hex| match hex {
+                rustc_ast::FormatDebugHex::Lower => FormatDebugHex::Lower,
+                rustc_ast::FormatDebugHex::Upper => FormatDebugHex::Upper,
+            }))
+            .build();
+
+        FormatPlaceholder::new(
+            placeholder.span.map(|span| self.to_span_id(span)),
+            self.to_format_arg_pos(&placeholder.argument),
+            format_trait,
+            spec,
+        )
+    }
+
+    fn to_format_count(&self, count: &rustc_ast::FormatCount) -> FormatCount<'ast> {
+        match count {
+            rustc_ast::FormatCount::Literal(value) => FormatCount::Lit(*value),
+            rustc_ast::FormatCount::Argument(pos) => FormatCount::Arg(self.to_format_arg_pos(pos)),
+        }
+    }
+
+    fn to_format_arg_pos(&self, pos: &rustc_ast::FormatArgPosition) -> FormatArgPos<'ast> {
+        let kind = match pos.kind {
+            rustc_ast::FormatArgPositionKind::Implicit => FormatArgPosKind::Implicit,
+            rustc_ast::FormatArgPositionKind::Number => FormatArgPosKind::Number,
+            rustc_ast::FormatArgPositionKind::Named => FormatArgPosKind::Named,
+        };
+        FormatArgPos::new(
+            pos.index.expect("rustc would have errored"),
+            kind,
+            pos.span.map(|span| self.to_span_id(span)),
+        )
+    }
+
     pub fn to_const_expr(&self, anon: hir::AnonConst) -> ConstExpr<'ast> {
         let body = self.rustc_cx.hir().body(anon.body);
         self.with_body(body.id(), || ConstExpr::new(self.to_expr(body.value)))
@@ -1,7 +1,7 @@
 use marker_api::ast::{
-    BindingArg, ConstArg, ConstParam, GenericArgKind, GenericArgs, GenericParamKind, GenericParams, Lifetime,
-    LifetimeArg, LifetimeClause, LifetimeKind, LifetimeParam, TraitBound, TyArg, TyClause, TyParam, TyParamBound,
-    WhereClauseKind,
+    BindingArg, ConstArg, ConstBindingArg, ConstParam, ConstraintArg, GenericArgKind, GenericArgs, GenericParamKind,
+    GenericParams, InferArg, Lifetime, LifetimeArg, LifetimeClause, LifetimeKind, LifetimeParam, TraitBound, TyArg,
+    TyClause, TyParam, TyParamBound, WhereClauseKind,
 };
 use rustc_hir as hir;
 
@@ -50,7 +50,9 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                 rustc_hir::GenericArg::Const(arg) => Some(GenericArgKind::Const(
                     self.alloc(ConstArg::new(self.to_span_id(arg.span), self.to_const_expr(arg.value))),
                 )),
-                rustc_hir::GenericArg::Infer(_) => todo!(),
+                rustc_hir::GenericArg::Infer(arg) => Some(GenericArgKind::Infer(
+                    self.alloc(InferArg::new(self.to_span_id(arg.span))),
+                )),
             })
             .collect();
         args.extend(rustc_args.bindings.iter().map(|binding| match &binding.kind {
@@ -62,9 +64,21 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                         self.to_syn_ty(rustc_ty),
                     )
                 })),
-                rustc_hir::Term::Const(_) => todo!(),
+                rustc_hir::Term::Const(anon) => GenericArgKind::ConstBinding(self.alloc({
+                    ConstBindingArg::new(
+                        self.to_span_id(binding.span),
+                        self.to_symbol_id(binding.ident.name),
+                        self.to_const_expr(*anon),
+                    )
+                })),
             },
-            rustc_hir::TypeBindingKind::Constraint { .. } => todo!(),
+            rustc_hir::TypeBindingKind::Constraint { bounds } => GenericArgKind::Constraint(self.alloc({
+                ConstraintArg::new(
+                    self.to_span_id(binding.span),
+                    self.to_symbol_id(binding.ident.name),
+                    self.to_syn_ty_param_bound(bounds),
+                )
+            })),
         }));
         GenericArgs::new(self.alloc_slice(args))
     }
@@ -76,13 +90,22 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
             .filter_map(|predicate| {
                 match predicate {
                     hir::WherePredicate::BoundPredicate(ty_bound) => {
-                        // FIXME Add span to API clause:
-                        // let span = to_api_span_id(ty_bound.span);
                         let params =
                             GenericParams::new(self.to_syn_generic_param_kinds(ty_bound.bound_generic_params), &[]);
                         let ty = self.to_syn_ty(ty_bound.bounded_ty);
+                        // Bounds declared on the generic parameter only span the bounds
+                        // themselves. The span is extended to include the parameter name.
+                        let span = match ty_bound.origin {
+                            hir::PredicateOrigin::GenericParam => ty_bound.bounded_ty.span.to(ty_bound.span),
+                            _ => ty_bound.span,
+                        };
                         Some(WhereClauseKind::Ty(self.alloc({
-                            TyClause::new(Some(params), ty, self.to_syn_ty_param_bound(predicate.bounds()))
+                            TyClause::new(
+                                self.to_span_id(span),
+                                Some(params),
+                                ty,
+                                self.to_syn_ty_param_bound(predicate.bounds()),
+                            )
                         })))
                     },
                     hir::WherePredicate::RegionPredicate(lifetime_bound) => {
@@ -99,11 +122,16 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                                     })
                                     .collect();
                                 let bounds = if bounds.is_empty() {
+                                    &[]
+                                } else {
                                     self.alloc_slice(bounds)
+                                };
+                                let span = if lifetime_bound.in_where_clause {
+                                    lifetime_bound.span
                                 } else {
-                                    &[]
+                                    lifetime_bound.lifetime.ident.span.to(lifetime_bound.span)
                                 };
-                                LifetimeClause::new(lifetime, bounds)
+                                LifetimeClause::new(self.to_span_id(span), lifetime, bounds)
                             }))
                         })
                     },
@@ -1,8 +1,9 @@
 use marker_api::{
     ast::{
         AdtKind, AssocItemKind, Body, CommonItemData, CommonPatData, ConstItem, EnumItem, EnumVariant, ExternBlockItem,
-        ExternCrateItem, ExternItemKind, FnItem, FnParam, IdentPat, ImplItem, ItemField, ItemKind, ModItem, PatKind,
-        StaticItem, StructItem, TraitItem, TyAliasItem, UnionItem, UnstableItem, UseItem, UseKind, Visibility,
+        ExternCrateItem, ExternItemKind, ExternTyItem, FnItem, FnParam, IdentPat, ImplItem, ItemField, ItemKind,
+        ModItem, PatKind, StaticItem, StructItem, TraitItem, TyAliasItem, UnionItem, UnstableItem, UseItem, UseKind,
+        Visibility,
     },
     common::{Abi, Constness, Mutability, Safety, Syncness},
     prelude::*,
@@ -112,10 +113,15 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                         Some(self.to_syn_ty(rustc_ty)),
                     )
                 })),
-                hir::ItemKind::OpaqueTy(_) => ItemKind::Unstable(self.alloc(UnstableItem::new(
-                    data,
-                    Some(self.to_symbol_id(rustc_span::sym::type_alias_impl_trait)),
-                ))),
+                hir::ItemKind::OpaqueTy(_) => {
+                    let reason = "`impl Trait` type alias";
+                    self.report_unsupported(reason, rustc_item.span);
+                    ItemKind::Unstable(self.alloc(UnstableItem::new(
+                        data,
+                        Some(self.to_symbol_id(rustc_span::sym::type_alias_impl_trait)),
+                        reason,
+                    )))
+                },
                 hir::ItemKind::Enum(enum_def, generics) => {
                     let variants = self.alloc_slice(enum_def.variants.iter().map(|variant| {
                         EnumVariant::new(
@@ -152,10 +158,15 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                         self.to_assoc_items(items),
                     )
                 })),
-                hir::ItemKind::TraitAlias(_, _) => ItemKind::Unstable(self.alloc(UnstableItem::new(
-                    data,
-                    Some(self.to_symbol_id(rustc_span::sym::trait_alias)),
-                ))),
+                hir::ItemKind::TraitAlias(_, _) => {
+                    let reason = "trait alias";
+                    self.report_unsupported(reason, rustc_item.span);
+                    ItemKind::Unstable(self.alloc(UnstableItem::new(
+                        data,
+                        Some(self.to_symbol_id(rustc_span::sym::trait_alias)),
+                        reason,
+                    )))
+                },
                 hir::ItemKind::Impl(imp) => ItemKind::Impl(self.alloc({
                     ImplItem::new(
                         data,
@@ -347,9 +358,7 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                 )),
                 CtorBlocker::new(),
             ),
-            hir::ForeignItemKind::Type => {
-                todo!("foreign type are currently sadly not supported. See rust-marker/marker#182")
-            },
+            hir::ForeignItemKind::Type => ExternItemKind::Ty(self.alloc(ExternTyItem::new(data)), CtorBlocker::new()),
         };
 
         self.items.borrow_mut().insert(id, item.as_item());
@@ -141,13 +141,17 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                 // Unstable:
                 // * Feature `box_patterns`
                 // * Tracking issue: rust#29641
-                PatKind::Unstable(self.alloc(UnstablePat::new(data)))
+                let reason = "box pattern";
+                self.report_unsupported(reason, pat.span);
+                PatKind::Unstable(self.alloc(UnstablePat::new(data, reason)))
             },
             rustc_hir::PatKind::Never => {
                 // Unstable:
                 // * Feature `never_patterns`
                 // * Tracking issue: rust#118155
-                PatKind::Unstable(self.alloc(UnstablePat::new(data)))
+                let reason = "never pattern";
+                self.report_unsupported(reason, pat.span);
+                PatKind::Unstable(self.alloc(UnstablePat::new(data, reason)))
             },
         }
     }
@@ -32,7 +32,7 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                     unreachable!("this will be handled by the block expr wrapping the desugar")
                 },
                 hir::LocalSource::AsyncFn | hir::LocalSource::AwaitDesugar => {
-                    eprintln!("skipping not implemented statement at: {:?}", stmt.span);
+                    self.report_unsupported("desugared `async` statement", stmt.span);
                     return None;
                 },
             },
@@ -222,6 +222,22 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
         }
     }
 
+    pub fn to_coroutine_kind(&self, kind: hir::CoroutineKind) -> CoroutineKind {
+        match kind {
+            hir::CoroutineKind::Coroutine(_) => CoroutineKind::Coroutine,
+            hir::CoroutineKind::Desugared(hir::CoroutineDesugaring::Async, _) => CoroutineKind::Async,
+            hir::CoroutineKind::Desugared(hir::CoroutineDesugaring::Gen, _) => CoroutineKind::Gen,
+            hir::CoroutineKind::Desugared(hir::CoroutineDesugaring::AsyncGen, _) => CoroutineKind::AsyncGen,
+        }
+    }
+
+    pub fn to_movability(&self, movability: hir::Movability) -> Movability {
+        match movability {
+            hir::Movability::Movable => Movability::Movable,
+            hir::Movability::Static => Movability::Static,
+        }
+    }
+
     #[must_use]
     pub fn to_abi(&self, rust_abi: rustc_target::spec::abi::Abi) -> Abi {
         match rust_abi {
@@ -245,7 +261,7 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                 self_ty.map(|ty| self.to_syn_ty(ty)),
                 None,
                 self.to_path(path),
-                self.to_path_target(&path.res),
+                self.to_path_target(&path.res, path.span),
             ),
             hir::QPath::TypeRelative(rustc_ty, segment) => {
                 // Segment and type conversion
@@ -265,7 +281,7 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                             res != hir::def::Res::Err,
                             "path resolution with `resolve()` failed for {qpath:#?}"
                         );
-                        self.to_path_target(&res)
+                        self.to_path_target(&res, segment.ident.span)
                     } else {
                         // Life is not perfect and resolving paths is hard. It would be
                         // interesting where some of the limitations from rustc come from
@@ -276,7 +292,7 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                         AstPathTarget::Unresolved
                     }
                 } else {
-                    self.to_path_target(&segment.res)
+                    self.to_path_target(&segment.res, segment.ident.span)
                 };
 
                 AstQPath::new(None, Some(marker_ty), path, res)
@@ -354,7 +370,7 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
         None
     }
 
-    fn to_path_target(&self, res: &hir::def::Res) -> AstPathTarget {
+    fn to_path_target(&self, res: &hir::def::Res, span: rustc_span::Span) -> AstPathTarget {
         match res {
             hir::def::Res::Def(
                 hir::def::DefKind::LifetimeParam | hir::def::DefKind::TyParam | hir::def::DefKind::ConstParam,
@@ -397,8 +413,14 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                 AstPathTarget::SelfTy(self.to_item_id(*self_src))
             },
             hir::def::Res::Local(id) => AstPathTarget::Var(self.to_var_id(*id)),
-            hir::def::Res::ToolMod => todo!("{res:#?}"),
-            hir::def::Res::NonMacroAttr(_) => todo!("{res:#?}"),
+            hir::def::Res::ToolMod => {
+                self.report_unsupported("path to a tool module", span);
+                AstPathTarget::Unresolved
+            },
+            hir::def::Res::NonMacroAttr(_) => {
+                self.report_unsupported("path to a non-macro attribute", span);
+                AstPathTarget::Unresolved
+            },
             hir::def::Res::Def(_, _) => {
                 unreachable!("all valid cases should be covered. This was triggered by: {res:#?}")
             },
@@ -1,4 +1,9 @@
-use marker_api::sem::{BindingArg, ConstArg, ConstValue, GenericArgKind, GenericArgs, TraitBound};
+use marker_api::sem::{
+    BindingArg, ConstArg, ConstBindingArg, ConstProjectionPredicate, ConstValue, GenericArgKind, GenericArgs, Lifetime,
+    LifetimeKind, LifetimeOutlivesPredicate, PredicateKind, ProjectionPredicate, TraitBound, TraitPredicate,
+    TyOutlivesPredicate,
+};
+use rustc_hir as hir;
 use rustc_middle as mid;
 
 use crate::conversion::marker::MarkerConverterInner;
@@ -59,7 +64,9 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                         self.to_item_id(binding.item_def_id()),
                         self.to_sem_ty(ty),
                     )))),
-                    mid::ty::TermKind::Const(_) => todo!(),
+                    mid::ty::TermKind::Const(_) => generics.push(GenericArgKind::ConstBinding(self.alloc(
+                        ConstBindingArg::new(self.to_item_id(binding.item_def_id()), ConstValue::new()),
+                    ))),
                 });
 
             marker_bounds.push(TraitBound::new(
@@ -79,3 +86,100 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
         self.alloc_slice(marker_bounds)
     }
 }
+
+impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
+    /// Converts the predicates of the given item. Generic parameters used in the
+    /// predicates are resolved using the generics of this item.
+    #[must_use]
+    pub fn to_sem_predicates(&self, def_id: hir::def_id::DefId) -> &'ast [PredicateKind<'ast>] {
+        self.with_generics_owner(def_id, || {
+            let predicates: Vec<_> = self
+                .rustc_cx
+                .predicates_of(def_id)
+                .instantiate_identity(self.rustc_cx)
+                .into_iter()
+                .filter_map(|(clause, span)| self.with_sem_span(span, || self.to_sem_predicate(clause)))
+                .collect();
+            self.alloc_slice(predicates)
+        })
+    }
+
+    #[must_use]
+    fn to_sem_predicate(&self, clause: mid::ty::Clause<'tcx>) -> Option<PredicateKind<'ast>> {
+        match clause.kind().skip_binder() {
+            mid::ty::ClauseKind::Trait(pred) => {
+                let trait_ref = pred.trait_ref;
+                Some(PredicateKind::Trait(self.alloc(TraitPredicate::new(
+                    self.to_sem_ty(trait_ref.self_ty()),
+                    self.to_sem_trait_bound_from_trait_ref(trait_ref),
+                ))))
+            },
+            mid::ty::ClauseKind::RegionOutlives(mid::ty::OutlivesPredicate(lifetime, bound)) => {
+                Some(PredicateKind::LifetimeOutlives(self.alloc(
+                    LifetimeOutlivesPredicate::new(self.to_sem_lifetime(lifetime), self.to_sem_lifetime(bound)),
+                )))
+            },
+            mid::ty::ClauseKind::TypeOutlives(mid::ty::OutlivesPredicate(ty, bound)) => {
+                Some(PredicateKind::TyOutlives(self.alloc(TyOutlivesPredicate::new(
+                    self.to_sem_ty(ty),
+                    self.to_sem_lifetime(bound),
+                ))))
+            },
+            mid::ty::ClauseKind::Projection(pred) => {
+                let trait_ref = pred.projection_ty.trait_ref(self.rustc_cx);
+                let self_ty = self.to_sem_ty(trait_ref.self_ty());
+                let trait_bound = self.to_sem_trait_bound_from_trait_ref(trait_ref);
+                let assoc_item = self.to_item_id(pred.projection_ty.def_id);
+                match pred.term.unpack() {
+                    mid::ty::TermKind::Ty(ty) => Some(PredicateKind::Projection(self.alloc(ProjectionPredicate::new(
+                        self_ty,
+                        trait_bound,
+                        assoc_item,
+                        self.to_sem_ty(ty),
+                    )))),
+                    mid::ty::TermKind::Const(_) => Some(PredicateKind::ConstProjection(self.alloc(
+                        ConstProjectionPredicate::new(self_ty, trait_bound, assoc_item, ConstValue::new()),
+                    ))),
+                }
+            },
+            mid::ty::ClauseKind::ConstArgHasType(..)
+            | mid::ty::ClauseKind::WellFormed(_)
+            | mid::ty::ClauseKind::ConstEvaluatable(_) => None,
+        }
+    }
+
+    #[must_use]
+    fn to_sem_trait_bound_from_trait_ref(&self, trait_ref: mid::ty::TraitRef<'tcx>) -> TraitBound<'ast> {
+        // The first generic argument is the `Self` type, which is not part
+        // of the bound itself.
+        TraitBound::new(
+            false,
+            self.to_ty_def_id(trait_ref.def_id),
+            self.to_sem_generic_args(&trait_ref.args[1..]),
+        )
+    }
+
+    #[must_use]
+    pub fn to_sem_lifetime(&self, region: mid::ty::Region<'tcx>) -> Lifetime<'ast> {
+        let kind = match region.kind() {
+            mid::ty::RegionKind::ReStatic => LifetimeKind::Static,
+            mid::ty::RegionKind::ReEarlyParam(param) => {
+                let generics = self.rustc_cx.generics_of(self.generics_owner());
+                LifetimeKind::Param(self.to_generic_id(generics.region_param(&param, self.rustc_cx).def_id))
+            },
+            mid::ty::RegionKind::ReBound(
+                _,
+                mid::ty::BoundRegion {
+                    kind: mid::ty::BoundRegionKind::BrNamed(def_id, _),
+                    ..
+                },
+            )
+            | mid::ty::RegionKind::ReLateParam(mid::ty::LateParamRegion {
+                bound_region: mid::ty::BoundRegionKind::BrNamed(def_id, _),
+                ..
+            }) => LifetimeKind::Param(self.to_generic_id(def_id)),
+            _ => LifetimeKind::Other,
+        };
+        Lifetime::new(kind)
+    }
+}
@@ -1,8 +1,8 @@
 use marker_api::{
     common::{NumKind, TextKind},
     sem::{
-        self, AdtTy, AliasTy, ArrayTy, BoolTy, ClosureTy, ConstValue, FnPtrTy, FnTy, GenericTy, NeverTy, NumTy,
-        RawPtrTy, RefTy, SliceTy, TextTy, TraitObjTy, TupleTy, TyKind, UnstableTy,
+        self, AdtTy, AliasTy, ArrayTy, BoolTy, ClosureTy, ConstValue, CoroutineTy, FnPtrTy, FnTy, ForeignTy, GenericTy,
+        NeverTy, NumTy, RawPtrTy, RefTy, SliceTy, TextTy, TraitObjTy, TupleTy, TyKind, UnstableTy,
     },
 };
 use rustc_middle as mid;
@@ -64,9 +64,14 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                         .build(),
                 ),
             ),
-            mid::ty::TyKind::Foreign(_) => {
-                todo!("foreign type are currently sadly not supported. See rust-marker/marker#182")
-            },
+            mid::ty::TyKind::Foreign(def_id) => TyKind::Foreign(
+                self.alloc(
+                    ForeignTy::builder()
+                        .data(data)
+                        .def_id(self.to_ty_def_id(*def_id))
+                        .build(),
+                ),
+            ),
             mid::ty::TyKind::Array(inner, _len) => TyKind::Array(
                 self.alloc(
                     ArrayTy::builder()
@@ -133,19 +138,18 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                         .build(),
                 ),
             ),
-            mid::ty::TyKind::Dynamic(binders, _region, kind) => {
-                if !matches!(kind, mid::ty::DynKind::Dyn) {
-                    unimplemented!("the docs are not totally clear, when `DynStar` is used, her it is: {rustc_ty:#?}")
-                }
-                TyKind::TraitObj(
-                    self.alloc(
-                        TraitObjTy::builder()
-                            .data(data)
-                            .bounds(self.to_sem_trait_bounds(binders))
-                            .build(),
-                    ),
-                )
+            mid::ty::TyKind::Dynamic(_, _, mid::ty::DynKind::DynStar) => {
+                self.report_unsupported_sem("`dyn*` trait object");
+                TyKind::Unstable(self.alloc(UnstableTy::builder().data(data).reason("`dyn*` trait object").build()))
             },
+            mid::ty::TyKind::Dynamic(binders, _region, mid::ty::DynKind::Dyn) => TyKind::TraitObj(
+                self.alloc(
+                    TraitObjTy::builder()
+                        .data(data)
+                        .bounds(self.to_sem_trait_bounds(binders))
+                        .build(),
+                ),
+            ),
             mid::ty::TyKind::Closure(id, generics) => TyKind::Closure(
                 self.alloc(
                     ClosureTy::builder()
@@ -155,8 +159,26 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                         .build(),
                 ),
             ),
-            mid::ty::TyKind::Coroutine(_, _, _) | mid::ty::TyKind::CoroutineWitness(_, _) => {
-                TyKind::Unstable(self.alloc(UnstableTy::builder().data(data).build()))
+            mid::ty::TyKind::Coroutine(id, generics, movability) => TyKind::Coroutine(
+                self.alloc(
+                    CoroutineTy::builder()
+                        .data(data)
+                        .def_id(self.to_ty_def_id(*id))
+                        .kind(
+                            self.to_coroutine_kind(
+                                self.rustc_cx
+                                    .coroutine_kind(*id)
+                                    .expect("every coroutine type has a coroutine kind"),
+                            ),
+                        )
+                        .movability(self.to_movability(*movability))
+                        .generics(self.to_sem_generic_args(generics))
+                        .build(),
+                ),
+            ),
+            mid::ty::TyKind::CoroutineWitness(_, _) => {
+                self.report_unsupported_sem("coroutine witness");
+                TyKind::Unstable(self.alloc(UnstableTy::builder().data(data).reason("coroutine witness").build()))
             },
             mid::ty::TyKind::Never => TyKind::Never(self.alloc(NeverTy::builder().data(data).build())),
             mid::ty::TyKind::Alias(_, info) => TyKind::Alias(
@@ -168,17 +190,9 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                 ),
             ),
             mid::ty::TyKind::Param(param) => {
-                let body_id = self
-                    .rustc_body
-                    .borrow()
-                    .expect("semantic `TyKind::Param` is only valid inside bodies");
-                // This is a local id, this makes sense, since rustc only accesses
-                // expressions and therefore semantic types of the current crate.
-                // This should be fine...
-                let owner = self.rustc_cx.hir().body_owner_def_id(body_id);
                 let generic_info = self
                     .rustc_cx
-                    .generics_of(owner.to_def_id())
+                    .generics_of(self.generics_owner())
                     .type_param(param, self.rustc_cx);
                 TyKind::Generic(
                     self.alloc(
@@ -1,7 +1,11 @@
 use marker_api::{
     prelude::Span,
-    span::{ExpnInfo, FileInfo, FilePos, SpanPos, SpanSource},
+    span::{
+        Comment, CommentKind, DocStyle, ExpnInfo, FileInfo, FilePos, MacroInfo, MacroKind, SourceFile, SpanPos,
+        SpanSource, Token, TokenKind,
+    },
 };
+use rustc_hir as hir;
 
 use crate::conversion::marker::MarkerConverterInner;
 
@@ -35,18 +39,9 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
         }
 
         let src_file = self.rustc_cx.sess.source_map().lookup_source_file(rust_span.lo());
-        let name = match &src_file.name {
-            rustc_span::FileName::Real(
-                rustc_span::RealFileName::LocalPath(file_path)
-                | rustc_span::RealFileName::Remapped {
-                    virtual_name: file_path,
-                    ..
-                },
-            ) => file_path.to_string_lossy().into_owned(),
-            _ => {
-                format!("MarkerConverter::to_span_source(): Unexpected file name: {rust_span:#?} -> {src_file:#?}")
-            },
-        };
+        let name = file_name(&src_file).unwrap_or_else(|| {
+            format!("MarkerConverter::to_span_source(): Unexpected file name: {rust_span:#?} -> {src_file:#?}")
+        });
         SpanSource::File(self.alloc(FileInfo::new(self.storage.alloc_str(&name), self.to_span_src_id(ctxt))))
     }
 
@@ -55,14 +50,83 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
     }
 
     pub fn to_expn_info(&self, data: &rustc_span::ExpnData) -> ExpnInfo<'ast> {
-        debug_assert!(
-            matches!(&data.kind, rustc_span::ExpnKind::Macro(_, _)),
-            "this expansion data doesn't belong to a macro: {data:#?}"
-        );
+        let rustc_span::ExpnKind::Macro(kind, _) = data.kind else {
+            unreachable!("this expansion data doesn't belong to a macro: {data:#?}")
+        };
         ExpnInfo::new(
             self.to_expn_id(data.parent),
             self.to_span_id(data.call_site),
             self.to_macro_id(data.macro_def_id.expect("filled, because this belongs to a macro")),
+            self.to_macro_kind(kind),
+        )
+    }
+
+    pub fn to_macro_kind(&self, kind: rustc_span::hygiene::MacroKind) -> MacroKind {
+        match kind {
+            rustc_span::hygiene::MacroKind::Bang => MacroKind::Bang,
+            rustc_span::hygiene::MacroKind::Attr => MacroKind::Attr,
+            rustc_span::hygiene::MacroKind::Derive => MacroKind::Derive,
+        }
+    }
+
+    pub fn to_macro_info(&self, def_id: hir::def_id::DefId) -> MacroInfo<'ast> {
+        let hir::def::DefKind::Macro(kind) = self.rustc_cx.def_kind(def_id) else {
+            unreachable!("the id doesn't belong to a macro: {def_id:#?}")
+        };
+        MacroInfo::builder()
+            .name(self.to_symbol_id(self.rustc_cx.item_name(def_id)))
+            .path(self.storage.alloc_str(&self.rustc_cx.def_path_str(def_id)))
+            .kind(self.to_macro_kind(kind))
+            .is_builtin(self.rustc_cx.has_attr(def_id, rustc_span::sym::rustc_builtin_macro))
+            .crate_id(self.to_crate_id(def_id.krate))
+            .crate_name(self.to_symbol_id(self.rustc_cx.crate_name(def_id.krate)))
+            .is_local(def_id.is_local())
+            .is_from_workspace(self.is_from_workspace(def_id))
+            .build()
+    }
+
+    /// Cargo-marker passes the crate names of all workspace members to the
+    /// driver. A macro from an external crate is part of the workspace, if its
+    /// crate is one of them. Only local macros are part of the workspace, if
+    /// the list is not available.
+    fn is_from_workspace(&self, def_id: hir::def_id::DefId) -> bool {
+        def_id.is_local()
+            || self
+                .workspace_crates
+                .contains(self.rustc_cx.crate_name(def_id.krate).as_str())
+    }
+
+    /// Converts a source file, which defines the given module. Returns [`None`],
+    /// if the file is not a real file or its source is unavailable.
+    pub fn to_source_file(
+        &self,
+        file: &rustc_span::SourceFile,
+        module: hir::def_id::LocalDefId,
+    ) -> Option<SourceFile<'ast>> {
+        let path = file_name(file)?;
+        let src = file.src.as_ref()?;
+
+        let crate_name = self.rustc_cx.crate_name(hir::def_id::LOCAL_CRATE);
+        let module_path = self.rustc_cx.def_path_str(module);
+        let module_path = if module_path.is_empty() {
+            crate_name.to_string()
+        } else {
+            format!("{crate_name}::{module_path}")
+        };
+        let line_starts = file
+            .lines()
+            .iter()
+            .map(|line| self.to_span_pos(file.absolute_position(*line)));
+
+        Some(
+            SourceFile::builder()
+                .path(self.storage.alloc_str(&path))
+                .module_path(self.storage.alloc_str(&module_path))
+                .module_id(self.to_item_id(module.to_def_id()))
+                .text(self.storage.alloc_str(src))
+                .line_starts(self.alloc_slice(line_starts))
+                .span(self.to_span_id(rustc_span::Span::with_root_ctxt(file.start_pos, file.end_position())))
+                .build(),
         )
     }
 
@@ -74,4 +138,116 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
     fn to_file_pos(&self, loc: &rustc_span::Loc) -> FilePos<'ast> {
         FilePos::new(loc.line, loc.col.0 + 1)
     }
+
+    /// Converts a comment token from [`rustc_lexer`]. The given `text` is the
+    /// source text of the token, including the comment delimiters. Returns
+    /// [`None`], if the token is not a comment.
+    pub fn to_comment(
+        &self,
+        kind: rustc_lexer::TokenKind,
+        text: &str,
+        span: rustc_span::Span,
+    ) -> Option<Comment<'ast>> {
+        let (kind, doc_style, text) = match kind {
+            rustc_lexer::TokenKind::LineComment { doc_style } => {
+                let prefix = if doc_style.is_some() { 3 } else { 2 };
+                (CommentKind::Line, doc_style, &text[prefix..])
+            },
+            rustc_lexer::TokenKind::BlockComment { doc_style, terminated } => {
+                let prefix = if doc_style.is_some() { 3 } else { 2 };
+                let suffix = if terminated { 2 } else { 0 };
+                (CommentKind::Block, doc_style, &text[prefix..text.len() - suffix])
+            },
+            _ => return None,
+        };
+        let doc_style = doc_style.map(|style| match style {
+            rustc_lexer::DocStyle::Outer => DocStyle::Outer,
+            rustc_lexer::DocStyle::Inner => DocStyle::Inner,
+        });
+        Some(Comment::new(
+            kind,
+            doc_style,
+            self.storage.alloc_str(text),
+            self.to_span_id(span),
+        ))
+    }
+
+    pub fn to_token(&self, kind: rustc_lexer::TokenKind, text: &str, span: rustc_span::Span) -> Token<'ast> {
+        let kind = match kind {
+            rustc_lexer::TokenKind::LineComment { .. } => TokenKind::LineComment,
+            rustc_lexer::TokenKind::BlockComment { .. } => TokenKind::BlockComment,
+            rustc_lexer::TokenKind::Whitespace => TokenKind::Whitespace,
+            rustc_lexer::TokenKind::Ident => TokenKind::Ident,
+            rustc_lexer::TokenKind::RawIdent => TokenKind::RawIdent,
+            rustc_lexer::TokenKind::Literal { .. } => TokenKind::Literal,
+            rustc_lexer::TokenKind::Lifetime { .. } => TokenKind::Lifetime,
+            rustc_lexer::TokenKind::Semi => TokenKind::Semi,
+            rustc_lexer::TokenKind::Comma => TokenKind::Comma,
+            rustc_lexer::TokenKind::Dot => TokenKind::Dot,
+            rustc_lexer::TokenKind::OpenParen => TokenKind::OpenParen,
+            rustc_lexer::TokenKind::CloseParen => TokenKind::CloseParen,
+            rustc_lexer::TokenKind::OpenBrace => TokenKind::OpenBrace,
+            rustc_lexer::TokenKind::CloseBrace => TokenKind::CloseBrace,
+            rustc_lexer::TokenKind::OpenBracket => TokenKind::OpenBracket,
+            rustc_lexer::TokenKind::CloseBracket => TokenKind::CloseBracket,
+            rustc_lexer::TokenKind::At => TokenKind::At,
+            rustc_lexer::TokenKind::Pound => TokenKind::Pound,
+            rustc_lexer::TokenKind::Tilde => TokenKind::Tilde,
+            rustc_lexer::TokenKind::Question => TokenKind::Question,
+            rustc_lexer::TokenKind::Colon => TokenKind::Colon,
+            rustc_lexer::TokenKind::Dollar => TokenKind::Dollar,
+            rustc_lexer::TokenKind::Eq => TokenKind::Eq,
+            rustc_lexer::TokenKind::Bang => TokenKind::Bang,
+            rustc_lexer::TokenKind::Lt => TokenKind::Lt,
+            rustc_lexer::TokenKind::Gt => TokenKind::Gt,
+            rustc_lexer::TokenKind::Minus => TokenKind::Minus,
+            rustc_lexer::TokenKind::And => TokenKind::And,
+            rustc_lexer::TokenKind::Or => TokenKind::Or,
+            rustc_lexer::TokenKind::Plus => TokenKind::Plus,
+            rustc_lexer::TokenKind::Star => TokenKind::Star,
+            rustc_lexer::TokenKind::Slash => TokenKind::Slash,
+            rustc_lexer::TokenKind::Caret => TokenKind::Caret,
+            rustc_lexer::TokenKind::Percent => TokenKind::Percent,
+            rustc_lexer::TokenKind::InvalidIdent
+            | rustc_lexer::TokenKind::UnknownPrefix
+            | rustc_lexer::TokenKind::Unknown
+            | rustc_lexer::TokenKind::Eof => TokenKind::Unknown,
+        };
+        Token::new(kind, self.storage.alloc_str(text), self.to_span_id(span))
+    }
+
+    /// Converts a doc comment attribute. Returns [`None`], if the attribute
+    /// is not a doc comment.
+    pub fn to_doc_comment(&self, attr: &rustc_ast::Attribute) -> Option<Comment<'ast>> {
+        let rustc_ast::AttrKind::DocComment(kind, sym) = attr.kind else {
+            return None;
+        };
+        let kind = match kind {
+            rustc_ast::token::CommentKind::Line => CommentKind::Line,
+            rustc_ast::token::CommentKind::Block => CommentKind::Block,
+        };
+        let doc_style = match attr.style {
+            rustc_ast::AttrStyle::Outer => DocStyle::Outer,
+            rustc_ast::AttrStyle::Inner => DocStyle::Inner,
+        };
+        Some(Comment::new(
+            kind,
+            Some(doc_style),
+            self.storage.alloc_str(sym.as_str()),
+            self.to_span_id(attr.span),
+        ))
+    }
+}
+
+fn file_name(file: &rustc_span::SourceFile) -> Option<String> {
+    match &file.name {
+        rustc_span::FileName::Real(
+            rustc_span::RealFileName::LocalPath(file_path)
+            | rustc_span::RealFileName::Remapped {
+                virtual_name: file_path,
+                ..
+            },
+        ) => Some(file_path.to_string_lossy().into_owned()),
+        _ => None,
+    }
 }
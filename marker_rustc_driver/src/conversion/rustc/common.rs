@@ -1,7 +1,7 @@
 use std::mem::{size_of, transmute};
 
 use marker_api::{
-    common::{CrateId, DriverTyId, ExpnId, Level, SpanId, SpanSrcId, SymbolId},
+    common::{CrateId, DriverTyId, ExpnId, Level, MacroId, SpanId, SpanSrcId, SymbolId},
     diagnostic::Applicability,
     prelude::*,
     span::SpanPos,
@@ -30,6 +30,7 @@ macro_rules! impl_into_def_id_for {
 
 impl_into_def_id_for!(GenericId);
 impl_into_def_id_for!(ItemId);
+impl_into_def_id_for!(MacroId);
 impl_into_def_id_for!(TyDefId);
 impl_into_def_id_for!(VariantId);
 
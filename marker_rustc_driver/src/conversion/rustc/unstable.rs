@@ -20,9 +20,12 @@ impl<'ast, 'tcx> RustcConverter<'ast, 'tcx> {
             // see that it actually has the `'static` lifetime
             let lint: &'static rustc_lint::Lint = lints.borrow_mut().entry(api_lint).or_insert_with(move || {
                 // Not extracted to an extra function, as it's very specific
+                // Marker's API filters emissions in macros, based on the `MacroReport`
+                // value. Rustc's filter has to be disabled for all values, that
+                // allow any external macros.
                 let report_in_external_macro = match api_lint.report_in_macro {
                     MacroReport::No => false,
-                    MacroReport::All => true,
+                    MacroReport::Local | MacroReport::Crates(_) | MacroReport::All => true,
                     _ => unreachable!(),
                 };
 
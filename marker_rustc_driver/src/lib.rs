@@ -23,23 +23,28 @@ extern crate rustc_errors;
 extern crate rustc_hash;
 extern crate rustc_hir;
 extern crate rustc_hir_analysis;
+extern crate rustc_infer;
 extern crate rustc_interface;
+extern crate rustc_lexer;
 extern crate rustc_lint;
 extern crate rustc_lint_defs;
 extern crate rustc_middle;
 extern crate rustc_session;
 extern crate rustc_span;
 extern crate rustc_target;
+extern crate rustc_trait_selection;
 
 pub mod context;
 pub mod conversion;
 pub mod lint_pass;
 
+use std::collections::BTreeMap;
 use std::env;
 use std::ops::Deref;
 
 use camino::Utf8Path;
-use marker_adapter::{LintCrateInfo, LINT_CRATES_ENV};
+use marker_adapter::{LintCrateInfo, LintLevelInfo, LINT_CRATES_ENV, LINT_LEVELS_ENV, PACKAGE_LINT_CRATES_ENV};
+use marker_api::common::Level;
 use marker_error::Context;
 
 use crate::conversion::rustc::RustcConverter;
@@ -49,6 +54,16 @@ const RUSTC_TOOLCHAIN_VERSION: &str = "nightly-2023-12-28";
 // endregion replace rust toolchain dev
 
 pub const MARKER_SYSROOT_ENV: &str = "MARKER_SYSROOT";
+/// If this environment value is set, every node, that can't be represented by
+/// Marker's API, will be reported with a note. See `cargo marker --report-unsupported`
+pub const MARKER_REPORT_UNSUPPORTED_ENV: &str = "MARKER_REPORT_UNSUPPORTED";
+/// A comma separated list of the crate names of all workspace members. This is
+/// used to determine, if a macro was defined in the linted workspace.
+pub const MARKER_WORKSPACE_CRATES_ENV: &str = "MARKER_WORKSPACE_CRATES";
+/// If this environment value is set, rustc's flattening of nested `format_args!`
+/// calls and inlining of literal arguments is disabled. See
+/// `cargo marker --unflatten-format-args`
+pub const MARKER_UNFLATTEN_FORMAT_ARGS_ENV: &str = "MARKER_UNFLATTEN_FORMAT_ARGS";
 
 struct DefaultCallbacks {
     env_vars: Vec<&'static str>,
@@ -101,6 +116,37 @@ impl rustc_driver::Callbacks for MarkerCallback {
 
             lint_store.register_lints(&lints);
 
+            // The lints of every lint crate are registered as a group, named
+            // like `marker::lint_crate::*`. This allows users to set the level
+            // of all lints of a lint crate, via the command line.
+            let mut crate_groups: BTreeMap<&str, Vec<_>> = BTreeMap::new();
+            for lint in &lints {
+                if let Some(krate) = lint.name.split("::").nth(1) {
+                    crate_groups
+                        .entry(krate)
+                        .or_default()
+                        .push(rustc_lint::LintId::of(lint));
+                }
+            }
+            for (krate, lint_ids) in crate_groups {
+                let name: &'static str = Box::leak(format!("marker::{krate}::*").into_boxed_str());
+                lint_store.register_group(true, name, None, lint_ids);
+            }
+
+            // Register the lint groups declared by lint crates. Rustc expects
+            // lowercase names, like it does for lints.
+            for group in lint_pass::RustcLintPass::marker_lint_groups() {
+                let lint_ids = group
+                    .lints
+                    .get()
+                    .iter()
+                    .map(|lint| rustc_lint::LintId::of(RustcConverter::static_to_lint(lint)))
+                    .collect();
+                let name: &'static str = Box::leak(group.name.to_ascii_lowercase().into_boxed_str());
+                lint_store.register_group(true, name, None, lint_ids);
+            }
+
+            lint_store.register_early_pass(|| Box::new(lint_pass::AstCollector));
             lint_store.register_late_pass(|_| Box::new(lint_pass::RustcLintPass));
         }));
     }
@@ -272,7 +318,15 @@ pub fn try_main(args: impl Iterator<Item = String>) -> Result<(), MainError> {
     let in_primary_package = env::var("CARGO_PRIMARY_PACKAGE").is_ok();
 
     let enable_marker = !cap_lints_allow && (!no_deps || in_primary_package);
-    let env_vars = vec![LINT_CRATES_ENV, MARKER_SYSROOT_ENV];
+    let env_vars = vec![
+        LINT_CRATES_ENV,
+        PACKAGE_LINT_CRATES_ENV,
+        LINT_LEVELS_ENV,
+        MARKER_SYSROOT_ENV,
+        MARKER_REPORT_UNSUPPORTED_ENV,
+        MARKER_WORKSPACE_CRATES_ENV,
+        MARKER_UNFLATTEN_FORMAT_ARGS_ENV,
+    ];
     if !enable_marker {
         rustc_driver::RunCompiler::new(&orig_args, &mut DefaultCallbacks { env_vars }).run()?;
         return Ok(());
@@ -281,6 +335,7 @@ pub fn try_main(args: impl Iterator<Item = String>) -> Result<(), MainError> {
     let lint_crates = LintCrateInfo::list_from_env()
         .context(|| "Error while determining the lint crates to load")?
         .unwrap_or_default();
+    let lint_levels = LintLevelInfo::list_from_env().context(|| "Error while determining the lint levels")?;
 
     let additional_args = [
         // Make it possible to use `#[allow(marker::{lint_name})]` without
@@ -294,12 +349,34 @@ pub fn try_main(args: impl Iterator<Item = String>) -> Result<(), MainError> {
     ]
     .into_iter()
     .map(str::to_string)
+    // Rustc flattens nested `format_args!` calls and inlines literal arguments
+    // during the HIR lowering. Disabling this keeps all arguments in the HIR,
+    // which is required to create `FormatArgsExpr`s for these calls.
+    .chain(
+        env::var_os(MARKER_UNFLATTEN_FORMAT_ARGS_ENV)
+            .is_some()
+            .then(|| "-Zflatten-format-args=no".to_string()),
+    )
     .chain(
         lint_crates
             .iter()
             .map(|krate| format!("--cfg=marker=\"{}\"", krate.name)),
     );
 
+    // The lint levels are inserted before the arguments from Cargo. Like
+    // Cargo's `[lints]` table, they can be overridden by `RUSTFLAGS`.
+    let level_args = lint_levels.iter().map(|info| {
+        let flag = match info.level {
+            Level::Allow => "-A",
+            Level::Warn => "-W",
+            Level::Deny => "-D",
+            Level::Forbid => "-F",
+            _ => unreachable!("all levels are covered"),
+        };
+        format!("{flag}{}", info.lint)
+    });
+    orig_args.splice(1..1, level_args);
+
     orig_args.extend(additional_args);
 
     let mut callback = MarkerCallback { env_vars, lint_crates };
@@ -1,7 +1,11 @@
-use std::cell::OnceCell;
+use std::{
+    cell::{OnceCell, RefCell},
+    rc::Rc,
+};
 
 use marker_adapter::{Adapter, LintCrateInfo};
-use marker_api::Lint;
+use marker_api::{Lint, LintGroup};
+use rustc_hash::FxHashMap;
 
 use crate::context::{storage::Storage, RustcContext};
 
@@ -19,6 +23,83 @@ thread_local! {
     /// only single threaded. This cell will therefore only be constructed once, and
     /// this driver will always use the same adapter.
     static ADAPTER: OnceCell<Adapter> = OnceCell::new();
+
+    /// The HIR lowering of `format_args!` loses most information about the format
+    /// string. This map stores the relevant parts of all `format_args!` calls,
+    /// collected by the [`AstCollector`]. The key is the span of the
+    /// `format_args!` expression, without a parent.
+    ///
+    /// The map is cleared at the end of the late lint pass.
+    static FORMAT_ARGS: RefCell<FxHashMap<rustc_span::Span, Rc<FormatArgsInfo>>> = RefCell::default();
+
+    /// The HIR lowering of `asm!` replaces `clobber_abi(..)` arguments with the
+    /// clobbered registers. This map stores the ABI names and spans of all `asm!`
+    /// expressions, using the same keys as [`FORMAT_ARGS`].
+    ///
+    /// The map is cleared at the end of the late lint pass.
+    static ASM_CLOBBER_ABIS: RefCell<FxHashMap<rustc_span::Span, Vec<(rustc_span::Symbol, rustc_span::Span)>>> =
+        RefCell::default();
+}
+
+/// The parts of an AST `format_args!` node, which are required to create a
+/// [`FormatArgsExpr`](marker_api::ast::FormatArgsExpr). The argument expressions
+/// are not stored, they're taken from the HIR instead.
+#[derive(Debug)]
+pub struct FormatArgsInfo {
+    /// The span of the format string.
+    pub span: rustc_span::Span,
+    pub template: Vec<rustc_ast::FormatArgsPiece>,
+    /// The kind and expression span of every argument.
+    pub args: Vec<(rustc_ast::FormatArgumentKind, rustc_span::Span)>,
+}
+
+/// Returns the AST information of the `format_args!` call, which created
+/// the expression with the given span.
+#[must_use]
+pub fn format_args_at(span: rustc_span::Span) -> Option<Rc<FormatArgsInfo>> {
+    FORMAT_ARGS.with(|map| map.borrow().get(&span.with_parent(None)).cloned())
+}
+
+/// Returns the names and spans of the `clobber_abi(..)` arguments of the `asm!`
+/// expression with the given span.
+#[must_use]
+pub fn asm_clobber_abis_at(span: rustc_span::Span) -> Vec<(rustc_span::Symbol, rustc_span::Span)> {
+    ASM_CLOBBER_ABIS.with(|map| map.borrow().get(&span.with_parent(None)).cloned().unwrap_or_default())
+}
+
+/// An early lint pass, which collects information from the AST, which is lost
+/// during the lowering to HIR, like the format string of `format_args!` calls.
+pub struct AstCollector;
+
+rustc_lint_defs::impl_lint_pass!(AstCollector => []);
+
+impl rustc_lint::EarlyLintPass for AstCollector {
+    fn check_expr(&mut self, _cx: &rustc_lint::EarlyContext<'_>, expr: &rustc_ast::Expr) {
+        match &expr.kind {
+            rustc_ast::ExprKind::FormatArgs(format_args) => {
+                let info = FormatArgsInfo {
+                    span: format_args.span,
+                    template: format_args.template.clone(),
+                    args: format_args
+                        .arguments
+                        .all_args()
+                        .iter()
+                        .map(|arg| (arg.kind.clone(), arg.expr.span))
+                        .collect(),
+                };
+                FORMAT_ARGS.with(|map| {
+                    map.borrow_mut().insert(expr.span.with_parent(None), Rc::new(info));
+                });
+            },
+            rustc_ast::ExprKind::InlineAsm(asm) if !asm.clobber_abis.is_empty() => {
+                ASM_CLOBBER_ABIS.with(|map| {
+                    map.borrow_mut()
+                        .insert(expr.span.with_parent(None), asm.clobber_abis.clone());
+                });
+            },
+            _ => {},
+        }
+    }
 }
 
 pub struct RustcLintPass;
@@ -36,6 +117,11 @@ impl RustcLintPass {
     pub fn marker_lints() -> Vec<&'static Lint> {
         ADAPTER.with(|adapter| adapter.get().unwrap().marker_lints())
     }
+
+    #[must_use]
+    pub fn marker_lint_groups() -> Vec<&'static LintGroup> {
+        ADAPTER.with(|adapter| adapter.get().unwrap().marker_lint_groups())
+    }
 }
 
 rustc_lint_defs::impl_lint_pass!(RustcLintPass => []);
@@ -46,6 +132,11 @@ impl<'tcx> rustc_lint::LateLintPass<'tcx> for RustcLintPass {
             process_crate(rustc_cx, adapter.get().unwrap());
         });
     }
+
+    fn check_crate_post(&mut self, _rustc_cx: &rustc_lint::LateContext<'tcx>) {
+        FORMAT_ARGS.with(|map| map.borrow_mut().clear());
+        ASM_CLOBBER_ABIS.with(|map| map.borrow_mut().clear());
+    }
 }
 
 pub fn process_crate(rustc_cx: &rustc_lint::LateContext<'_>, adapter: &Adapter) {
@@ -4,10 +4,11 @@
 mod utils;
 
 use marker_api::{
-    ast::{AstPathTarget, EnumVariant, ItemField, LetStmt, StaticItem},
+    ast::{AstPathTarget, EnumVariant, FormatPieceKind, ItemField, LetStmt, StaticItem},
     diagnostic::Applicability,
     prelude::*,
     sem::TyKind,
+    span::{SourceFile, SpanSource},
     LintPass, LintPassInfo, LintPassInfoBuilder,
 };
 
@@ -35,6 +36,26 @@ marker_api::declare_lint! {
     marker_api::common::MacroReport::All,
 }
 
+marker_api::declare_lint! {
+    /// # What it does
+    /// A lint used for marker's uitests.
+    ///
+    /// It's only allowed to emit code in local macros.
+    TEST_MACRO_REPORT_LOCAL,
+    Warn,
+    marker_api::common::MacroReport::Local,
+}
+
+marker_api::declare_lint! {
+    /// # What it does
+    /// A lint used for marker's uitests.
+    ///
+    /// It's only allowed to emit code in local macros and macros from `alloc`.
+    TEST_MACRO_REPORT_CRATES,
+    Warn,
+    marker_api::common::MacroReport::crates(&[marker_api::ffi::FfiStr::new("alloc")]),
+}
+
 marker_api::declare_lint! {
     /// # What it does
     /// A lint used for markers uitests.
@@ -68,6 +89,22 @@ marker_api::declare_lint! {
     Warn,
 }
 
+marker_api::declare_lint! {
+    /// # What it does
+    /// A lint to test [`LintPass::check_file`] and [`MarkerContext::source_files`].
+    /// Every source file is reported, if this lint is set to warn.
+    TEST_SOURCE_FILES,
+    Allow,
+}
+
+marker_api::declare_lint_group! {
+    /// A lint group used for marker's uitests.
+    ///
+    /// It contains the lints which are emitted for items with test names.
+    TEST_LINT_GROUP,
+    [ITEM_WITH_TEST_NAME],
+}
+
 fn emit_item_with_test_name_lint<'ast>(
     cx: &'ast MarkerContext<'ast>,
     node: impl EmissionNode<'ast>,
@@ -84,10 +121,24 @@ impl LintPass for TestLintPass {
             ITEM_WITH_TEST_NAME,
             PRINT_EVERY_EXPR,
             utils::TEST_CONTAINS_RETURN,
+            utils::TEST_STD_MACROS,
+            TEST_SOURCE_FILES,
         ]))
+        .groups(Box::new([TEST_LINT_GROUP]))
         .build()
     }
 
+    fn check_file<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, file: &'ast SourceFile<'ast>) {
+        let is_listed = cx.source_files().iter().any(|listed| listed.path() == file.path());
+        cx.emit_lint(TEST_SOURCE_FILES, file, "checking source file")
+            .span(file.line_span(1).unwrap())
+            .decorate(|diag| {
+                diag.note(format!("module path: {}, listed: {is_listed}", file.module_path()));
+                diag.note(format!("line count: {}", file.line_count()));
+                diag.note(format!("last line: {:?}", file.line(file.line_count())));
+            });
+    }
+
     fn check_item<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
         utils::check_item(cx, item);
 
@@ -105,6 +156,8 @@ impl LintPass for TestLintPass {
             check_static_item(cx, item);
         }
 
+        check_doc_comments(cx, item);
+
         if matches!(
             item.ident().map(marker_api::span::Ident::name),
             Some(name) if name.starts_with("FindMe") || name.starts_with("FIND_ME") || name.starts_with("find_me")
@@ -150,6 +203,23 @@ impl LintPass for TestLintPass {
                         diag.note(format!("Body: {:#?}", cx.ast().body(func.body_id().unwrap())));
                     });
             }
+            if item
+                .ident()
+                .map(|ident| ident.name().starts_with("print_predicates"))
+                .unwrap_or_default()
+            {
+                cx.emit_lint(TEST_LINT, item, "printing predicates").decorate(|diag| {
+                    diag.span(item.ident().unwrap().span());
+                    let clauses: Vec<_> = func
+                        .generics()
+                        .clauses()
+                        .iter()
+                        .map(|clause| clause.span().snippet_or("..").to_string())
+                        .collect();
+                    diag.note(format!("Clauses: {clauses:#?}"));
+                    diag.note(format!("Predicates: {:#?}", cx.predicates_of(item.id())));
+                });
+            }
             if item
                 .ident()
                 .map(|name| name.name().starts_with("test_vis"))
@@ -182,6 +252,7 @@ impl LintPass for TestLintPass {
         }
     }
 
+    #[allow(clippy::too_many_lines)]
     fn check_stmt<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>) {
         // I didn't realize that `let_chains` are still unstable. This makes the
         // code significantly less readable -.-
@@ -199,6 +270,27 @@ impl LintPass for TestLintPass {
                     diag.note(format!("Snippet: {}", span.snippet_or("..")));
                     diag.note(format!("Source: {:#?}", span.source()));
                 });
+            } else if ident.name().starts_with("_macro_report") {
+                cx.emit_lint(TEST_MACRO_REPORT_LOCAL, expr, "reported with `MacroReport::Local`");
+                cx.emit_lint(TEST_MACRO_REPORT_CRATES, expr, "reported with `MacroReport::Crates`");
+            } else if ident.name().starts_with("_macro") {
+                cx.emit_lint(PRINT_SPAN_LINT, stmt, "print macro info")
+                    .decorate(|diag| {
+                        let mut current = match expr.span().source() {
+                            SpanSource::Macro(info) => Some(info),
+                            _ => None,
+                        };
+                        while let Some(info) = current {
+                            diag.note(format!("Kind: {:?}, Info: {:#?}", info.kind(), info.macro_info()));
+                            diag.note(format!(
+                                "Name: {}, Path: {}, Crate: {}",
+                                info.macro_info().name(),
+                                info.macro_info().path(),
+                                info.macro_info().crate_name()
+                            ));
+                            current = info.parent();
+                        }
+                    });
             } else if ident.name().starts_with("_ty") {
                 cx.emit_lint(TEST_LINT, stmt, "print type test").decorate(|diag| {
                     diag.note(format!("{:#?}", expr.ty()));
@@ -224,13 +316,56 @@ impl LintPass for TestLintPass {
                     let ids = cx.resolve_ty_ids(path);
                     diag.note(format!("Is this a {:#?} -> {}", path, ids.contains(&adt.def_id())));
                 });
+            } else if ident.name().starts_with("_check_comments") {
+                cx.emit_lint(TEST_LINT, stmt, "checking comments").decorate(|diag| {
+                    for comment in cx.comments_before(stmt.span()) {
+                        diag.span_note(
+                            format!("before: {:?}: {:?}", comment.kind(), comment.text()),
+                            comment.span(),
+                        );
+                    }
+                    for comment in cx.comments_in(expr.span()) {
+                        diag.span_note(
+                            format!(
+                                "inside: {:?} (doc: {:?}): {:?}",
+                                comment.kind(),
+                                comment.doc_style(),
+                                comment.text()
+                            ),
+                            comment.span(),
+                        );
+                    }
+                });
+            } else if ident.name().starts_with("_check_tokens") {
+                cx.emit_lint(TEST_LINT, stmt, "checking tokens").decorate(|diag| {
+                    let tokens: Vec<_> = expr
+                        .span()
+                        .tokens()
+                        .iter()
+                        .map(|token| format!("{:?}({:?})", token.kind(), token.text()))
+                        .collect();
+                    diag.note(tokens.join(" "));
+                    if let Some(token) = expr.span().tokens().iter().find(|token| !token.is_trivia()) {
+                        diag.span_note("first non-trivia token", token.span());
+                    }
+                });
+            } else if ident.name().starts_with("_check_span_ops") {
+                check_span_ops(cx, stmt, expr);
+            } else if ident.name().starts_with("_check_format_args") {
+                check_format_args(cx, stmt, expr);
+            } else if ident.name().starts_with("_check_asm_clobber_abis") {
+                check_asm_clobber_abis(cx, stmt, expr);
             } else if ident.name().starts_with("_check_ast_map") {
                 check_ast_map(cx, lets);
+            } else if ident.name().starts_with("_check_impls") {
+                check_impls(cx, stmt, expr);
             }
         }
     }
 
     fn check_expr<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, expr: ExprKind<'ast>) {
+        utils::check_expr(cx, expr);
+
         cx.emit_lint(PRINT_EVERY_EXPR, expr, "expr").decorate(|diag| {
             diag.note(&format!("SpanSource: {:#?}", expr.span().source()));
             diag.note(&format!("Snippet: {:#?}", expr.span().snippet_or("<..>")));
@@ -238,6 +373,68 @@ impl LintPass for TestLintPass {
     }
 }
 
+fn check_doc_comments<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    if matches!(
+        item.ident().map(marker_api::span::Ident::name),
+        Some(name) if name.starts_with("documented")
+    ) {
+        cx.emit_lint(TEST_LINT, item, "checking doc comments").decorate(|diag| {
+            for comment in cx.doc_comments(item.id()) {
+                diag.span_note(
+                    format!("{:?}: {:?}", comment.doc_style(), comment.text()),
+                    comment.span(),
+                );
+            }
+        });
+    }
+}
+
+fn check_asm_clobber_abis<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    let ExprKind::InlineAsm(asm) = expr else {
+        cx.emit_lint(TEST_LINT, stmt, "expected an `InlineAsmExpr`");
+        return;
+    };
+    cx.emit_lint(TEST_LINT, stmt, "checking clobber ABIs").decorate(|diag| {
+        for abi in asm.clobber_abis() {
+            diag.note(format!("ABI: {} from `{}`", abi.name(), abi.span().snippet_or("..")));
+        }
+    });
+}
+
+fn check_format_args<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    let ExprKind::FormatArgs(format_args) = expr else {
+        cx.emit_lint(TEST_LINT, stmt, "expected a `FormatArgsExpr`");
+        return;
+    };
+    cx.emit_lint(TEST_LINT, stmt, "checking format args").decorate(|diag| {
+        diag.note(format!(
+            "Format string: {}",
+            format_args.format_str_span().snippet_or("..")
+        ));
+        for piece in format_args.pieces() {
+            let snippet = piece.span().map_or("<no span>", |span| span.snippet_or(".."));
+            match piece {
+                FormatPieceKind::Lit(lit) => diag.note(format!("Lit: {:?} from `{snippet}`", lit.value())),
+                FormatPieceKind::Placeholder(placeholder) => diag.note(format!(
+                    "Placeholder: `{snippet}` arg: {}, trait: {:?}, default spec: {}",
+                    placeholder.arg().index(),
+                    placeholder.format_trait(),
+                    placeholder.spec().is_default()
+                )),
+                _ => unreachable!(),
+            };
+        }
+        for arg in format_args.args() {
+            diag.note(format!(
+                "Arg: {:?} {:?} `{}`",
+                arg.kind(),
+                arg.ident().map(Ident::name),
+                arg.expr().span().snippet_or("..")
+            ));
+        }
+    });
+}
+
 fn check_ast_map<'ast>(cx: &'ast MarkerContext<'ast>, lets: &'ast LetStmt<'ast>) {
     let Some(expr) = lets.init() else { return };
 
@@ -268,6 +465,77 @@ fn check_ast_map<'ast>(cx: &'ast MarkerContext<'ast>, lets: &'ast LetStmt<'ast>)
     }
 }
 
+fn check_span_ops<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    cx.emit_lint(TEST_LINT, stmt, "checking span operations")
+        .decorate(|diag| {
+            let span = expr.span();
+            diag.note(format!("lines: {:?}", span.line_range()));
+            diag.span_note("shrink_to_lo", &span.shrink_to_lo());
+            diag.span_note("shrink_to_hi", &span.shrink_to_hi());
+            diag.span_note("expand_to_lines", &span.expand_to_lines());
+            let callsite = span.source_callsite();
+            diag.note(format!("source_callsite: {:?}", callsite.snippet()));
+            diag.note(format!("source_callsite lines: {:?}", callsite.line_range()));
+            if let ExprKind::BinaryOp(op) = expr {
+                let (left, right) = (op.left(), op.right());
+                let (left, right) = (left.span(), right.span());
+                diag.note(format!("ctxt_eq: {}", left.ctxt_eq(right)));
+                diag.span_note("to", &left.to(right));
+                diag.note(format!("between: {:?}", left.between(right).snippet()));
+            }
+        });
+}
+
+fn check_impls<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    const UNAVAILABLE: &str = "<unavailable>";
+
+    fn impl_snippets<'ast>(cx: &'ast MarkerContext<'ast>, ids: &[ItemId]) -> Vec<String> {
+        ids.iter()
+            .map(|id| match cx.ast().item(*id) {
+                Some(item) => item
+                    .span()
+                    .snippet_or("..")
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .to_string(),
+                None => UNAVAILABLE.to_string(),
+            })
+            .collect()
+    }
+
+    cx.emit_lint(TEST_LINT, stmt, "checking impls").decorate(|diag| {
+        let ty = expr.ty();
+        if let TyKind::Adt(adt) = ty {
+            // Only the local impls are printed. The number of impls from
+            // external crates, like `std`, can change between toolchains.
+            let (local, external): (Vec<_>, Vec<_>) = impl_snippets(cx, cx.inherent_impls_of(adt.def_id()))
+                .into_iter()
+                .partition(|snippet| snippet != UNAVAILABLE);
+            diag.note(format!("local inherent impls: {local:#?}"));
+            diag.note(format!("has external inherent impls: {}", !external.is_empty()));
+        }
+
+        for path in ["crate::LocalTrait", "std::clone::Clone", "std::string::ToString"] {
+            let impl_id = cx
+                .resolve_ty_ids(path)
+                .iter()
+                .find_map(|trait_id| cx.trait_impl_for(ty, *trait_id));
+            diag.note(format!(
+                "`{path}` impl: {:?}",
+                impl_id.map(|id| impl_snippets(cx, &[id]))
+            ));
+        }
+
+        let local_impls = cx
+            .resolve_ty_ids("crate::LocalTrait")
+            .iter()
+            .map(|trait_id| cx.impls_of_trait(*trait_id).len())
+            .sum::<usize>();
+        diag.note(format!("`crate::LocalTrait` impls: {local_impls}"));
+    });
+}
+
 fn check_static_item<'ast>(cx: &'ast MarkerContext<'ast>, item: &'ast StaticItem<'ast>) {
     if let Some(name) = item.ident() {
         let name = name.name();
@@ -1,5 +1,5 @@
-use marker_api::prelude::*;
-use marker_utils::visitor::BoolTraversable;
+use marker_api::{ast::FormatArg, prelude::*};
+use marker_utils::{macros, visitor::BoolTraversable};
 
 marker_api::declare_lint! {
     /// # What it does
@@ -8,6 +8,15 @@ marker_api::declare_lint! {
     Warn,
 }
 
+marker_api::declare_lint! {
+    /// # What it does
+    /// Tests the recognition functions from [`marker_utils::macros`]. Every
+    /// recognized expression is reported, if this lint is set to warn.
+    TEST_STD_MACROS,
+    Allow,
+    marker_api::common::MacroReport::All,
+}
+
 pub fn check_item<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
     let ItemKind::Fn(fn_item) = item else { return };
     let Some(ident) = fn_item.ident() else { return };
@@ -26,3 +35,64 @@ pub fn check_item<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
         });
     }
 }
+
+pub fn check_expr<'ast>(cx: &'ast MarkerContext<'ast>, expr: ExprKind<'ast>) {
+    fn snippets(exprs: &[ExprKind<'_>]) -> String {
+        let snippets: Vec<_> = exprs
+            .iter()
+            .map(|expr| format!("`{}`", expr.span().snippet_or("..")))
+            .collect();
+        snippets.join(", ")
+    }
+
+    fn message(message: Option<&macros::PanicMessage<'_>>) -> String {
+        match message {
+            Some(macros::PanicMessage::Format(format_args)) => {
+                let args: Vec<_> = format_args.args().iter().map(FormatArg::expr).collect();
+                format!(
+                    "Format(`{}`, args: [{}])",
+                    format_args.format_str_span().snippet_or(".."),
+                    snippets(&args)
+                )
+            },
+            Some(macros::PanicMessage::Display(expr)) => format!("Display(`{}`)", expr.span().snippet_or("..")),
+            Some(_) => unreachable!(),
+            None => "None".to_string(),
+        }
+    }
+
+    let note = if let Some(assert) = macros::assert_expn(expr) {
+        format!(
+            "{:?} (debug: {}), args: [{}], message: {}",
+            assert.kind(),
+            assert.is_debug(),
+            snippets(assert.args()),
+            message(assert.message())
+        )
+    } else if let Some(matches) = macros::matches_expn(expr) {
+        format!(
+            "matches, scrutinee: `{}`, pat: `{}`, guard: {:?}",
+            matches.scrutinee().span().snippet_or(".."),
+            matches.pat().span().snippet_or(".."),
+            matches.guard().map(|guard| guard.span().snippet_or(".."))
+        )
+    } else if let Some(vec) = macros::vec_expn(expr) {
+        match vec {
+            macros::VecExpn::Empty => "vec, empty".to_string(),
+            macros::VecExpn::List(elements) => format!("vec, list: [{}]", snippets(elements)),
+            macros::VecExpn::Repeat { elem, len } => format!("vec, repeat: [{}]", snippets(&[elem, len])),
+            _ => unreachable!(),
+        }
+    } else if let Some(placeholder) = macros::panic_placeholder_expn(expr) {
+        format!("{:?}, message: {}", placeholder.kind(), message(placeholder.message()))
+    } else if let Some(dbg) = macros::dbg_expn(expr) {
+        format!("dbg, args: [{}]", snippets(dbg.args()))
+    } else {
+        return;
+    };
+
+    cx.emit_lint(TEST_STD_MACROS, expr, "recognized std macro")
+        .decorate(|diag| {
+            diag.note(note);
+        });
+}
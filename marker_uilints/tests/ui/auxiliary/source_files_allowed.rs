@@ -0,0 +1 @@
+#![allow(marker::marker_uilints::test_source_files)]
@@ -0,0 +1,4 @@
+//! A module in its own file
+mod inline {}
+
+fn in_module() {}
@@ -0,0 +1,29 @@
+/// Outer doc comment
+/** Outer block doc */
+#[allow(unused)]
+fn documented_fn() {
+    //! Inner doc comment
+}
+
+fn main() {
+    let a = 1; // Trailing comment of `a`
+
+    // SAFETY: The first comment
+    /* A block comment */
+    let _check_comments_before = 2;
+
+    // Separated by code
+    let b = 3;
+    let _check_comments_no_comment = 4;
+
+    let _check_comments_inside = {
+        // Line comment inside
+        let c = /* inline */ a + b;
+        /** A doc comment on an expression */
+        c
+    };
+}
+
+fn same_line() {
+    let _check_comments_same_line = /* not before the statement */ 1; /* before */ let _check_comments_after = 2;
+}
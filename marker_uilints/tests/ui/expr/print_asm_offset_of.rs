@@ -0,0 +1,44 @@
+//@only-target-x86_64
+#![feature(offset_of)]
+#![feature(asm_const)]
+use std::arch::asm;
+use std::mem::offset_of;
+
+struct Outer {
+    first: u8,
+    inner: Inner,
+}
+
+struct Inner {
+    second: u32,
+}
+
+static STATIC: u32 = 1;
+
+extern "C" fn function() {}
+
+fn main() {
+    let _print_offset_of = offset_of!(Outer, first);
+    let _print_offset_of_nested = offset_of!(Outer, inner.second);
+
+    let input: u64 = 1;
+    let output: u64;
+    let mut both: u64 = 2;
+    let split: u64;
+    unsafe {
+        let _print_asm = asm!(
+            "mov {0}, {1}",
+            "add {0:e}, {c} /* {2} */",
+            out(reg) output,
+            in(reg) input,
+            inlateout(reg) input => split,
+            c = const 5,
+            inout("rcx") both,
+            out("rax") _,
+            options(pure, nomem, nostack),
+        );
+        let _print_asm_sym = asm!("/* {} {} */", sym function, sym STATIC, options(att_syntax));
+        let _check_asm_clobber_abis = asm!("/* */", clobber_abi("C"));
+        let _check_asm_clobber_abis_multiple = asm!("/* */", clobber_abi("sysv64", "win64"));
+    }
+}
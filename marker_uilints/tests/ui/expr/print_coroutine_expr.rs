@@ -0,0 +1,22 @@
+#![feature(async_closure)]
+#![feature(coroutines)]
+
+fn main() {
+    let x = 1;
+
+    let _print_async_closure = async move |y: u32| -> u32 {
+        // The body of async closures should be visible to lints
+        let _print_in_async_closure = y;
+        y
+    };
+    let _print_coroutine = static move || {
+        let _print_in_coroutine = x;
+        yield 1;
+        yield 2;
+    };
+    let _ty_async_block = async { 1 };
+    let _ty_async_move_block = async move { x };
+    let _ty_coroutine = || {
+        yield 3;
+    };
+}
@@ -0,0 +1,15 @@
+fn main() {
+    let value = 7;
+    let name = "marker";
+
+    let _print_simple = format_args!("Hello {}!", name);
+
+    let _check_format_args_simple = format_args!("Hello {}!", name);
+    let _check_format_args_spec = format_args!("{value:>+#08.3?} {0:x} {:1$}", value, 4);
+    let _check_format_args_named = format_args!(r#"{a} "{name}""#, a = value);
+    let _check_format_args_escaped = format_args!("{{{}}} and {0}", value);
+    let _check_format_args_no_args = format_args!("only text");
+    let _check_format_args_concat = format_args!(concat!("{}", "-"), value);
+    // Rustc inlines the literal argument, unless `MARKER_UNFLATTEN_FORMAT_ARGS` is set
+    let _check_format_args_inlined = format_args!("{} {}", 1, value);
+}
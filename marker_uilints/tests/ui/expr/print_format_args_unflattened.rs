@@ -0,0 +1,8 @@
+//@rustc-env:MARKER_UNFLATTEN_FORMAT_ARGS=1
+
+fn main() {
+    let value = 7;
+
+    let _check_format_args_inlined = format_args!("{} {}", 1, value);
+    let _check_format_args_nested = format_args!("{} {}", format_args!("{}", value), "text");
+}
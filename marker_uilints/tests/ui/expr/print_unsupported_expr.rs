@@ -0,0 +1,16 @@
+#![feature(inline_const)]
+#![feature(box_patterns)]
+#![feature(type_ascription)]
+
+fn main() {
+    let _print_const_block = const { 1 + 2 };
+    let _print_const_block_again = const { 3 };
+
+    let boxed = Box::new(1);
+    let _print_box_pat = match boxed {
+        box 1 => 1,
+        _ => 2,
+    };
+
+    let _print_type_ascription = std::prelude::v1::type_ascribe!(4, u8);
+}
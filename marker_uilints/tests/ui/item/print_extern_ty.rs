@@ -0,0 +1,13 @@
+#![feature(extern_types)]
+
+extern "C" {
+    type PrintMeHandle;
+}
+
+fn get_handle() -> &'static PrintMeHandle {
+    unimplemented!()
+}
+
+fn main() {
+    let _ty = get_handle();
+}
@@ -0,0 +1,13 @@
+const FIND_ME_DEFAULT: i32 = 0;
+
+#[allow(marker::marker_uilints::test_lint_group)]
+const FIND_ME_ALLOW: i32 = 0;
+
+#[deny(marker::marker_uilints::test_lint_group)]
+const FIND_ME_DENY: i32 = 0;
+
+#[allow(marker::marker_uilints::test_lint_group)]
+mod allowed {
+    #[warn(marker::marker_uilints::item_with_test_name)]
+    const FIND_ME_WARN: i32 = 0;
+}
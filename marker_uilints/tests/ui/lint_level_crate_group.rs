@@ -0,0 +1,9 @@
+//@rustc-env:MARKER_LINT_LEVELS==deny:marker::marker_uilints::*,allow:marker::marker_uilints::print_every_expr,allow:marker::marker_uilints::test_source_files
+// The level of all lints of a lint crate can be set on the command line, like
+// `cargo marker -D marker::marker_uilints::*`. Levels of specific lints, which
+// are specified afterwards, and attributes take precedence.
+
+const FIND_ME_GROUP_DENY: i32 = 0;
+
+#[allow(marker::marker_uilints::item_with_test_name)]
+const FIND_ME_ALLOW: i32 = 0;
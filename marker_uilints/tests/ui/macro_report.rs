@@ -0,0 +1,20 @@
+macro_rules! local_macro {
+    () => {
+        1 + 2
+    };
+}
+
+macro_rules! wrapper_macro {
+    ($e:expr) => {
+        $e
+    };
+}
+
+fn main() {
+    let _macro_report_user_code = 4 + 5;
+    let _macro_report_local = local_macro!();
+    let _macro_report_vec = vec![1, 2, 3];
+    let _macro_report_format = format!("{}", 12);
+    let _macro_report_assert = assert!(true);
+    let _macro_report_arg = wrapper_macro!(6 + 7);
+}
@@ -0,0 +1,17 @@
+#![feature(associated_type_bounds)]
+#![feature(associated_const_equality)]
+#![feature(generic_arg_infer)]
+
+use std::fmt::Debug;
+
+pub trait Matrix {
+    const N: usize;
+}
+
+fn print_me_constraint(_: impl Iterator<Item: Debug>) {}
+
+fn print_me_const_binding(_: impl Matrix<N = 3>) {}
+
+fn main() {
+    let _print_infer: Vec<u8> = Vec::<_>::new();
+}
@@ -0,0 +1,19 @@
+macro_rules! local_macro {
+    () => {
+        1 + 2
+    };
+}
+
+macro_rules! nested_macro {
+    () => {
+        local_macro!()
+    };
+}
+
+fn main() {
+    let _macro_local = local_macro!();
+    let _macro_nested = nested_macro!();
+    let _macro_vec = vec![1, 2, 3];
+    let _macro_format = format!("{}", 12);
+    let _macro_builtin = assert!(true);
+}
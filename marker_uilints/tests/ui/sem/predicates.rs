@@ -0,0 +1,23 @@
+#![feature(associated_const_equality)]
+
+use std::fmt::Debug;
+
+fn print_predicates_simple<'a, 'b: 'a, T: Debug + 'a>(_: &'a T, _: &'b T) {}
+
+fn print_predicates_where<T, I>(_: T, _: I)
+where
+    T: Clone + 'static,
+    I: IntoIterator<Item = u8>,
+    for<'x> &'x T: Debug,
+{
+}
+
+fn print_predicates_unsized<T: ?Sized>(_: &T) {}
+
+trait Matrix {
+    const N: usize;
+}
+
+fn print_predicates_const<T: Matrix<N = 3>>() {}
+
+fn main() {}
@@ -0,0 +1,35 @@
+use std::fmt::Display;
+
+#[derive(Clone)]
+struct Impled;
+
+impl Impled {
+    fn new() -> Self {
+        Impled
+    }
+}
+
+impl Impled {
+    fn other(&self) {}
+}
+
+impl Display for Impled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Impled")
+    }
+}
+
+trait LocalTrait {}
+
+impl LocalTrait for Impled {}
+impl LocalTrait for u32 {}
+impl<T> LocalTrait for Vec<T> {}
+
+struct NotImpled;
+
+fn main() {
+    let _check_impls_impled = Impled::new();
+    let _check_impls_not_impled = NotImpled;
+    let _check_impls_vec = vec![1, 2, 3];
+    let _check_impls_u32 = 7u32;
+}
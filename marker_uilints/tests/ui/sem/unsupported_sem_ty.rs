@@ -0,0 +1,7 @@
+//@rustc-env:MARKER_REPORT_UNSUPPORTED=1
+// Unsupported semantic types are reported at the expression, whose type
+// contains them.
+
+fn main() {
+    let _ty_async_block = async { 1 };
+}
@@ -0,0 +1,13 @@
+#![warn(marker::marker_uilints::test_source_files)]
+
+#[path = "auxiliary/source_files_module.rs"]
+mod module;
+
+#[path = "auxiliary/source_files_allowed.rs"]
+mod allowed;
+
+mod inline {
+    fn in_root_file() {}
+}
+
+fn main() {}
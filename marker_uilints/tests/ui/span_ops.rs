@@ -0,0 +1,14 @@
+macro_rules! add {
+    ($a:expr, $b:expr) => {
+        $a + $b
+    };
+}
+
+fn main() {
+    let a = 1;
+    let b = 2;
+    let _check_span_ops_binary = a  +  b;
+    let _check_span_ops_multi_line = a
+        + b; // Trailing comment
+    let _check_span_ops_macro = add!(a, b);
+}
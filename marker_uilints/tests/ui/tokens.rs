@@ -0,0 +1,12 @@
+fn main() {
+    let _check_tokens_tuple = (1, 2,);
+    let _check_tokens_trivia = {
+        // Comment
+        r#try::<'static, u8>(1) /* block */ != 'c'
+    };
+    let _check_tokens_macro = vec![1];
+}
+
+fn r#try<'a, T: 'a>(_: T) -> char {
+    'a'
+}
@@ -0,0 +1,51 @@
+//@rustc-env:MARKER_UNFLATTEN_FORMAT_ARGS=1
+#![warn(marker::marker_uilints::test_std_macros)]
+
+fn main() {
+    let a = 1;
+    let value = Some(a);
+
+    assert!(a == 1);
+    assert!(a == 1, "msg {}", a);
+    assert!(a == 1, "{}", a);
+    assert_eq!(a, 1);
+    assert_ne!(a, 2, "{a} is not {}", 2);
+    debug_assert!(a == 1, "debug");
+    debug_assert_eq!(a, 1);
+    debug_assert_ne!(a, 2, "hey");
+
+    let _ = matches!(value, Some(1 | 2));
+    let _ = matches!(value, Some(x) if x > 0);
+
+    let _: Vec<u8> = vec![];
+    let _ = vec![1, 2];
+    let _ = vec![a; 3];
+
+    let _ = dbg!();
+    let _ = dbg!(a);
+    let _ = dbg!(a, value);
+
+    if a == 2 {
+        todo!();
+    } else if a == 3 {
+        todo!("later {}", a);
+    } else if a == 4 {
+        unimplemented!();
+    } else if a == 5 {
+        unimplemented!("{}", a);
+    } else if a == 6 {
+        unreachable!();
+    } else if a == 7 {
+        unreachable!("state {a}");
+    }
+
+    // Look-alikes shouldn't be recognized
+    if !(a == 1) {
+        panic!("assertion failed");
+    }
+    let _ = match value {
+        Some(1) => true,
+        _ => false,
+    };
+    let _ = Vec::<u8>::new();
+}
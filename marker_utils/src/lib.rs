@@ -2,7 +2,9 @@
 #![warn(clippy::pedantic)]
 #![warn(clippy::exhaustive_enums)]
 #![allow(clippy::module_name_repetitions)]
+#![allow(clippy::must_use_candidate)]
 #![allow(clippy::unused_self)] // `self` is needed to potentualy change the behavior later
 #![allow(clippy::trivially_copy_pass_by_ref)] // Needed to potentualy change the behavior later
 
+pub mod macros;
 pub mod visitor;
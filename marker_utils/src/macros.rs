@@ -0,0 +1,505 @@
+//! This module contains functions to recognize the expansions of common macros
+//! from the standard library.
+//!
+//! Macros like [`assert_eq!`] or [`matches!`] expand into deeply nested `match`
+//! and `if` expressions. The functions in this module take the root expression
+//! of such an expansion and return the arguments, that were passed to the macro.
+//! All other expressions, including the nested expressions of a recognized
+//! expansion, are rejected. This makes it safe to call these functions from
+//! [`LintPass::check_expr`](marker_api::LintPass::check_expr).
+//!
+//! ```ignore
+//! fn check_expr<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, expr: ExprKind<'ast>) {
+//!     if let Some(assert) = marker_utils::macros::assert_expn(expr) {
+//!         if let [cond] = assert.args() {
+//!             // Check the condition of `assert!(cond)`
+//!         }
+//!     }
+//! }
+//! ```
+
+use marker_api::{
+    ast::{BlockExpr, FormatArgsExpr, MatchArm, UnaryOpKind},
+    prelude::*,
+    span::{ExpnInfo, SpanSource},
+};
+
+/// The crates, that define the macros recognized by this module.
+const STD_CRATES: &[&str] = &["core", "alloc", "std"];
+
+/// The different kinds of assertion macros, recognized by [`assert_expn`].
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum AssertKind {
+    /// An [`assert!`] or [`debug_assert!`] macro.
+    Assert,
+    /// An [`assert_eq!`] or [`debug_assert_eq!`] macro.
+    AssertEq,
+    /// An [`assert_ne!`] or [`debug_assert_ne!`] macro.
+    AssertNe,
+}
+
+/// The expansion of an assertion macro, returned by [`assert_expn`].
+///
+/// ```
+/// # let (a, b) = (1, 1);
+/// assert!(a == b);
+/// //      ^^^^^^ args
+///
+/// debug_assert_ne!(a, 2, "`a` is {} and shouldn't be 2", a);
+/// //               ^  ^  ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ message
+/// //               args
+/// ```
+#[derive(Debug)]
+pub struct AssertExpn<'ast> {
+    kind: AssertKind,
+    is_debug: bool,
+    args: Vec<ExprKind<'ast>>,
+    message: Option<PanicMessage<'ast>>,
+}
+
+impl<'ast> AssertExpn<'ast> {
+    pub fn kind(&self) -> AssertKind {
+        self.kind
+    }
+
+    /// Returns `true`, if this is one of the `debug_assert*!` macros.
+    pub fn is_debug(&self) -> bool {
+        self.is_debug
+    }
+
+    /// The asserted expressions. This is the condition for [`AssertKind::Assert`]
+    /// and the left and right operand for [`AssertKind::AssertEq`] and
+    /// [`AssertKind::AssertNe`].
+    pub fn args(&self) -> &[ExprKind<'ast>] {
+        &self.args
+    }
+
+    /// The custom panic message, if one was provided.
+    pub fn message(&self) -> Option<&PanicMessage<'ast>> {
+        self.message.as_ref()
+    }
+}
+
+/// A custom panic message, passed to a macro like [`assert!`] or [`todo!`].
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone)]
+pub enum PanicMessage<'ast> {
+    /// The message was provided as format arguments, like `"{} is invalid", value`.
+    Format(&'ast FormatArgsExpr<'ast>),
+    /// The standard library displays single values directly, without creating
+    /// format arguments. This is the case for messages like `"{}", value`. This
+    /// variant contains the displayed expression.
+    Display(ExprKind<'ast>),
+}
+
+/// Recognizes the expansions of [`assert!`], [`assert_eq!`], [`assert_ne!`] and
+/// their `debug_*` counterparts.
+pub fn assert_expn(expr: ExprKind<'_>) -> Option<AssertExpn<'_>> {
+    for (kind, name, debug_name) in [
+        (AssertKind::Assert, "assert", "debug_assert"),
+        (AssertKind::AssertEq, "assert_eq", "debug_assert_eq"),
+        (AssertKind::AssertNe, "assert_ne", "debug_assert_ne"),
+    ] {
+        if let Some(expn) = std_expn_of(expr.span(), name, &[]) {
+            // The `debug_assert*!` macros are implemented with the non-debug versions
+            if is_nested_in(expn, debug_name) {
+                return None;
+            }
+            return assert_expn_of_kind(expr, kind);
+        }
+        if is_expn_of(expr.span(), debug_name, &[]) {
+            // if cfg!(debug_assertions) { assert!(...); }
+            let ExprKind::If(if_expr) = expr else { return None };
+            let ExprKind::BoolLit(_) = if_expr.condition() else {
+                return None;
+            };
+            let ExprKind::Block(block) = if_expr.then() else {
+                return None;
+            };
+            let mut assert = assert_expn_of_kind(single_expr(block)?, kind)?;
+            assert.is_debug = true;
+            return Some(assert);
+        }
+    }
+    None
+}
+
+fn assert_expn_of_kind(expr: ExprKind<'_>, kind: AssertKind) -> Option<AssertExpn<'_>> {
+    if kind == AssertKind::Assert {
+        // if !cond { <panic> }
+        let ExprKind::If(if_expr) = expr else { return None };
+        let ExprKind::UnaryOp(not) = if_expr.condition() else {
+            return None;
+        };
+        if not.kind() != UnaryOpKind::Not || if_expr.els().is_some() {
+            return None;
+        }
+        let ExprKind::Block(then) = if_expr.then() else {
+            return None;
+        };
+        let message = match then.expr() {
+            // The default message is passed as a string literal
+            Some(ExprKind::Call(call)) if is_expn_of(call.span(), "assert", &[]) => None,
+            Some(panic) => Some(panic_message(panic)?),
+            None => return None,
+        };
+        return Some(AssertExpn {
+            kind,
+            is_debug: false,
+            args: vec![not.expr()],
+            message,
+        });
+    }
+
+    // match (&left, &right) {
+    //     (left_val, right_val) => {
+    //         if !(*left_val == *right_val) {
+    //             let kind = AssertKind::Eq;
+    //             assert_failed(kind, &*left_val, &*right_val, None | Some(format_args!(..)));
+    //         }
+    //     }
+    // }
+    let ExprKind::Match(match_expr) = expr else {
+        return None;
+    };
+    let ExprKind::Tuple(scrutinee) = match_expr.scrutinee() else {
+        return None;
+    };
+    let args = scrutinee
+        .elements()
+        .iter()
+        .map(|element| match element {
+            ExprKind::Ref(reference) => Some(reference.expr()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let [arm] = match_expr.arms() else { return None };
+    if args.len() != 2 {
+        return None;
+    }
+
+    let ExprKind::Block(arm_block) = arm.expr() else {
+        return None;
+    };
+    let Some(ExprKind::If(if_expr)) = arm_block.expr() else {
+        return None;
+    };
+    let ExprKind::Block(then) = if_expr.then() else {
+        return None;
+    };
+    let Some(StmtKind::Expr(failed)) = then.stmts().last() else {
+        return None;
+    };
+    let ExprKind::Call(failed) = failed.expr() else {
+        return None;
+    };
+    let [_kind, _left, _right, ExprKind::Ctor(option)] = failed.args() else {
+        return None;
+    };
+    let message = match option.fields() {
+        [] => None,
+        [some] => match some.expr() {
+            ExprKind::FormatArgs(format_args) => Some(PanicMessage::Format(format_args)),
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    Some(AssertExpn {
+        kind,
+        is_debug: false,
+        args,
+        message,
+    })
+}
+
+/// The expansion of a [`matches!`] macro, returned by [`matches_expn`].
+///
+/// ```
+/// # let value = Some(1);
+/// matches!(value, Some(1 | 2) if true);
+/// //       ^^^^^  ^^^^^^^^^^     ^^^^ guard
+/// //       |      pattern
+/// //       scrutinee
+/// ```
+#[derive(Debug)]
+pub struct MatchesExpn<'ast> {
+    scrutinee: ExprKind<'ast>,
+    arm: &'ast MatchArm<'ast>,
+}
+
+impl<'ast> MatchesExpn<'ast> {
+    pub fn scrutinee(&self) -> ExprKind<'ast> {
+        self.scrutinee
+    }
+
+    pub fn pat(&self) -> PatKind<'ast> {
+        self.arm.pat()
+    }
+
+    pub fn guard(&self) -> Option<ExprKind<'ast>> {
+        self.arm.guard()
+    }
+}
+
+/// Recognizes the expansion of a [`matches!`] macro.
+pub fn matches_expn(expr: ExprKind<'_>) -> Option<MatchesExpn<'_>> {
+    // match scrutinee { pat if guard => true, _ => false }
+    let ExprKind::Match(match_expr) = expr else {
+        return None;
+    };
+    if !is_expn_of(expr.span(), "matches", &[]) {
+        return None;
+    }
+    let [arm, _wildcard] = match_expr.arms() else {
+        return None;
+    };
+    Some(MatchesExpn {
+        scrutinee: match_expr.scrutinee(),
+        arm,
+    })
+}
+
+/// The expansion of a [`vec!`] macro, returned by [`vec_expn`].
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone)]
+pub enum VecExpn<'ast> {
+    /// An empty vector, created with `vec![]`.
+    Empty,
+    /// A vector containing the given elements, like `vec![1, 2, 3]`.
+    List(&'ast [ExprKind<'ast>]),
+    /// A vector created from an element and a length, like `vec![elem; len]`.
+    Repeat { elem: ExprKind<'ast>, len: ExprKind<'ast> },
+}
+
+/// Recognizes the expansion of a [`vec!`] macro.
+pub fn vec_expn(expr: ExprKind<'_>) -> Option<VecExpn<'_>> {
+    let ExprKind::Call(call) = expr else { return None };
+    if !is_expn_of(expr.span(), "vec", &[]) {
+        return None;
+    }
+    match call.args() {
+        // Vec::new()
+        [] => Some(VecExpn::Empty),
+        // <[_]>::into_vec(Box::new([elements]))
+        [ExprKind::Call(boxed)] => match boxed.args() {
+            [ExprKind::Array(array)] => Some(VecExpn::List(array.elements())),
+            _ => None,
+        },
+        // vec::from_elem(elem, len)
+        [elem, len] => Some(VecExpn::Repeat { elem: *elem, len: *len }),
+        _ => None,
+    }
+}
+
+/// The different kinds of panicking placeholder macros, recognized by [`panic_placeholder_expn`].
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PanicPlaceholderKind {
+    /// A [`todo!`] macro.
+    Todo,
+    /// An [`unimplemented!`] macro.
+    Unimplemented,
+    /// An [`unreachable!`] macro.
+    Unreachable,
+}
+
+/// The expansion of a panicking placeholder macro, returned by [`panic_placeholder_expn`].
+#[derive(Debug)]
+pub struct PanicPlaceholderExpn<'ast> {
+    kind: PanicPlaceholderKind,
+    message: Option<PanicMessage<'ast>>,
+}
+
+impl<'ast> PanicPlaceholderExpn<'ast> {
+    pub fn kind(&self) -> PanicPlaceholderKind {
+        self.kind
+    }
+
+    /// The custom panic message, if one was provided.
+    pub fn message(&self) -> Option<&PanicMessage<'ast>> {
+        self.message.as_ref()
+    }
+}
+
+/// Recognizes the expansions of [`todo!`], [`unimplemented!`] and [`unreachable!`].
+pub fn panic_placeholder_expn(expr: ExprKind<'_>) -> Option<PanicPlaceholderExpn<'_>> {
+    /// Macros from the standard library, which are used to implement the
+    /// placeholder macros.
+    const WRAPPERS: &[&str] = &[
+        "panic",
+        "panic_2015",
+        "panic_2021",
+        "unreachable_2015",
+        "unreachable_2021",
+    ];
+
+    for (kind, name) in [
+        (PanicPlaceholderKind::Todo, "todo"),
+        (PanicPlaceholderKind::Unimplemented, "unimplemented"),
+        (PanicPlaceholderKind::Unreachable, "unreachable"),
+    ] {
+        match expr {
+            // panicking::panic("not yet implemented")
+            ExprKind::Call(call) if is_expn_of(expr.span(), name, &["unreachable_2015", "unreachable_2021"]) => {
+                let [ExprKind::StrLit(_)] = call.args() else {
+                    return None;
+                };
+                return Some(PanicPlaceholderExpn { kind, message: None });
+            },
+            // { panicking::panic_fmt(format_args!("not yet implemented: {}", format_args!(..))); }
+            ExprKind::Block(_) if is_expn_of(expr.span(), name, WRAPPERS) => {
+                return Some(PanicPlaceholderExpn {
+                    kind,
+                    message: Some(panic_message(expr)?),
+                });
+            },
+            _ => {},
+        }
+    }
+    None
+}
+
+/// The expansion of a [`dbg!`] macro, returned by [`dbg_expn`].
+#[derive(Debug)]
+pub struct DbgExpn<'ast> {
+    args: Vec<ExprKind<'ast>>,
+}
+
+impl<'ast> DbgExpn<'ast> {
+    /// The expressions, that are printed and returned by the macro. This is
+    /// empty for `dbg!()`.
+    pub fn args(&self) -> &[ExprKind<'ast>] {
+        &self.args
+    }
+}
+
+/// Recognizes the expansion of a [`dbg!`] macro.
+pub fn dbg_expn(expr: ExprKind<'_>) -> Option<DbgExpn<'_>> {
+    if let Some(expn) = std_expn_of(expr.span(), "dbg", &[]) {
+        // `dbg!(a, b)` expands to a tuple of `dbg!(a)` and `dbg!(b)`
+        if is_nested_in(expn, "dbg") {
+            return None;
+        }
+        let args = match expr {
+            ExprKind::Match(_) => vec![dbg_arg(expr)?],
+            ExprKind::Tuple(tuple) => tuple
+                .elements()
+                .iter()
+                .map(|element| dbg_arg(*element))
+                .collect::<Option<_>>()?,
+            _ => return None,
+        };
+        return Some(DbgExpn { args });
+    }
+
+    // `dbg!()` directly expands to an `eprintln!()` call. The `eprintln!()`
+    // of `dbg!(value)` is distinguished by the number of printed values.
+    let ExprKind::Block(block) = expr else { return None };
+    let SpanSource::Macro(eprintln) = expr.span().source() else {
+        return None;
+    };
+    if !is_std_macro(eprintln, "eprintln") || !is_nested_in(eprintln, "dbg") {
+        return None;
+    }
+    let ExprKind::Call(print) = single_expr(block)? else {
+        return None;
+    };
+    match print.args() {
+        [ExprKind::FormatArgs(format_args)] if format_args.args().len() == 3 => Some(DbgExpn { args: vec![] }),
+        _ => None,
+    }
+}
+
+fn dbg_arg(expr: ExprKind<'_>) -> Option<ExprKind<'_>> {
+    // match value { tmp => { eprintln!(..); tmp } }
+    let ExprKind::Match(match_expr) = expr else {
+        return None;
+    };
+    if !is_expn_of(expr.span(), "dbg", &[]) {
+        return None;
+    }
+    let [_tmp] = match_expr.arms() else { return None };
+    Some(match_expr.scrutinee())
+}
+
+/// Extracts the message from the expansion of a `panic!` macro, that was invoked
+/// with format arguments.
+fn panic_message(expr: ExprKind<'_>) -> Option<PanicMessage<'_>> {
+    // { panicking::panic_fmt(format_args!(..)); }
+    // { fn panic_cold_display<T: Display>(arg: &T) -> ! { .. } panic_cold_display(&value); }
+    let ExprKind::Block(block) = expr else { return None };
+    let Some(StmtKind::Expr(stmt)) = block.stmts().last() else {
+        return None;
+    };
+    let ExprKind::Call(call) = stmt.expr() else {
+        return None;
+    };
+    match call.args() {
+        [ExprKind::FormatArgs(format_args)] => {
+            // Macros like `todo!` prefix the user message, by wrapping it in
+            // another `format_args!` call.
+            match format_args.args() {
+                [arg] if is_from_std_expn(format_args.format_str_span()) => match arg.expr() {
+                    ExprKind::FormatArgs(inner) => Some(PanicMessage::Format(inner)),
+                    _ => Some(PanicMessage::Format(format_args)),
+                },
+                _ => Some(PanicMessage::Format(format_args)),
+            }
+        },
+        [ExprKind::Ref(reference)] => Some(PanicMessage::Display(reference.expr())),
+        _ => None,
+    }
+}
+
+/// Returns the only statement or trailing expression of the given block.
+fn single_expr<'ast>(block: &'ast BlockExpr<'ast>) -> Option<ExprKind<'ast>> {
+    match (block.stmts(), block.expr()) {
+        ([], Some(expr)) => Some(expr),
+        ([StmtKind::Expr(stmt)], None) => Some(stmt.expr()),
+        _ => None,
+    }
+}
+
+/// Returns `true`, if the given span was directly expanded from the std macro
+/// with the given name. Expansions of the `wrappers` macros are skipped, if
+/// they were invoked by the macro.
+fn is_expn_of(span: &Span<'_>, name: &str, wrappers: &[&str]) -> bool {
+    std_expn_of(span, name, wrappers).is_some()
+}
+
+/// Returns the expansion of the std macro with the given name, if the span
+/// was directly expanded from it. See [`is_expn_of`].
+fn std_expn_of<'ast>(span: &Span<'ast>, name: &str, wrappers: &[&str]) -> Option<&'ast ExpnInfo<'ast>> {
+    let SpanSource::Macro(mut expn) = span.source() else {
+        return None;
+    };
+    loop {
+        if is_std_macro(expn, name) {
+            return Some(expn);
+        }
+        if !wrappers.iter().any(|wrapper| is_std_macro(expn, wrapper)) {
+            return None;
+        }
+        expn = expn.parent()?;
+    }
+}
+
+/// Returns `true`, if the given expansion was created by a macro call, inside
+/// the expansion of the std macro with the given name.
+fn is_nested_in(expn: &ExpnInfo<'_>, name: &str) -> bool {
+    expn.parent().is_some_and(|parent| is_std_macro(parent, name))
+}
+
+fn is_from_std_expn(span: &Span<'_>) -> bool {
+    matches!(
+        span.source(),
+        SpanSource::Macro(expn) if STD_CRATES.contains(&expn.macro_info().crate_name())
+    )
+}
+
+fn is_std_macro(expn: &ExpnInfo<'_>, name: &str) -> bool {
+    let info = expn.macro_info();
+    info.name() == name && STD_CRATES.contains(&info.crate_name())
+}
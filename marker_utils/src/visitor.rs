@@ -167,7 +167,11 @@ pub fn traverse_item<'ast, B>(
                 traverse_item(cx, visitor, ext_item.as_item())?;
             }
         },
-        ItemKind::ExternCrate(_) | ItemKind::Use(_) | ItemKind::Unstable(_) | ItemKind::TyAlias(_) => {
+        ItemKind::ExternCrate(_)
+        | ItemKind::Use(_)
+        | ItemKind::Unstable(_)
+        | ItemKind::TyAlias(_)
+        | ItemKind::ExternTy(_) => {
             // These items have no sub nodes, which are visited by this visitor
         },
         _ => unreachable!("all items are covered"),
@@ -238,6 +242,12 @@ pub fn traverse_expr<'ast, B>(
                 traverse_body(cx, visitor, body)?;
             }
         },
+        ExprKind::Coroutine(e) => {
+            if let VisitorScope::AllBodies = visitor.scope() {
+                let body = cx.ast().body(e.body_id());
+                traverse_body(cx, visitor, body)?;
+            }
+        },
         ExprKind::UnaryOp(e) => {
             traverse_expr(cx, visitor, e.expr())?;
         },
@@ -352,12 +362,39 @@ pub fn traverse_expr<'ast, B>(
         ExprKind::Await(e) => {
             traverse_expr(cx, visitor, e.expr())?;
         },
+        ExprKind::FormatArgs(e) => {
+            for arg in e.args() {
+                traverse_expr(cx, visitor, arg.expr())?;
+            }
+        },
+        ExprKind::InlineAsm(e) => {
+            for operand in e.operands() {
+                match operand {
+                    ast::AsmOperandKind::Reg(reg) => {
+                        if let Some(in_expr) = reg.in_expr() {
+                            traverse_expr(cx, visitor, in_expr)?;
+                        }
+                        // The output of `inout` operands is the same expression as the input
+                        if reg.direction() != ast::AsmDirection::InOut {
+                            if let Some(out_expr) = reg.out_expr() {
+                                traverse_expr(cx, visitor, out_expr)?;
+                            }
+                        }
+                    },
+                    ast::AsmOperandKind::Const(c) | ast::AsmOperandKind::SymFn(c) => {
+                        traverse_expr(cx, visitor, c.expr().expr())?;
+                    },
+                    _ => {},
+                }
+            }
+        },
         ExprKind::IntLit(_)
         | ExprKind::FloatLit(_)
         | ExprKind::StrLit(_)
         | ExprKind::CharLit(_)
         | ExprKind::BoolLit(_)
         | ExprKind::Unstable(_)
+        | ExprKind::OffsetOf(_)
         | ExprKind::Path(_)
         | ExprKind::Continue(_) => {
             // These expressions have no sub nodes, which are visited by this visitor